@@ -3,10 +3,11 @@
 /// Since the datatypes for the PointCloud2 message are very limited,
 /// we need to encode the enum into a supported type.
 /// This needs some manual work to tell the library how to encode and decode the enum.
+/// Here, the label implements `GetFieldDatatype` and `FromBytes` like any other supported field type.
+/// For a shorter route via `PointData::get_field_value`, see the `FromFieldValue` trait.
 ///
-/// Important Note: This example is only possible with disabled `derive` feature,
-/// because the library (currently) does not know the size of your chosen supported type at compile time.
-/// This makes direct copies impossible.
+/// Important Note: The point is not `Copy`, so it only converts with the iterator functions that decode field by field.
+/// Copying the raw bytes with the `_vec` functions could produce invalid enum values from received messages.
 use ros_pointcloud2::prelude::*;
 
 #[derive(Debug, PartialEq, Clone, Default, Copy)]
@@ -15,6 +16,7 @@ enum Label {
     Human,
     Deer,
     Car,
+    Unknown,
 }
 
 // Define a custom point with an enum.
@@ -36,6 +38,7 @@ impl From<Label> for u8 {
             Label::Human => 0,
             Label::Deer => 1,
             Label::Car => 2,
+            Label::Unknown => u8::MAX,
         }
     }
 }
@@ -46,7 +49,8 @@ impl From<u8> for Label {
             0 => Label::Human,
             1 => Label::Deer,
             2 => Label::Car,
-            _ => panic!("Invalid label"),
+            // Received messages may contain any value.
+            _ => Label::Unknown,
         }
     }
 }
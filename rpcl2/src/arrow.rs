@@ -0,0 +1,244 @@
+//! Conversions between [`PointCloud2Msg`] and columnar Arrow [`RecordBatch`]es. Requires the `arrow` feature.
+//!
+//! Every field of the message becomes one column with the same name, so clouds can be written to Parquet
+//! or queried with DataFusion. Fields with a count of 1 map to primitive columns and fields with a larger count
+//! to fixed size lists of primitives.
+use alloc::format;
+use alloc::string::ToString;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+use ::arrow::array::{make_array, Array, ArrayData, ArrayRef, FixedSizeListArray};
+use ::arrow::buffer::Buffer;
+use ::arrow::datatypes::{DataType, Field, Schema};
+use ::arrow::error::ArrowError;
+use ::arrow::record_batch::RecordBatch;
+
+use crate::ros::PointFieldMsg;
+use crate::{
+    system_endian, Endian, FieldDatatype, MsgConversionError, PointCloud2Msg, PointCloud2MsgBuilder,
+};
+
+fn invalid(err: ArrowError) -> MsgConversionError {
+    MsgConversionError::InvalidArrow(err.to_string())
+}
+
+fn arrow_type(datatype: FieldDatatype) -> DataType {
+    match datatype {
+        FieldDatatype::I8 => DataType::Int8,
+        FieldDatatype::U8 => DataType::UInt8,
+        FieldDatatype::I16 => DataType::Int16,
+        FieldDatatype::U16 => DataType::UInt16,
+        FieldDatatype::I32 => DataType::Int32,
+        FieldDatatype::U32 => DataType::UInt32,
+        FieldDatatype::F32 | FieldDatatype::RGB => DataType::Float32,
+        FieldDatatype::F64 => DataType::Float64,
+    }
+}
+
+fn field_datatype(datatype: &DataType) -> Option<FieldDatatype> {
+    match datatype {
+        DataType::Int8 => Some(FieldDatatype::I8),
+        DataType::UInt8 => Some(FieldDatatype::U8),
+        DataType::Int16 => Some(FieldDatatype::I16),
+        DataType::UInt16 => Some(FieldDatatype::U16),
+        DataType::Int32 => Some(FieldDatatype::I32),
+        DataType::UInt32 => Some(FieldDatatype::U32),
+        DataType::Float32 => Some(FieldDatatype::F32),
+        DataType::Float64 => Some(FieldDatatype::F64),
+        _ => None,
+    }
+}
+
+/// Check if all values are finite, given as native endian bytes of a float datatype.
+fn all_finite(datatype: FieldDatatype, bytes: &[u8]) -> bool {
+    match datatype {
+        FieldDatatype::F32 => bytes
+            .chunks_exact(4)
+            .all(|value| f32::from_ne_bytes([value[0], value[1], value[2], value[3]]).is_finite()),
+        FieldDatatype::F64 => bytes.chunks_exact(8).all(|value| {
+            let mut buffer = [0; 8];
+            buffer.copy_from_slice(value);
+            f64::from_ne_bytes(buffer).is_finite()
+        }),
+        _ => true,
+    }
+}
+
+impl PointCloud2Msg {
+    /// Convert the cloud into an Arrow [`RecordBatch`] with one column per field. Requires the `arrow` feature.
+    ///
+    /// The values are converted to the endian of the system, which Arrow expects. Padding, the header and
+    /// the dimensions of organized clouds are not part of the batch, so every point becomes one row.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let msg = PointCloud2Msg::try_from_vec(vec![PointXYZI::new(1.0, 2.0, 3.0, 0.5)]).unwrap();
+    /// let batch = msg.to_arrow().unwrap();
+    ///
+    /// assert_eq!(batch.num_rows(), 1);
+    /// assert_eq!(batch.num_columns(), 4);
+    /// assert!(batch.column_by_name("intensity").is_some());
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::InvalidArrow`] if the batch can not be created, e.g. for a message without fields.
+    /// Also returns an error if a field datatype is unsupported, a field does not fit into the point or the buffer is too short.
+    pub fn to_arrow(&self) -> Result<RecordBatch, MsgConversionError> {
+        self.check_data_len()?;
+        let point_count = self.point_count();
+        let swap = self.endian != system_endian();
+
+        let mut schema_fields = Vec::with_capacity(self.fields.len());
+        let mut columns: Vec<ArrayRef> = Vec::with_capacity(self.fields.len());
+        for field in self.fields.iter() {
+            let datatype = FieldDatatype::try_from(field)?;
+            if field.count == 0 {
+                return Err(MsgConversionError::UnsupportedFieldCount);
+            }
+            let size = datatype.size();
+            let count = field.count as usize;
+            let start = field.offset as usize;
+            let width = size * count;
+            if start + width > self.point_step as usize {
+                return Err(MsgConversionError::DataLengthMismatch);
+            }
+
+            let mut bytes = Vec::with_capacity(point_count * width);
+            for idx in 0..point_count {
+                let offset = self.point_offset(idx) + start;
+                bytes.extend_from_slice(&self.data[offset..offset + width]);
+            }
+            if swap && size > 1 {
+                bytes
+                    .chunks_exact_mut(size)
+                    .for_each(|value| value.reverse());
+            }
+
+            let values = ArrayData::builder(arrow_type(datatype))
+                .len(point_count * count)
+                .add_buffer(Buffer::from_slice_ref(&bytes))
+                .build()
+                .map_err(invalid)?;
+            let values = make_array(values);
+            let column: ArrayRef = if count == 1 {
+                values
+            } else {
+                let item = Arc::new(Field::new("item", arrow_type(datatype), false));
+                let list = FixedSizeListArray::try_new(item, i32::try_from(count)?, values, None)
+                    .map_err(invalid)?;
+                Arc::new(list)
+            };
+
+            schema_fields.push(Field::new(
+                field.name.clone(),
+                column.data_type().clone(),
+                false,
+            ));
+            columns.push(column);
+        }
+
+        RecordBatch::try_new(Arc::new(Schema::new(schema_fields)), columns).map_err(invalid)
+    }
+
+    /// Create an unorganized [`PointCloud2Msg`] from an Arrow [`RecordBatch`]. Requires the `arrow` feature.
+    ///
+    /// Every column becomes a field in the order of the batch without padding between them. Columns of primitive numbers
+    /// that are supported by PointCloud2 and fixed size lists of them are accepted. The message uses the endian of the system
+    /// and is marked as sparse if a float value is not finite.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let cloud_points = vec![PointXYZI::new(1.0, 2.0, 3.0, 0.5), PointXYZI::new(4.0, 5.0, 6.0, 1.5)];
+    /// let msg = PointCloud2Msg::try_from_vec(cloud_points.clone()).unwrap();
+    ///
+    /// let back = PointCloud2Msg::try_from_arrow(&msg.to_arrow().unwrap()).unwrap();
+    /// let points: Vec<PointXYZI> = back.try_into_iter().unwrap().collect();
+    /// assert_eq!(points, cloud_points);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::InvalidArrow`] if a column has an unsupported type or contains null values.
+    /// Also returns an error if the batch has no columns or is too large for the message.
+    pub fn try_from_arrow(batch: &RecordBatch) -> Result<Self, MsgConversionError> {
+        let schema = batch.schema();
+        let point_count = batch.num_rows();
+
+        let mut fields = Vec::with_capacity(batch.num_columns());
+        let mut columns = Vec::with_capacity(batch.num_columns());
+        let mut point_step: u32 = 0;
+        for (schema_field, column) in schema.fields().iter().zip(batch.columns()) {
+            let (values, count) = match column.data_type() {
+                DataType::FixedSizeList(_, count) => {
+                    let list = column
+                        .as_any()
+                        .downcast_ref::<FixedSizeListArray>()
+                        .ok_or(MsgConversionError::InvalidFieldFormat)?;
+                    (list.values().clone(), u32::try_from(*count)?)
+                }
+                _ => (column.clone(), 1),
+            };
+
+            if column.null_count() > 0 || values.null_count() > 0 {
+                return Err(MsgConversionError::InvalidArrow(format!(
+                    "column {} contains null values",
+                    schema_field.name()
+                )));
+            }
+            let datatype = field_datatype(values.data_type()).ok_or_else(|| {
+                MsgConversionError::InvalidArrow(format!(
+                    "column {} has the unsupported type {}",
+                    schema_field.name(),
+                    column.data_type()
+                ))
+            })?;
+            if count == 0 {
+                return Err(MsgConversionError::UnsupportedFieldCount);
+            }
+
+            fields.push(PointFieldMsg {
+                name: schema_field.name().clone(),
+                offset: point_step,
+                datatype: datatype.into(),
+                count,
+            });
+            let width = count
+                .checked_mul(datatype.size() as u32)
+                .ok_or(MsgConversionError::NumberConversion)?;
+            columns.push((values.to_data(), datatype, width as usize));
+            point_step = point_step
+                .checked_add(width)
+                .ok_or(MsgConversionError::NumberConversion)?;
+        }
+
+        let row_step = u32::try_from(point_count)?
+            .checked_mul(point_step)
+            .ok_or(MsgConversionError::NumberConversion)?;
+        let mut data = vec![0; row_step as usize];
+        let mut dense = true;
+        for (field, (values, datatype, width)) in fields.iter().zip(columns.iter()) {
+            let start = values.offset() * datatype.size();
+            let bytes = &values.buffers()[0].as_slice()[start..start + point_count * width];
+            dense &= all_finite(*datatype, bytes);
+
+            for (idx, value) in bytes.chunks_exact(*width).enumerate() {
+                let offset = idx * point_step as usize + field.offset as usize;
+                data[offset..offset + width].copy_from_slice(value);
+            }
+        }
+
+        PointCloud2MsgBuilder::new()
+            .fields(fields)
+            .point_step(point_step)
+            .width(u32::try_from(point_count)?)
+            .row_step(row_step)
+            .endian(system_endian() == Endian::Big)
+            .dense(dense)
+            .data(data)
+            .build()
+    }
+}
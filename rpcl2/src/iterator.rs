@@ -1,10 +1,9 @@
 //! Iterator implementations for [`PointCloud2Msg`] including a parallel iterator for rayon.
 use crate::{
-    Endian, FieldDatatype, MsgConversionError, PointCloud2Msg, PointConvertible, PointData,
-    RPCL2Point,
+    ColorDecode, Endian, FieldDatatype, MsgConversionError, PointCloud2Msg, PointConvertible,
+    PointData, RPCL2Point,
 };
 
-use alloc::borrow::ToOwned;
 use alloc::string::String;
 use alloc::vec::Vec;
 
@@ -30,6 +29,7 @@ where
     C: PointConvertible<N>,
 {
     iteration: usize,
+    /// Exclusive end of the remaining points.
     iteration_back: usize,
     data: ByteBufferView<N>,
     _phantom: core::marker::PhantomData<C>,
@@ -41,7 +41,7 @@ where
     C: PointConvertible<N> + Send + Sync,
 {
     fn len(&self) -> usize {
-        self.data.len()
+        self.iteration_back - self.iteration
     }
 }
 
@@ -51,12 +51,12 @@ where
     C: PointConvertible<N> + Send + Sync,
 {
     fn next_back(&mut self) -> Option<Self::Item> {
-        if self.iteration_back < self.iteration {
+        if self.iteration_back <= self.iteration {
             return None; // iteration finished
         }
 
-        let p = self.data.point_at(self.iteration_back);
         self.iteration_back -= 1;
+        let p = self.data.point_at(self.iteration_back);
         Some(C::from(p))
     }
 }
@@ -144,18 +144,12 @@ where
     type Item = C;
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let buf_len = self.data.len();
-        (buf_len, Some(buf_len))
+        let remaining = self.iteration_back - self.iteration;
+        (remaining, Some(remaining))
     }
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.iteration >= self.data.len() || self.iteration_back < self.iteration {
-            return None; // iteration finished
-        }
-
-        let p = self.data.point_at(self.iteration);
-        self.iteration += 1;
-        Some(C::from(p))
+        self.next_raw().map(C::from)
     }
 }
 
@@ -165,20 +159,28 @@ struct ByteBufferView<const N: usize> {
     #[cfg(not(feature = "rayon"))]
     data: Vec<u8>,
     start_point_idx: usize,
+    /// Exclusive end of the points in the view.
     end_point_idx: usize,
     point_step_size: usize,
-    offsets: [usize; N],
+    /// Points per row and bytes per row, which differ from `width * point_step_size` if the rows are padded.
+    width: usize,
+    row_step_size: usize,
+    /// Offsets of the fields in a point or `None` for fields that are read as zero.
+    offsets: [Option<usize>; N],
     pdata: Vec<(String, FieldDatatype)>,
     endian: Endian,
 }
 
 impl<const N: usize> ByteBufferView<N> {
+    #[allow(clippy::too_many_arguments)]
     fn new(
         data: Vec<u8>,
         point_step_size: usize,
+        width: usize,
+        row_step_size: usize,
         start_point_idx: usize,
         end_point_idx: usize,
-        offsets: [usize; N],
+        offsets: [Option<usize>; N],
         pdata: Vec<(String, FieldDatatype)>,
         endian: Endian,
     ) -> Self {
@@ -190,6 +192,8 @@ impl<const N: usize> ByteBufferView<N> {
             start_point_idx,
             end_point_idx,
             point_step_size,
+            width,
+            row_step_size,
             offsets,
             pdata,
             endian,
@@ -198,24 +202,32 @@ impl<const N: usize> ByteBufferView<N> {
 
     #[inline]
     fn len(&self) -> usize {
-        self.end_point_idx - self.start_point_idx + 1
+        self.end_point_idx - self.start_point_idx
     }
 
     #[inline]
     fn point_at(&self, idx: usize) -> RPCL2Point<N> {
-        let offset = (self.start_point_idx + idx) * self.point_step_size;
+        let point_idx = self.start_point_idx + idx;
+        let offset = if self.row_step_size == self.width * self.point_step_size {
+            point_idx * self.point_step_size
+        } else {
+            (point_idx / self.width) * self.row_step_size
+                + (point_idx % self.width) * self.point_step_size
+        };
         let mut pdata = [PointData::default(); N];
         pdata
             .iter_mut()
             .zip(self.offsets.iter())
             .zip(self.pdata.iter())
             .for_each(|((pdata_entry, in_point_offset), (_, pdata_type))| {
-                *pdata_entry = PointData::from_buffer(
-                    &self.data,
-                    offset + in_point_offset,
-                    *pdata_type,
-                    self.endian,
-                );
+                if let Some(in_point_offset) = in_point_offset {
+                    *pdata_entry = PointData::from_buffer(
+                        &self.data,
+                        offset + in_point_offset,
+                        *pdata_type,
+                        self.endian,
+                    );
+                }
             });
 
         pdata.into()
@@ -226,8 +238,10 @@ impl<const N: usize> ByteBufferView<N> {
         Self {
             data: self.data.clone(),
             start_point_idx: start,
-            end_point_idx: start + size - 1,
+            end_point_idx: start + size,
             point_step_size: self.point_step_size,
+            width: self.width,
+            row_step_size: self.row_step_size,
             offsets: self.offsets,
             pdata: self.pdata.clone(),
             endian: self.endian,
@@ -260,86 +274,145 @@ where
     /// The theoretical time complexity is O(n) where n is the number of fields defined in the message for a single point which is typically small.
     /// It therefore has a constant time complexity O(1) for practical purposes.
     fn try_from(cloud: PointCloud2Msg) -> Result<Self, Self::Error> {
+        Self::from_msg(cloud, false, ColorDecode::Packed)
+    }
+}
+
+impl<const N: usize, C> PointCloudIterator<N, C>
+where
+    C: PointConvertible<N>,
+{
+    /// Create the iterator with the fields in the order of the layout of `C`.
+    ///
+    /// With `lossy`, fields of `C` that are missing in the message are read as zero, except for `x`, `y` and `z`.
+    /// With [`ColorDecode::Channels`], missing `r`, `g` and `b` fields are read from the bytes of a packed `rgb` field.
+    pub(crate) fn from_msg(
+        cloud: PointCloud2Msg,
+        lossy: bool,
+        color: ColorDecode,
+    ) -> Result<Self, MsgConversionError> {
+        crate::check_point_size::<N>()?;
+
         let fields_only = crate::ordered_field_names::<N, C>();
+        let field_elements = crate::ordered_field_elements::<N, C>();
 
-        let not_found_fieldnames = fields_only
+        let packed_rgb = match color {
+            ColorDecode::Packed => None,
+            ColorDecode::Channels => cloud.fields.iter().find(|field| field.name == "rgb"),
+        };
+        // Byte of a channel in the packed value, which is stored as 0x00RRGGBB.
+        let channel_byte = |name: &str| {
+            let le_byte = match name {
+                "r" => 2,
+                "g" => 1,
+                "b" => 0,
+                _ => return None,
+            };
+            packed_rgb.map(|_| match cloud.endian {
+                Endian::Little => le_byte,
+                Endian::Big => 3 - le_byte,
+            })
+        };
+
+        let names_not_found = fields_only
             .iter()
-            .map(|name| {
-                let found = cloud.fields.iter().any(|field| field.name == *name);
-                (name, found)
+            .filter(|name| {
+                let required = !lossy || matches!(name.as_ref(), "x" | "y" | "z");
+                required
+                    && !cloud.fields.iter().any(|field| field.name == **name)
+                    && channel_byte(name).is_none()
             })
-            .filter(|(_, found)| !*found)
+            .cloned()
             .collect::<Vec<_>>();
 
-        if !not_found_fieldnames.is_empty() {
-            let names_not_found = not_found_fieldnames
-                .into_iter()
-                .map(|(name, _)| (*name).to_owned())
-                .collect::<Vec<String>>();
+        if !names_not_found.is_empty() {
             return Err(MsgConversionError::FieldsNotFound(names_not_found));
         }
 
-        let mut pdata_with_offsets = Vec::with_capacity(N);
-        for field in cloud.fields.iter() {
-            if fields_only.contains(&field.name) {
-                pdata_with_offsets.push((
-                    field.name.clone(),
-                    field.datatype.try_into()?,
-                    field.offset as usize,
-                ));
-            }
-        }
-
-        pdata_with_offsets
-            .sort_unstable_by(|(_, _, offset1), (_, _, offset2)| offset1.cmp(offset2));
-
         debug_assert!(
-            pdata_with_offsets.len() == N,
-            "Not all fields were found in the message. Expected {} but found {}.",
-            N,
-            pdata_with_offsets.len()
+            field_elements.len() == N,
+            "The layout describes {} values but the point has {}.",
+            field_elements.len(),
+            N
         );
 
-        let mut offsets = [usize::default(); N];
+        let mut offsets = [None; N];
         let mut pdata = vec![(String::default(), FieldDatatype::default()); N];
-
-        pdata_with_offsets
-            .into_iter()
-            .zip(pdata.iter_mut())
+        let target: RPCL2Point<N> = C::default().into();
+        for (idx, (((name, element), offset), pdata)) in field_elements
+            .iter()
             .zip(offsets.iter_mut())
-            .for_each(|(((name, datatype, offset), pdata), pdata_offset)| {
-                *pdata = (name, datatype);
-                *pdata_offset = offset;
-            });
+            .zip(pdata.iter_mut())
+            .enumerate()
+        {
+            if let Some(field) = cloud.fields.iter().find(|field| field.name == *name) {
+                if *element >= field.count as usize {
+                    return Err(MsgConversionError::UnsupportedFieldCount);
+                }
+
+                let datatype: FieldDatatype = field.datatype.try_into()?;
+                let field_offset = field.offset as usize + element * datatype.size();
+                if field_offset + datatype.size() > cloud.point_step as usize {
+                    return Err(MsgConversionError::DataLengthMismatch);
+                }
+
+                *offset = Some(field_offset);
+                *pdata = (field.name.clone(), datatype);
+            } else if let (Some(rgb), Some(byte)) = (packed_rgb, channel_byte(name)) {
+                let rgb_size = FieldDatatype::try_from(rgb.datatype)?.size();
+                if target[idx].datatype() != FieldDatatype::U8 || rgb_size != 4 || rgb.count != 1 {
+                    return Err(MsgConversionError::InvalidFieldFormat);
+                }
+
+                let field_offset = rgb.offset as usize + byte;
+                if rgb.offset as usize + rgb_size > cloud.point_step as usize {
+                    return Err(MsgConversionError::DataLengthMismatch);
+                }
+
+                *offset = Some(field_offset);
+                *pdata = (String::from(name.as_ref()), FieldDatatype::U8);
+            }
+        }
 
         let point_step_size = cloud.point_step as usize;
-        let cloud_length = cloud.dimensions.width as usize * cloud.dimensions.height as usize;
-        if point_step_size * cloud_length != cloud.data.len() {
-            return Err(MsgConversionError::DataLengthMismatch);
+        let width = cloud.dimensions.width as usize;
+        let height = cloud.dimensions.height as usize;
+        let cloud_length = cloud
+            .dimensions
+            .checked_len()
+            .ok_or(MsgConversionError::NumberConversion)?;
+        if point_step_size == 0 && cloud_length > 0 {
+            return Err(MsgConversionError::ZeroPointStep);
         }
-
-        let last_offset = match offsets.last() {
-            Some(offset) => *offset,
-            None => return Err(MsgConversionError::DataLengthMismatch),
-        };
-
-        let last_pdata = match pdata.last() {
-            Some(pdata) => pdata,
-            None => return Err(MsgConversionError::DataLengthMismatch),
+        let packed_row_step = width
+            .checked_mul(point_step_size)
+            .ok_or(MsgConversionError::NumberConversion)?;
+
+        // Rows of organized clouds may be padded up to the row step.
+        let row_step_size = if height > 1 {
+            cloud.row_step as usize
+        } else {
+            packed_row_step
         };
-
-        let size_with_last_pdata = last_offset + last_pdata.1.size();
-        if size_with_last_pdata > point_step_size {
+        if row_step_size < packed_row_step {
             return Err(MsgConversionError::DataLengthMismatch);
         }
 
-        let cloud_length = cloud.dimensions.width as usize * cloud.dimensions.height as usize;
+        let expected_data_len = row_step_size
+            .checked_mul(height)
+            .ok_or(MsgConversionError::NumberConversion)?;
+        // Trailing bytes after the declared points are ignored, see `PointCloud2Msg::trailing_bytes`.
+        if expected_data_len > cloud.data.len() {
+            return Err(MsgConversionError::DataLengthMismatch);
+        }
 
         let data = ByteBufferView::new(
             cloud.data,
             point_step_size,
+            width,
+            row_step_size,
             0,
-            cloud_length - 1,
+            cloud_length,
             offsets,
             pdata,
             cloud.endian,
@@ -347,23 +420,30 @@ where
 
         Ok(Self {
             iteration: 0,
-            iteration_back: cloud_length - 1,
+            iteration_back: cloud_length,
             data,
             _phantom: core::marker::PhantomData,
         })
     }
-}
 
-impl<const N: usize, C> PointCloudIterator<N, C>
-where
-    C: PointConvertible<N>,
-{
+    /// The next point before it is converted into `C`.
+    #[inline]
+    pub(crate) fn next_raw(&mut self) -> Option<RPCL2Point<N>> {
+        if self.iteration >= self.iteration_back {
+            return None; // iteration finished
+        }
+
+        let p = self.data.point_at(self.iteration);
+        self.iteration += 1;
+        Some(p)
+    }
+
     #[inline]
     #[must_use]
     fn from_byte_buffer_view(data: ByteBufferView<N>) -> Self {
         Self {
             iteration: 0,
-            iteration_back: data.len() - 1,
+            iteration_back: data.len(),
             data,
             _phantom: core::marker::PhantomData,
         }
@@ -0,0 +1,284 @@
+//! Reading LAS files into a [`PointCloud2Msg`]. Requires the `las` feature.
+//!
+//! Airborne LiDAR and survey data is usually shipped as LAS. The point records of the formats 0 to 10 are mapped onto fields
+//! with the names used in the LAS specification. The coordinates are stored as integers in the file and are converted with the scale
+//! and offset of the file header into `f64` fields `x`, `y` and `z`, so no precision is lost.
+//! Use [`convert_field_type`](PointCloud2Msg::convert_field_type) to get `f32` coordinates for the predefined point types.
+//!
+//! Compressed LAZ files are not supported and return an error.
+use std::io::Read;
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::ros::PointFieldMsg;
+use crate::{FieldDatatype, MsgConversionError, PointCloud2Msg, PointCloud2MsgBuilder};
+
+/// Size of the public header of LAS 1.0 to 1.3, which is extended by LAS 1.4.
+const HEADER_SIZE: usize = 227;
+/// Size of the public header of LAS 1.4 with the 64 bit point count.
+const HEADER_SIZE_1_4: usize = 375;
+
+/// The header values that are needed to read the point records.
+struct LasHeader {
+    point_format: u8,
+    record_length: usize,
+    point_count: u64,
+    scale: [f64; 3],
+    offset: [f64; 3],
+}
+
+fn invalid(reason: &str) -> MsgConversionError {
+    MsgConversionError::InvalidLas(reason.to_string())
+}
+
+fn read_exact<R: Read>(reader: &mut R, buffer: &mut [u8]) -> Result<(), MsgConversionError> {
+    reader
+        .read_exact(buffer)
+        .map_err(|err| MsgConversionError::InvalidLas(err.to_string()))
+}
+
+fn u16_at(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+    let mut value = [0; 4];
+    value.copy_from_slice(&bytes[offset..offset + 4]);
+    u32::from_le_bytes(value)
+}
+
+fn u64_at(bytes: &[u8], offset: usize) -> u64 {
+    let mut value = [0; 8];
+    value.copy_from_slice(&bytes[offset..offset + 8]);
+    u64::from_le_bytes(value)
+}
+
+fn f64_at(bytes: &[u8], offset: usize) -> f64 {
+    f64::from_bits(u64_at(bytes, offset))
+}
+
+fn read_header<R: Read>(reader: &mut R) -> Result<(LasHeader, usize), MsgConversionError> {
+    let mut bytes = vec![0; HEADER_SIZE];
+    read_exact(reader, &mut bytes)?;
+    if &bytes[0..4] != b"LASF" {
+        return Err(invalid("missing LASF signature"));
+    }
+
+    let version = (bytes[24], bytes[25]);
+    let header_size = u16_at(&bytes, 94) as usize;
+    let point_data_offset = u32_at(&bytes, 96) as usize;
+    let point_format = bytes[104];
+    if point_format & 0xc0 != 0 {
+        return Err(invalid("compressed LAZ point records are not supported"));
+    }
+
+    let mut point_count = u32_at(&bytes, 107) as u64;
+    let mut consumed = HEADER_SIZE;
+    if version >= (1, 4) && header_size >= HEADER_SIZE_1_4 {
+        bytes.resize(HEADER_SIZE_1_4, 0);
+        read_exact(reader, &mut bytes[HEADER_SIZE..])?;
+        consumed = HEADER_SIZE_1_4;
+        if point_count == 0 {
+            point_count = u64_at(&bytes, 247);
+        }
+    }
+
+    if point_data_offset < consumed {
+        return Err(invalid("the point data starts inside the header"));
+    }
+
+    let header = LasHeader {
+        point_format,
+        record_length: u16_at(&bytes, 105) as usize,
+        point_count,
+        scale: [
+            f64_at(&bytes, 131),
+            f64_at(&bytes, 139),
+            f64_at(&bytes, 147),
+        ],
+        offset: [
+            f64_at(&bytes, 155),
+            f64_at(&bytes, 163),
+            f64_at(&bytes, 171),
+        ],
+    };
+    Ok((header, point_data_offset - consumed))
+}
+
+/// A field of the output and where its value is found in a point record.
+struct Column {
+    name: &'static str,
+    datatype: FieldDatatype,
+    source: Source,
+}
+
+enum Source {
+    /// Scaled coordinate of the axis.
+    Coordinate(usize),
+    /// Little endian bytes of the same size as the output datatype.
+    Bytes(usize),
+    /// Bits of a byte, given by the byte offset, shift and mask.
+    Bits(usize, u8, u8),
+    /// The `i8` scan angle rank of the legacy formats, widened to `i16`.
+    ScanAngleRank(usize),
+}
+
+/// The fields of a point record format in the order of the output, which is sorted by alignment.
+fn columns(point_format: u8) -> Result<(Vec<Column>, usize), MsgConversionError> {
+    use FieldDatatype::{F64, I16, U16, U8};
+    use Source::{Bits, Bytes, Coordinate, ScanAngleRank};
+
+    let column = |name, datatype, source| Column {
+        name,
+        datatype,
+        source,
+    };
+
+    let legacy = point_format <= 5;
+    if point_format > 10 {
+        return Err(invalid("unknown point data record format"));
+    }
+
+    let (core_size, gps_time, rgb) = if legacy {
+        let gps_time = matches!(point_format, 1 | 3 | 4 | 5).then_some(20);
+        let rgb = match point_format {
+            2 => Some(20),
+            3 | 5 => Some(28),
+            _ => None,
+        };
+        let size = match point_format {
+            0 => 20,
+            1 => 28,
+            2 => 26,
+            3 => 34,
+            4 => 57,
+            _ => 63,
+        };
+        (size, gps_time, rgb)
+    } else {
+        let rgb = matches!(point_format, 7 | 8 | 10).then_some(30);
+        let size = match point_format {
+            6 => 30,
+            7 => 36,
+            8 => 38,
+            9 => 59,
+            _ => 67,
+        };
+        (size, Some(22), rgb)
+    };
+
+    let mut columns = vec![
+        column("x", F64, Coordinate(0)),
+        column("y", F64, Coordinate(1)),
+        column("z", F64, Coordinate(2)),
+    ];
+    if let Some(offset) = gps_time {
+        columns.push(column("gps_time", F64, Bytes(offset)));
+    }
+    columns.push(column("intensity", U16, Bytes(12)));
+    if legacy {
+        columns.push(column("scan_angle", I16, ScanAngleRank(16)));
+        columns.push(column("point_source_id", U16, Bytes(18)));
+    } else {
+        columns.push(column("scan_angle", I16, Bytes(18)));
+        columns.push(column("point_source_id", U16, Bytes(20)));
+    }
+    if let Some(offset) = rgb {
+        columns.push(column("red", U16, Bytes(offset)));
+        columns.push(column("green", U16, Bytes(offset + 2)));
+        columns.push(column("blue", U16, Bytes(offset + 4)));
+    }
+    if legacy {
+        columns.push(column("return_number", U8, Bits(14, 0, 0b111)));
+        columns.push(column("number_of_returns", U8, Bits(14, 3, 0b111)));
+        columns.push(column("classification", U8, Bits(15, 0, 0b1_1111)));
+        columns.push(column("user_data", U8, Bytes(17)));
+    } else {
+        columns.push(column("return_number", U8, Bits(14, 0, 0b1111)));
+        columns.push(column("number_of_returns", U8, Bits(14, 4, 0b1111)));
+        columns.push(column("classification", U8, Bytes(16)));
+        columns.push(column("user_data", U8, Bytes(17)));
+    }
+
+    Ok((columns, core_size))
+}
+
+/// Read an uncompressed LAS file into an unorganized [`PointCloud2Msg`].
+///
+/// The coordinates are scaled into `f64` fields `x`, `y` and `z`. The other fields keep the types of the specification:
+/// `intensity`, `scan_angle`, `point_source_id` and the optional `red`, `green` and `blue` as 16 bit integers,
+/// `return_number`, `number_of_returns`, `classification` and `user_data` as `u8` and the optional `gps_time` as `f64`.
+/// Legacy scan angle ranks are widened to `i16` degrees, while the formats 6 to 10 store them in steps of 0.006 degrees.
+/// Extra bytes and wave packets are skipped.
+///
+/// # Example
+/// ```ignore
+/// let file = std::io::BufReader::new(std::fs::File::open("survey.las")?);
+/// let msg = ros_pointcloud2::las::from_las_reader(file)?;
+///
+/// let coordinates = msg.convert_field_type("x", FieldDatatype::F32)?; // ...
+/// ```
+///
+/// # Errors
+/// Returns [`MsgConversionError::InvalidLas`] if the input is not a valid LAS file, is compressed or ends early.
+pub fn from_las_reader<R: Read>(mut reader: R) -> Result<PointCloud2Msg, MsgConversionError> {
+    let (header, skip) = read_header(&mut reader)?;
+    let (columns, core_size) = columns(header.point_format)?;
+    if header.record_length < core_size {
+        return Err(invalid(
+            "the point record length is too short for the point format",
+        ));
+    }
+
+    // Skip the variable length records.
+    std::io::copy(&mut (&mut reader).take(skip as u64), &mut std::io::sink())
+        .map_err(|err| MsgConversionError::InvalidLas(err.to_string()))?;
+
+    let mut fields = Vec::with_capacity(columns.len());
+    let mut offset = 0;
+    for column in columns.iter() {
+        fields.push(PointFieldMsg {
+            name: String::from(column.name),
+            offset,
+            datatype: column.datatype.into(),
+            count: 1,
+        });
+        offset += column.datatype.size() as u32;
+    }
+    let point_step = offset.next_multiple_of(8);
+
+    let point_count = usize::try_from(header.point_count)?;
+    // The header is untrusted, so only a bounded amount is reserved up front and the
+    // buffer grows with the records that are actually read.
+    let mut data = Vec::with_capacity(point_count.min(1 << 16) * point_step as usize);
+    let mut record = vec![0; header.record_length];
+    for _ in 0..point_count {
+        read_exact(&mut reader, &mut record)?;
+        let start = data.len();
+        for column in columns.iter() {
+            let size = column.datatype.size();
+            match column.source {
+                Source::Coordinate(axis) => {
+                    let raw = u32_at(&record, axis * 4) as i32;
+                    let value = raw as f64 * header.scale[axis] + header.offset[axis];
+                    data.extend_from_slice(&value.to_le_bytes());
+                }
+                Source::Bytes(offset) => data.extend_from_slice(&record[offset..offset + size]),
+                Source::Bits(offset, shift, mask) => data.push((record[offset] >> shift) & mask),
+                Source::ScanAngleRank(offset) => {
+                    data.extend_from_slice(&(record[offset] as i8 as i16).to_le_bytes());
+                }
+            }
+        }
+        data.resize(start + point_step as usize, 0);
+    }
+
+    PointCloud2MsgBuilder::new()
+        .fields(fields)
+        .point_step(point_step)
+        .width(u32::try_from(point_count)?)
+        .row_step(u32::try_from(point_count * point_step as usize)?)
+        .data(data)
+        .build()
+}
@@ -6,9 +6,12 @@
 //! - [`try_from_vec`](PointCloud2Msg::try_from_vec)
 //! - [`try_into_vec`](PointCloud2Msg::try_into_vec)
 //!
-//! You can use the iterator functions for more control over the conversion process.
+//! The direct copy needs plain data points that implement [`CopyPointConvertible`], which is the case for every `Copy` point.
+//!
+//! You can use the iterator functions for more control over the conversion process. They also work with points that are only `Clone`.
 //! - [`try_from_iter`](PointCloud2Msg::try_from_iter)
 //! - [`try_into_iter`](PointCloud2Msg::try_into_iter)
+//! - [`PointCloud2Writer`] when the points arrive one at a time
 //!
 //! These feature predictable performance but they do not scale well with large clouds. Learn more about that in the [performance section](https://github.com/stelzo/ros_pointcloud2?tab=readme-ov-file#performance) of the repository.
 //! The iterators are useful when your conversions are more complex than a simple copy or the cloud is small enough.
@@ -58,7 +61,14 @@
 //! - (rclrs_msg) — Integration for ROS2 [rclrs](https://github.com/ros2-rust/ros2_rust) but it currently needs [this workaround](https://github.com/stelzo/ros_pointcloud2?tab=readme-ov-file#rclrs-ros2_rust).
 //! - derive — Offers implementations for the [`PointConvertible`] trait needed for custom points.
 //! - rayon — Parallel iterator support for `_par_iter` functions.
-//! - nalgebra — Predefined points offer a nalgebra typed getter for coordinates (e.g. [`xyz`](points::PointXYZ::xyz)).
+//! - serde — Create messages from JSON arrays of points with [`try_from_json_points`](PointCloud2Msg::try_from_json_points), e.g. for test fixtures. Requires `std`.
+//! - smallvec — Decode points of any layout without a point type and without allocating per point with [`dynamic_iter`](PointCloud2Msg::dynamic_iter).
+//! - las — Read uncompressed LAS files with [`las::from_las_reader`]. Requires `std`.
+//! - arrow — Convert clouds to and from columnar Arrow record batches with [`to_arrow`](PointCloud2Msg::to_arrow) and [`try_from_arrow`](PointCloud2Msg::try_from_arrow). Requires `std`.
+//! - half — Pack and unpack octahedral normals as [`half::f16`] with [`pack_normal_oct_f16`](points::pack_normal_oct_f16).
+//! - test-util — Check that custom point types survive the conversion with [`test_util::assert_roundtrip`] and [`test_util::assert_roundtrip_copy`].
+//! - log — Debug level [log](https://docs.rs/log) events that explain why a conversion can not copy the buffer directly.
+//! - nalgebra — Predefined points offer a nalgebra typed getter for coordinates (e.g. [`xyz`](points::PointXYZ::xyz)) and `nalgebra::Point3<f32>` can be converted directly.
 //! - std *(enabled by default)* — Omit this feature to use this library in no_std environments. ROS integrations and 'rayon' will not work with no_std.
 //!
 //! # Custom Points
@@ -140,11 +150,26 @@
 // Setup an allocator with #[global_allocator]
 // see: https://doc.rust-lang.org/std/alloc/trait.GlobalAlloc.html
 
+/// Emit a debug event with the `log` feature, compiles to nothing otherwise.
+macro_rules! debug_log {
+    ($($arg:tt)+) => {
+        #[cfg(feature = "log")]
+        log::debug!($($arg)+);
+    };
+}
+
 pub mod points;
 pub mod prelude;
 pub mod ros;
 
 pub mod iterator;
+#[cfg(feature = "las")]
+pub mod las;
+
+#[cfg(feature = "arrow")]
+mod arrow;
+#[cfg(feature = "test-util")]
+pub mod test_util;
 
 use crate::ros::{HeaderMsg, PointFieldMsg};
 
@@ -152,11 +177,13 @@ use core::str::FromStr;
 
 #[macro_use]
 extern crate alloc;
+use alloc::borrow::Cow;
 use alloc::string::String;
 use alloc::vec::Vec;
 
 /// All errors that can occur while converting to or from the message type.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum MsgConversionError {
     InvalidFieldFormat,
     #[cfg(feature = "std")]
@@ -164,10 +191,40 @@ pub enum MsgConversionError {
     #[cfg(not(feature = "std"))]
     UnsupportedFieldType,
     DataLengthMismatch,
-    FieldsNotFound(Vec<String>),
+    FieldsNotFound(Vec<Cow<'static, str>>),
     UnsupportedFieldCount,
     NumberConversion,
     ExhaustedSource,
+    InvalidArgument,
+    SchemaMismatch {
+        missing: Vec<String>,
+        extra: Vec<String>,
+        mismatched: Vec<String>,
+    },
+    ExceedsLimit {
+        points: usize,
+        limit: usize,
+    },
+    /// A field or the point step (`field` is `None`) is not a multiple of `alignment`.
+    Misaligned {
+        field: Option<String>,
+        alignment: u32,
+    },
+    /// The point type has no values or the message has a point step of 0 for a non-empty cloud.
+    ZeroPointStep,
+    // The following variants are only returned with their features enabled, but always exist
+    // so that enabling a feature does not change the enum for other crates in the build.
+    /// The input of `try_from_json_points` is not a JSON array of points (`serde` feature).
+    InvalidJson(String),
+    /// The input of `las::from_las_reader` is not a supported LAS file (`las` feature).
+    InvalidLas(String),
+    /// The cloud can not be converted to or from an Arrow record batch (`arrow` feature).
+    InvalidArrow(String),
+    /// The point at `index` differs after writing and reading it, see `test_util::assert_roundtrip` (`test-util` feature).
+    RoundtripMismatch {
+        index: usize,
+        fields: Vec<String>,
+    },
 }
 
 impl From<core::num::TryFromIntError> for MsgConversionError {
@@ -203,10 +260,7 @@ impl core::fmt::Display for MsgConversionError {
                 write!(f, "Some fields are not found in the message: {fields:?}")
             }
             MsgConversionError::UnsupportedFieldCount => {
-                write!(
-                    f,
-                    "Only field_count 1 is supported for reading and writing."
-                )
+                write!(f, "The field count does not match the point type or is 0.")
             }
             MsgConversionError::NumberConversion => {
                 write!(f, "The number is too large to be converted into a PointCloud2 supported datatype.")
@@ -217,6 +271,67 @@ impl core::fmt::Display for MsgConversionError {
                     "The conversion requests more data from the source type than is available."
                 )
             }
+            MsgConversionError::InvalidArgument => {
+                write!(f, "An argument is outside of its valid range.")
+            }
+            MsgConversionError::SchemaMismatch {
+                missing,
+                extra,
+                mismatched,
+            } => {
+                write!(
+                    f,
+                    "The message fields do not match the expected schema. Missing: {missing:?}, unexpected: {extra:?}, different datatype: {mismatched:?}"
+                )
+            }
+            MsgConversionError::ExceedsLimit { points, limit } => {
+                write!(
+                    f,
+                    "The message declares {points} points, which exceeds the limit of {limit}."
+                )
+            }
+            MsgConversionError::Misaligned {
+                field: Some(name),
+                alignment,
+            } => {
+                write!(
+                    f,
+                    "The offset of field {name} is not aligned to its datatype size of {alignment} bytes."
+                )
+            }
+            MsgConversionError::Misaligned {
+                field: None,
+                alignment,
+            } => {
+                write!(
+                    f,
+                    "The point step is not a multiple of the largest field alignment of {alignment} bytes."
+                )
+            }
+            MsgConversionError::ZeroPointStep => {
+                write!(
+                    f,
+                    "The point type or message describes points without any bytes."
+                )
+            }
+            MsgConversionError::InvalidJson(reason) => {
+                write!(f, "The JSON input can not be parsed as points: {reason}")
+            }
+            MsgConversionError::InvalidLas(reason) => {
+                write!(f, "The input can not be read as a LAS file: {reason}")
+            }
+            MsgConversionError::InvalidArrow(reason) => {
+                write!(
+                    f,
+                    "The cloud can not be converted to or from an Arrow record batch: {reason}"
+                )
+            }
+            MsgConversionError::RoundtripMismatch { index, fields } => {
+                write!(
+                    f,
+                    "The point at index {index} differs after the conversion in the values {fields:?}."
+                )
+            }
         }
     }
 }
@@ -229,6 +344,16 @@ impl std::error::Error for MsgConversionError {
     }
 }
 
+/// Point types without values can not be written to or read from a message.
+#[inline]
+fn check_point_size<const N: usize>() -> Result<(), MsgConversionError> {
+    if N == 0 {
+        return Err(MsgConversionError::ZeroPointStep);
+    }
+
+    Ok(())
+}
+
 fn system_endian() -> Endian {
     if cfg!(target_endian = "big") {
         Endian::Big
@@ -247,16 +372,150 @@ impl LayoutDescription {
     pub fn new(fields: &[LayoutField]) -> Self {
         Self(fields.into())
     }
+
+    /// Create the layout from the byte offsets of the fields in a `#[repr(C)]` struct of size `struct_size`.
+    ///
+    /// The fields need to be given in declaration order. Padding is inserted between the fields and at the end of the struct.
+    /// This is what [`layout_from_repr_c!`](crate::layout_from_repr_c) uses, which computes the offsets for you.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// #[repr(C, align(16))]
+    /// struct MyPointXYZL {
+    ///     x: f32,
+    ///     y: f32,
+    ///     z: f32,
+    ///     label: u8,
+    /// }
+    ///
+    /// let layout = LayoutDescription::from_offsets(
+    ///     core::mem::size_of::<MyPointXYZL>(),
+    ///     &[
+    ///         ("x", FieldDatatype::F32, 0),
+    ///         ("y", FieldDatatype::F32, 4),
+    ///         ("z", FieldDatatype::F32, 8),
+    ///         ("label", FieldDatatype::U8, 12),
+    ///     ],
+    /// );
+    /// ```
+    pub fn from_offsets(
+        struct_size: usize,
+        fields: &[(&'static str, FieldDatatype, usize)],
+    ) -> Self {
+        let mut layout = Vec::with_capacity(fields.len() * 2 + 1);
+        let mut last_field_end = 0;
+        for &(name, datatype, offset) in fields.iter() {
+            debug_assert!(
+                offset >= last_field_end,
+                "Fields must be in declaration order of a repr(C) struct without overlaps."
+            );
+            if offset > last_field_end {
+                layout.push(LayoutField::padding(offset - last_field_end));
+            }
+            layout.push(LayoutField::new(
+                name,
+                datatype.type_name(),
+                datatype.size(),
+            ));
+            last_field_end = offset + datatype.size();
+        }
+
+        if struct_size > last_field_end {
+            layout.push(LayoutField::padding(struct_size - last_field_end));
+        }
+
+        Self(layout)
+    }
+}
+
+/// Compute the [`LayoutDescription`] of a `#[repr(C)]` point struct including its padding, the same way the derive macro does.
+///
+/// This is useful for manual [`PointConvertible`] implementations, e.g. for points that can not use the derive.
+/// List the fields with their types in declaration order. Renaming a field in the message is done with `name as "message_name"`.
+///
+/// # Example
+/// ```
+/// use ros_pointcloud2::prelude::*;
+///
+/// #[derive(Clone, Debug, PartialEq, Copy, Default)]
+/// #[repr(C, align(16))]
+/// pub struct MyPointXYZL {
+///     pub x: f32,
+///     pub y: f32,
+///     pub z: f32,
+///     pub label: u8,
+/// }
+///
+/// impl From<MyPointXYZL> for RPCL2Point<4> {
+///     fn from(point: MyPointXYZL) -> Self {
+///         [point.x.into(), point.y.into(), point.z.into(), point.label.into()].into()
+///     }
+/// }
+///
+/// impl From<RPCL2Point<4>> for MyPointXYZL {
+///     fn from(point: RPCL2Point<4>) -> Self {
+///         Self {
+///             x: point[0].get(),
+///             y: point[1].get(),
+///             z: point[2].get(),
+///             label: point[3].get(),
+///         }
+///     }
+/// }
+///
+/// unsafe impl PointConvertible<4> for MyPointXYZL {
+///     fn layout() -> LayoutDescription {
+///         ros_pointcloud2::layout_from_repr_c!(MyPointXYZL {
+///             x: f32,
+///             y: f32,
+///             z: f32,
+///             label as "l": u8,
+///         })
+///     }
+/// }
+///
+/// let msg = PointCloud2Msg::try_from_vec(vec![MyPointXYZL::default()]).unwrap();
+/// assert_eq!(msg.point_step, 16);
+/// assert_eq!(msg.fields[3].name, "l");
+/// ```
+#[macro_export]
+macro_rules! layout_from_repr_c {
+    ($point:ident { $($field:ident $(as $rename:literal)? : $ty:ty),+ $(,)? }) => {
+        $crate::LayoutDescription::from_offsets(
+            ::core::mem::size_of::<$point>(),
+            &[$((
+                $crate::layout_from_repr_c!(@name $field $($rename)?),
+                <$ty as $crate::GetFieldDatatype>::field_datatype(),
+                ::core::mem::offset_of!($point, $field),
+            )),+],
+        )
+    };
+    (@name $field:ident) => {
+        ::core::stringify!($field)
+    };
+    (@name $field:ident $rename:literal) => {
+        $rename
+    };
 }
 
 /// Enum to describe the field type and size in a padded or unpadded layout.
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub enum LayoutField {
     Field {
         name: alloc::borrow::Cow<'static, str>,
         ty: alloc::borrow::Cow<'static, str>,
         size: usize,
     },
+    /// A field with `count` consecutive values of `size` bytes each, e.g. a normal vector or a descriptor.
+    Array {
+        name: alloc::borrow::Cow<'static, str>,
+        ty: alloc::borrow::Cow<'static, str>,
+        size: usize,
+        count: usize,
+    },
     Padding {
         size: usize,
     },
@@ -271,6 +530,29 @@ impl LayoutField {
         }
     }
 
+    /// A field with `count` values of type `ty` that are stored as a single message field with the same count.
+    ///
+    /// The point has one [`PointData`] per value, so an array of 3 counts as 3 towards `N` of [`PointConvertible<N>`].
+    /// Read the values with [`RPCL2Point::get_array`].
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let layout = LayoutDescription::new(&[
+    ///     LayoutField::new("x", "f32", 4),
+    ///     LayoutField::array("normal", "f32", 4, 3),
+    /// ]);
+    /// ```
+    pub fn array(name: &'static str, ty: &'static str, size: usize, count: usize) -> Self {
+        LayoutField::Array {
+            name: name.into(),
+            ty: ty.into(),
+            size,
+            count,
+        }
+    }
+
     pub fn padding(size: usize) -> Self {
         LayoutField::Padding { size }
     }
@@ -290,6 +572,278 @@ pub struct PointCloud2Msg {
     pub row_step: u32,
     pub data: Vec<u8>,
     pub dense: Denseness,
+    /// Scale and offset of fields with quantized values. This is not part of the ROS message and dropped when converting to a ROS type.
+    pub field_transforms: Vec<FieldTransform>,
+}
+
+/// Scale and offset of a field that stores quantized values, e.g. LAS coordinates stored as integers.
+///
+/// The real value is `value * scale + offset`, see [`PointCloud2Msg::decode_scaled_field_f64`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldTransform {
+    pub name: String,
+    pub scale: f64,
+    pub offset: f64,
+}
+
+/// Borrowed view of a contiguous range of points in a [`PointCloud2Msg`].
+///
+/// The view shares the schema and the byte buffer with the message it was created from, so no point data is copied.
+/// See [`PointCloud2Msg::sub_view`].
+#[derive(Clone, Debug)]
+pub struct PointCloud2Ref<'a> {
+    pub header: &'a HeaderMsg,
+    pub dimensions: CloudDimensions,
+    pub fields: &'a [PointFieldMsg],
+    pub endian: Endian,
+    pub point_step: u32,
+    pub row_step: u32,
+    pub data: &'a [u8],
+    pub dense: Denseness,
+    pub field_transforms: &'a [FieldTransform],
+}
+
+impl<'a> PointCloud2Ref<'a> {
+    /// Number of points in the view.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.dimensions.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Raw bytes of the point at `idx` with a length of `point_step`.
+    #[must_use]
+    pub fn point_bytes(&self, idx: usize) -> Option<&'a [u8]> {
+        let point_step = self.point_step as usize;
+        self.data.get(idx * point_step..(idx + 1) * point_step)
+    }
+
+    /// Narrow the view further to the points `start..start + len` of this view.
+    ///
+    /// # Errors
+    /// Returns an error if the range is outside of the view.
+    pub fn sub_view(
+        &self,
+        start: usize,
+        len: usize,
+    ) -> Result<PointCloud2Ref<'a>, MsgConversionError> {
+        sub_view_of(
+            self.header,
+            self.fields,
+            self.endian,
+            self.point_step,
+            self.data,
+            self.dense,
+            self.field_transforms,
+            self.len(),
+            start,
+            len,
+        )
+    }
+
+    /// Copy the viewed points into an owned [`PointCloud2Msg`], e.g. to use the conversion functions.
+    #[must_use]
+    pub fn to_msg(&self) -> PointCloud2Msg {
+        PointCloud2Msg {
+            header: self.header.clone(),
+            dimensions: self.dimensions.clone(),
+            fields: self.fields.to_vec(),
+            endian: self.endian,
+            point_step: self.point_step,
+            row_step: self.row_step,
+            data: self.data.to_vec(),
+            dense: self.dense,
+            field_transforms: self.field_transforms.to_vec(),
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn sub_view_of<'a>(
+    header: &'a HeaderMsg,
+    fields: &'a [PointFieldMsg],
+    endian: Endian,
+    point_step: u32,
+    data: &'a [u8],
+    dense: Denseness,
+    field_transforms: &'a [FieldTransform],
+    available: usize,
+    start: usize,
+    len: usize,
+) -> Result<PointCloud2Ref<'a>, MsgConversionError> {
+    let end = start
+        .checked_add(len)
+        .ok_or(MsgConversionError::ExhaustedSource)?;
+    if end > available {
+        return Err(MsgConversionError::ExhaustedSource);
+    }
+
+    let step = point_step as usize;
+    let data = data
+        .get(start * step..end * step)
+        .ok_or(MsgConversionError::DataLengthMismatch)?;
+
+    Ok(PointCloud2Ref {
+        header,
+        dimensions: CloudDimensionsBuilder::new_with_width(len).build()?,
+        fields,
+        endian,
+        point_step,
+        row_step: u32::try_from(len * step)?,
+        data,
+        dense,
+        field_transforms,
+    })
+}
+
+/// Incremental writer that appends points one at a time to the byte buffer of a new [`PointCloud2Msg`].
+///
+/// This is useful when the points arrive over multiple callbacks, e.g. in a scan assembler,
+/// so they do not need to be collected into a `Vec` first. The written message is the same as with
+/// [`try_from_iter`](PointCloud2Msg::try_from_iter).
+///
+/// # Example
+/// ```
+/// use ros_pointcloud2::prelude::*;
+///
+/// let mut writer = PointCloud2Writer::<3, PointXYZ>::new().unwrap();
+/// writer.push(PointXYZ::new(1.0, 2.0, 3.0)).unwrap();
+/// writer.push(PointXYZ::new(4.0, 5.0, 6.0)).unwrap();
+/// assert_eq!(writer.len(), 2);
+///
+/// let msg = writer.finish().unwrap();
+/// assert_eq!(msg.dimensions.width, 2);
+/// ```
+#[derive(Clone, Debug)]
+pub struct PointCloud2Writer<const N: usize, C>
+where
+    C: PointConvertible<N>,
+{
+    fields: Vec<PointFieldMsg>,
+    point_step: u32,
+    datatypes: [FieldDatatype; N],
+    data: Vec<u8>,
+    len: usize,
+    _phantom: core::marker::PhantomData<C>,
+}
+
+impl<const N: usize, C> PointCloud2Writer<N, C>
+where
+    C: PointConvertible<N>,
+{
+    /// Create a writer with the fields of `C`.
+    ///
+    /// # Errors
+    /// Returns an error if the layout of `C` can not be described by the message.
+    pub fn new() -> Result<Self, MsgConversionError> {
+        check_point_size::<N>()?;
+
+        let point: RPCL2Point<N> = C::default().into();
+        debug_assert!(point.fields.len() == N);
+
+        let layout_fields = crate::ordered_layout_fields::<N, C>();
+        debug_assert!(layout_fields.iter().map(|(_, count)| count).sum::<usize>() == N);
+
+        let datatypes: [FieldDatatype; N] = core::array::from_fn(|i| point.fields[i].datatype);
+        let mut pdata_offsets_acc: u32 = 0;
+        let mut fields = Vec::with_capacity(layout_fields.len());
+        let mut elements = datatypes.iter();
+        for (field_name, count) in layout_fields {
+            let datatype = *elements.next().ok_or(MsgConversionError::ExhaustedSource)?;
+            // All values of an array field share the datatype of the message field.
+            for _ in 1..count {
+                if elements.next() != Some(&datatype) {
+                    return Err(MsgConversionError::InvalidFieldFormat);
+                }
+            }
+
+            let datatype_code = datatype.into();
+            let _ = FieldDatatype::try_from(datatype_code)?;
+            let field_count = u32::try_from(count)?;
+
+            fields.push(PointFieldMsg {
+                name: field_name.into_owned(),
+                offset: pdata_offsets_acc,
+                datatype: datatype_code,
+                count: field_count,
+            });
+
+            pdata_offsets_acc += field_count * datatype.size() as u32;
+        }
+
+        Ok(Self {
+            fields,
+            point_step: pdata_offsets_acc,
+            datatypes,
+            data: Vec::new(),
+            len: 0,
+            _phantom: core::marker::PhantomData,
+        })
+    }
+
+    /// Append a point to the end of the buffer.
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::InvalidFieldFormat`] if the point yields other field datatypes than the default point,
+    /// which hints at a faulty manual [`PointConvertible`] implementation. The point is not written in that case.
+    pub fn push(&mut self, point: C) -> Result<(), MsgConversionError> {
+        self.push_point(point.into())
+    }
+
+    fn push_point(&mut self, point: RPCL2Point<N>) -> Result<(), MsgConversionError> {
+        // Every point must match the schema, otherwise the byte widths diverge and corrupt the message.
+        if point
+            .fields
+            .iter()
+            .zip(self.datatypes.iter())
+            .any(|(pdata, datatype)| pdata.datatype != *datatype)
+        {
+            return Err(MsgConversionError::InvalidFieldFormat);
+        }
+
+        point.fields.iter().for_each(|pdata| {
+            let truncated_bytes =
+                unsafe { core::slice::from_raw_parts(pdata.bytes.as_ptr(), pdata.datatype.size()) };
+            self.data.extend_from_slice(truncated_bytes);
+        });
+
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Number of written points.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Create the unorganized message from the written points.
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::NumberConversion`] if the number of points does not fit into the message dimensions.
+    pub fn finish(self) -> Result<PointCloud2Msg, MsgConversionError> {
+        let width = u32::try_from(self.len)?;
+        let row_step = width
+            .checked_mul(self.point_step)
+            .ok_or(MsgConversionError::NumberConversion)?;
+
+        PointCloud2MsgBuilder::new()
+            .fields(self.fields)
+            .point_step(self.point_step)
+            .width(width)
+            .row_step(row_step)
+            .data(self.data)
+            .build()
+    }
 }
 
 /// Endianess encoding hint for the message.
@@ -300,6 +854,24 @@ pub enum Endian {
     Little,
 }
 
+impl Endian {
+    /// Interpret the `is_bigendian` flag of a ROS message.
+    #[must_use]
+    pub fn from_ros_bool(is_bigendian: bool) -> Self {
+        if is_bigendian {
+            Endian::Big
+        } else {
+            Endian::Little
+        }
+    }
+
+    /// The `is_bigendian` flag for a ROS message.
+    #[must_use]
+    pub fn to_ros_bool(self) -> bool {
+        self == Endian::Big
+    }
+}
+
 /// Density flag for the message. Writing sparse point clouds is not supported.
 #[derive(Default, Clone, Debug, PartialEq, Copy)]
 pub enum Denseness {
@@ -308,11 +880,66 @@ pub enum Denseness {
     Sparse,
 }
 
-#[derive(Clone, Debug, PartialEq)]
-enum ByteSimilarity {
-    Equal,
-    Overlapping,
-    Different,
+impl Denseness {
+    /// Interpret the `is_dense` flag of a ROS message.
+    #[must_use]
+    pub fn from_ros_bool(is_dense: bool) -> Self {
+        if is_dense {
+            Denseness::Dense
+        } else {
+            Denseness::Sparse
+        }
+    }
+
+    /// The `is_dense` flag for a ROS message.
+    #[must_use]
+    pub fn to_ros_bool(self) -> bool {
+        self == Denseness::Dense
+    }
+}
+
+/// How the memory layout of a point type relates to the points in a message.
+///
+/// See [`PointCloud2Msg::layout_matches`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatchKind {
+    /// The buffer can be copied as a whole.
+    Exact,
+    /// The type matches the start of each point but the message has additional bytes per point, so every point is copied on its own.
+    Strided,
+    /// The layouts differ, so every field of every point is decoded.
+    RequiresConversion,
+}
+
+/// The strategy that decoded the points, reported by [`PointCloud2Msg::try_into_vec_profiled`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodePath {
+    /// The whole buffer was copied at once.
+    ZeroCopy,
+    /// Every point was copied on its own because the message has additional bytes per point.
+    Strided,
+    /// The layouts differ, so every field of every point was decoded.
+    PerField,
+    /// The message endian differs from the system, so every field of every point was decoded and byte swapped.
+    EndianFallback,
+}
+
+/// How a packed `rgb` field of a message is decoded, see [`PointCloud2Msg::try_into_iter_with_color`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorDecode {
+    /// The `rgb` field is read as is, so the point type needs a packed `rgb` field as well.
+    #[default]
+    Packed,
+    /// The `rgb` field is unpacked into the `u8` fields `r`, `g` and `b` of the point type if the message has no such fields.
+    Channels,
+}
+
+/// A coordinate axis of a point, see [`PointCloud2Msg::remap_axes`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
 }
 
 /// Creating a [`CloudDimensions`] type with the builder pattern to avoid invalid states when using 1-row point clouds.
@@ -349,6 +976,9 @@ pub struct PointCloud2MsgBuilder {
     row_step: u32,
     data: Vec<u8>,
     is_dense: bool,
+    point_alignment: Option<u32>,
+    alignment_check: bool,
+    allow_overlapping_fields: bool,
 }
 
 impl PointCloud2MsgBuilder {
@@ -405,55 +1035,263 @@ impl PointCloud2MsgBuilder {
         self
     }
 
-    /// Build the [`PointCloud2Msg`] from the builder.
+    /// Copy the fields, point step and endian of another message, e.g. for pass-through nodes
+    /// that only modify the data and need the same format on the wire.
     ///
-    /// # Errors
-    /// Returns an error if the fields are empty, the field count is not 1, the field format is invalid, the data length does not match the point step, or the field size is too large.
-    pub fn build(self) -> Result<PointCloud2Msg, MsgConversionError> {
-        if self.fields.is_empty() {
-            return Err(MsgConversionError::FieldsNotFound(vec![]));
-        }
-
-        if self.fields.iter().any(|f| f.count != 1) {
-            return Err(MsgConversionError::UnsupportedFieldCount);
-        }
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    /// use ros_pointcloud2::PointCloud2MsgBuilder;
+    ///
+    /// let input = PointCloud2Msg::try_from_vec(vec![PointXYZI::new(1.0, 2.0, 3.0, 0.5)]).unwrap();
+    ///
+    /// let output = PointCloud2MsgBuilder::new()
+    ///     .with_schema_from(&input)
+    ///     .width(1)
+    ///     .row_step(input.point_step)
+    ///     .data(input.data.clone())
+    ///     .build()
+    ///     .unwrap();
+    /// assert!(output.same_schema(&input));
+    /// ```
+    #[must_use]
+    pub fn with_schema_from(mut self, msg: &PointCloud2Msg) -> Self {
+        self.fields = msg.fields.clone();
+        self.point_step = msg.point_step;
+        self.is_big_endian = msg.endian.to_ros_bool();
+        self
+    }
 
-        let fields_size = self
-            .fields
-            .iter()
-            .map(FieldDatatype::try_from)
-            .collect::<Result<Vec<_>, _>>()?
-            .iter()
-            .map(|f| f.size() as u32)
-            .sum::<_>();
+    /// Round the point step up to a multiple of `alignment` bytes by adding padding at the end of each point.
+    ///
+    /// The data is laid out again when building and the row step is recomputed.
+    /// This is needed for consumers like C++ PCL, where the SSE optimized points are 16 byte aligned.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    /// use ros_pointcloud2::PointCloud2MsgBuilder;
+    ///
+    /// let msg = PointCloud2Msg::try_from_vec(vec![PointXYZ::new(1.0, 2.0, 3.0)])
+    ///     .unwrap()
+    ///     .repack_tight()
+    ///     .unwrap();
+    /// assert_eq!(msg.point_step, 12);
+    ///
+    /// let aligned = PointCloud2MsgBuilder::new()
+    ///     .fields(msg.fields)
+    ///     .point_step(msg.point_step)
+    ///     .width(1)
+    ///     .data(msg.data)
+    ///     .with_point_alignment(16)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(aligned.point_step, 16);
+    /// assert_eq!(aligned.data.len(), 16);
+    /// ```
+    #[must_use]
+    pub fn with_point_alignment(mut self, alignment: u32) -> Self {
+        self.point_alignment = Some(alignment);
+        self
+    }
+
+    /// Require that every field offset is a multiple of the size of its datatype and that the point step
+    /// is a multiple of the largest field alignment.
+    ///
+    /// C++ consumers like PCL read the fields in place, so a misaligned field, e.g. a f64 at offset 4,
+    /// crashes there while it decodes fine in Rust. The check runs after [`with_point_alignment`](PointCloud2MsgBuilder::with_point_alignment).
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    /// use ros_pointcloud2::PointCloud2MsgBuilder;
+    /// use ros_pointcloud2::ros::PointFieldMsg;
+    ///
+    /// let fields = vec![
+    ///     PointFieldMsg { name: "x".into(), offset: 0, datatype: 7, count: 1 },
+    ///     PointFieldMsg { name: "t".into(), offset: 4, datatype: 8, count: 1 },
+    /// ];
+    /// let result = PointCloud2MsgBuilder::new()
+    ///     .fields(fields)
+    ///     .point_step(12)
+    ///     .with_alignment_check(true)
+    ///     .build();
+    /// assert!(matches!(result, Err(MsgConversionError::Misaligned { .. })));
+    /// ```
+    #[must_use]
+    pub fn with_alignment_check(mut self, check: bool) -> Self {
+        self.alignment_check = check;
+        self
+    }
+
+    /// Accept fields that share bytes of the point, e.g. the PCL convention of a union that exposes the same color
+    /// as `rgb` and `rgba` at one offset.
+    ///
+    /// Overlapping fields are rejected by default because they usually hint at a wrong offset.
+    /// The conversions resolve fields by name, so a point type reads the view it names.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    /// use ros_pointcloud2::PointCloud2MsgBuilder;
+    /// use ros_pointcloud2::ros::PointFieldMsg;
+    ///
+    /// let fields = vec![
+    ///     PointFieldMsg { name: "rgb".into(), offset: 0, datatype: 7, count: 1 },
+    ///     PointFieldMsg { name: "rgba".into(), offset: 0, datatype: 6, count: 1 },
+    /// ];
+    /// let builder = PointCloud2MsgBuilder::new().fields(fields).point_step(4);
+    ///
+    /// assert!(builder.clone().build().is_err());
+    /// assert!(builder.allow_overlapping_fields(true).build().is_ok());
+    /// ```
+    #[must_use]
+    pub fn allow_overlapping_fields(mut self, allow: bool) -> Self {
+        self.allow_overlapping_fields = allow;
+        self
+    }
+
+    /// Build the [`PointCloud2Msg`] from the builder.
+    ///
+    /// # Errors
+    /// Returns an error if the fields are empty, a field count is 0, the field format is invalid, the fields overlap or do not fit into the point step,
+    /// the data is shorter than `width` points, the field size is too large or the point alignment is not a power of two.
+    /// Additional bytes after the points are kept, see [`PointCloud2Msg::trailing_bytes`].
+    /// With [`with_alignment_check`](PointCloud2MsgBuilder::with_alignment_check), misaligned fields return [`MsgConversionError::Misaligned`].
+    pub fn build(mut self) -> Result<PointCloud2Msg, MsgConversionError> {
+        if self.fields.is_empty() {
+            return Err(MsgConversionError::FieldsNotFound(Vec::new()));
+        }
+
+        if self.point_step == 0 {
+            return Err(MsgConversionError::ZeroPointStep);
+        }
 
-        if self.point_step < fields_size {
+        if self.fields.iter().any(|f| f.count == 0) {
+            return Err(MsgConversionError::UnsupportedFieldCount);
+        }
+
+        if self.point_step
+            < PointCloud2Msg::fields_end(&self.fields, self.allow_overlapping_fields)?
+        {
             return Err(MsgConversionError::InvalidFieldFormat);
         }
 
-        if self.data.len() as u32 % self.point_step != 0 {
+        // Bytes after the declared points are kept and reported by `trailing_bytes`.
+        let required = (self.width as usize)
+            .checked_mul(self.point_step as usize)
+            .ok_or(MsgConversionError::NumberConversion)?;
+        if self.data.len() < required {
             return Err(MsgConversionError::DataLengthMismatch);
         }
 
+        if let Some(alignment) = self.point_alignment {
+            if !alignment.is_power_of_two() {
+                return Err(MsgConversionError::InvalidArgument);
+            }
+
+            let aligned_step = self
+                .point_step
+                .checked_next_multiple_of(alignment)
+                .ok_or(MsgConversionError::NumberConversion)?;
+            if aligned_step != self.point_step {
+                let point_step = self.point_step as usize;
+                let point_count = self.width as usize;
+                let mut data = Vec::with_capacity(point_count * aligned_step as usize);
+                for point in self.data.chunks_exact(point_step).take(point_count) {
+                    data.extend_from_slice(point);
+                    data.resize(data.len() + (aligned_step - self.point_step) as usize, 0);
+                }
+
+                self.data = data;
+                self.point_step = aligned_step;
+                self.row_step = self
+                    .width
+                    .checked_mul(aligned_step)
+                    .ok_or(MsgConversionError::NumberConversion)?;
+            }
+        }
+
+        if self.alignment_check {
+            let mut max_alignment = 1;
+            for field in self.fields.iter() {
+                let alignment = FieldDatatype::try_from(field)?.size() as u32;
+                if field.offset % alignment != 0 {
+                    return Err(MsgConversionError::Misaligned {
+                        field: Some(field.name.clone()),
+                        alignment,
+                    });
+                }
+                max_alignment = max_alignment.max(alignment);
+            }
+
+            if self.point_step % max_alignment != 0 {
+                return Err(MsgConversionError::Misaligned {
+                    field: None,
+                    alignment: max_alignment,
+                });
+            }
+        }
+
         Ok(PointCloud2Msg {
             header: self.header,
             dimensions: CloudDimensionsBuilder::new_with_width(self.width as usize).build()?,
             fields: self.fields,
-            endian: if self.is_big_endian {
-                Endian::Big
-            } else {
-                Endian::Little
-            },
+            endian: Endian::from_ros_bool(self.is_big_endian),
             point_step: self.point_step,
             row_step: self.row_step,
             data: self.data,
-            dense: if self.is_dense {
-                Denseness::Dense
-            } else {
-                Denseness::Sparse
-            },
+            dense: Denseness::from_ros_bool(self.is_dense),
+            field_transforms: Vec::new(),
         })
     }
+
+    /// Build the [`PointCloud2Msg`] like [`build`](PointCloud2MsgBuilder::build), but require that the fields cover the point without gaps.
+    ///
+    /// The fields sorted by offset must start at 0, each field must start where the previous one ends
+    /// and the last field must end at the point step. Padding has to be declared as a field, e.g. a `u8` array
+    /// named `_` like PCL does. This catches a point step or offset that does not fit the fields,
+    /// which otherwise only shows as garbage when decoding. Padding added by [`with_point_alignment`](PointCloud2MsgBuilder::with_point_alignment)
+    /// is requested explicitly and still allowed.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    /// use ros_pointcloud2::PointCloud2MsgBuilder;
+    /// use ros_pointcloud2::ros::PointFieldMsg;
+    ///
+    /// let fields = vec![
+    ///     PointFieldMsg { name: "x".into(), offset: 0, datatype: 7, count: 1 },
+    ///     PointFieldMsg { name: "y".into(), offset: 4, datatype: 7, count: 1 },
+    /// ];
+    /// let builder = PointCloud2MsgBuilder::new().fields(fields.clone()).point_step(12);
+    /// assert!(builder.clone().build().is_ok());
+    /// assert!(matches!(builder.build_strict(), Err(MsgConversionError::InvalidFieldFormat)));
+    ///
+    /// let mut padded = fields;
+    /// padded.push(PointFieldMsg { name: "_".into(), offset: 8, datatype: 2, count: 4 });
+    /// assert!(PointCloud2MsgBuilder::new().fields(padded).point_step(12).build_strict().is_ok());
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::InvalidFieldFormat`] if the fields leave gaps, overlap or do not end at the point step.
+    /// Otherwise, the same errors as [`build`](PointCloud2MsgBuilder::build) are returned.
+    pub fn build_strict(self) -> Result<PointCloud2Msg, MsgConversionError> {
+        if !self.fields.is_empty() {
+            let mut end = 0;
+            for (start, field_end) in PointCloud2Msg::field_ranges(&self.fields)? {
+                if start != end {
+                    return Err(MsgConversionError::InvalidFieldFormat);
+                }
+                end = field_end;
+            }
+            if end != self.point_step {
+                return Err(MsgConversionError::InvalidFieldFormat);
+            }
+        }
+
+        self.build()
+    }
 }
 
 /// Dimensions of the point cloud as width and height.
@@ -463,42 +1301,193 @@ pub struct CloudDimensions {
     pub height: u32,
 }
 
-fn ordered_field_names<const N: usize, C: PointConvertible<N>>() -> Vec<String> {
+impl CloudDimensions {
+    /// Number of points as `width * height` or `None` if the product does not fit into `usize`.
+    ///
+    /// This can happen for large organized clouds on 32-bit targets.
+    #[must_use]
+    pub fn checked_len(&self) -> Option<usize> {
+        (self.width as usize).checked_mul(self.height as usize)
+    }
+
+    /// Number of points as `width * height`, saturating at `usize::MAX`.
+    ///
+    /// Use [`checked_len`](CloudDimensions::checked_len) when the length is used to address the buffer.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.checked_len().unwrap_or(usize::MAX)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+}
+
+/// Dense 3D grid with the number of points per cell, created by [`PointCloud2Msg::occupancy_grid`].
+///
+/// Cells are stored with `x` changing fastest, followed by `y` and `z`. Counts saturate at `u16::MAX`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OccupancyGrid3D {
+    /// Number of cells along the `x`, `y` and `z` axis.
+    pub dimensions: [usize; 3],
+    /// Minimum corner of the grid.
+    pub origin: [f32; 3],
+    /// Size of a single cell.
+    pub leaf: [f32; 3],
+    pub counts: Vec<u16>,
+}
+
+impl OccupancyGrid3D {
+    /// Linear index of a cell in [`counts`](OccupancyGrid3D::counts) or `None` if the cell is outside the grid.
+    #[must_use]
+    pub fn index(&self, cell: [usize; 3]) -> Option<usize> {
+        if (0..3).any(|axis| cell[axis] >= self.dimensions[axis]) {
+            return None;
+        }
+
+        Some(cell[0] + self.dimensions[0] * (cell[1] + self.dimensions[1] * cell[2]))
+    }
+
+    /// The cell that contains the given coordinates or `None` if they are outside the grid.
+    #[must_use]
+    pub fn cell_of(&self, point: [f32; 3]) -> Option<[usize; 3]> {
+        let mut cell = [0; 3];
+        for axis in 0..3 {
+            let pos = (point[axis] - self.origin[axis]) / self.leaf[axis];
+            if pos.is_nan() {
+                return None;
+            }
+            let pos = floor_to_i64(pos);
+            if pos < 0 || pos as u64 >= self.dimensions[axis] as u64 {
+                return None;
+            }
+            cell[axis] = pos as usize;
+        }
+
+        Some(cell)
+    }
+
+    /// Number of points in a cell. Cells outside the grid are empty.
+    #[must_use]
+    pub fn count(&self, cell: [usize; 3]) -> u16 {
+        self.index(cell).map_or(0, |idx| self.counts[idx])
+    }
+
+    #[must_use]
+    pub fn is_occupied(&self, cell: [usize; 3]) -> bool {
+        self.count(cell) > 0
+    }
+
+    /// Occupancy of every cell in the same order as [`counts`](OccupancyGrid3D::counts).
+    #[must_use]
+    pub fn occupancy(&self) -> Vec<bool> {
+        self.counts.iter().map(|&c| c > 0).collect()
+    }
+}
+
+/// Small deterministic random number generator for sampling without external dependencies.
+///
+/// See <https://prng.di.unimi.it/splitmix64.c>.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in `0..bound` using the widening multiply method.
+    fn next_below(&mut self, bound: u64) -> u64 {
+        ((self.next_u64() as u128 * bound as u128) >> 64) as u64
+    }
+}
+
+/// 64 bit FNV-1a hash, which is fixed across platforms, runs and versions unlike the hasher of the standard library.
+///
+/// See <http://www.isthe.com/chongo/tech/comp/fnv/index.html>.
+struct Fnv1a64(u64);
+
+impl Fnv1a64 {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ *byte as u64).wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn write_u32(&mut self, value: u32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// `floor` for finite values without std, saturating at the bounds of `i64`.
+#[inline]
+fn floor_to_i64(value: f32) -> i64 {
+    let truncated = value as i64;
+    if (truncated as f32) > value {
+        truncated - 1
+    } else {
+        truncated
+    }
+}
+
+/// Names and counts of the fields of `C` in layout order.
+fn ordered_layout_fields<const N: usize, C: PointConvertible<N>>() -> Vec<(Cow<'static, str>, usize)>
+{
     C::layout()
         .0
-        .iter()
-        .filter(|field| {
-            matches!(
-                field,
-                LayoutField::Field {
-                    name: _,
-                    ty: _,
-                    size: _,
-                }
-            )
-        })
-        .map(|field| match field {
-            LayoutField::Field {
-                name,
-                ty: _,
-                size: _,
-            } => name.as_ref().into(),
-            _ => unreachable!("Fields must be filtered before."),
+        .into_iter()
+        .filter_map(|field| match field {
+            LayoutField::Field { name, .. } => Some((name, 1)),
+            LayoutField::Array { name, count, .. } => Some((name, count)),
+            LayoutField::Padding { .. } => None,
         })
         .collect()
 }
 
+fn ordered_field_names<const N: usize, C: PointConvertible<N>>() -> Vec<Cow<'static, str>> {
+    ordered_layout_fields::<N, C>()
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect()
+}
+
+/// The field name and element index of every value of `C`, so the result has `N` entries.
+fn ordered_field_elements<const N: usize, C: PointConvertible<N>>(
+) -> Vec<(Cow<'static, str>, usize)> {
+    ordered_layout_fields::<N, C>()
+        .into_iter()
+        .flat_map(|(name, count)| (0..count).map(move |element| (name.clone(), element)))
+        .collect()
+}
+
 impl PointCloud2Msg {
     #[inline]
-    fn byte_similarity<const N: usize, C>(&self) -> Result<ByteSimilarity, MsgConversionError>
+    fn byte_similarity<const N: usize, C>(&self) -> Result<MatchKind, MsgConversionError>
     where
         C: PointConvertible<N>,
     {
+        check_point_size::<N>()?;
+
         let field_names = ordered_field_names::<N, C>();
         let target_layout = KnownLayoutInfo::try_from(C::layout())?;
 
         debug_assert!(field_names.len() <= target_layout.fields.len());
-        debug_assert!(self.fields.len() >= target_layout.fields.len());
 
         let mut offset: u32 = 0;
         let mut field_counter = 0;
@@ -519,10 +1508,19 @@ impl PointCloud2Msg {
 
                     if msg_f.name != *f_translated
                         || msg_f.offset != offset
-                        || msg_f.datatype != *datatype
-                        || msg_f.count != 1
+                        || !datatype.matches_code(msg_f.datatype)
+                        || msg_f.count != *count
                     {
-                        return Ok(ByteSimilarity::Different);
+                        debug_log!(
+                            "layout differs at field {}: message has {} at offset {} with datatype {}, point type expects {} at offset {}",
+                            field_counter - 1,
+                            msg_f.name,
+                            msg_f.offset,
+                            msg_f.datatype,
+                            f_translated,
+                            offset
+                        );
+                        return Ok(MatchKind::RequiresConversion);
                     }
 
                     offset += size * count;
@@ -533,266 +1531,3927 @@ impl PointCloud2Msg {
             }
         }
 
-        Ok(if offset == self.point_step {
-            ByteSimilarity::Equal
+        // A type that is larger than a point would read beyond the last point when copied.
+        Ok(if offset > self.point_step {
+            debug_log!(
+                "point type needs {} bytes but the point step is {}",
+                offset,
+                self.point_step
+            );
+            MatchKind::RequiresConversion
+        } else if offset == self.point_step && !self.has_row_padding() {
+            MatchKind::Exact
         } else {
-            ByteSimilarity::Overlapping
+            debug_log!(
+                "message has extra bytes per point or row, copying point by point (point step {}, point type {} bytes)",
+                self.point_step,
+                offset
+            );
+            MatchKind::Strided
         })
     }
 
-    /// Create a [`PointCloud2Msg`] from any iterable type that implements [`PointConvertible`].
+    /// Check how the point type `C` can be read from this message by [`try_into_vec`](PointCloud2Msg::try_into_vec).
+    ///
+    /// This allows branching on whether the conversion copies the buffer directly or decodes every point before committing to it.
     ///
     /// # Example
     /// ```
     /// use ros_pointcloud2::prelude::*;
     ///
-    /// let cloud_points: Vec<PointXYZ> = vec![
-    ///     PointXYZ::new(1.0, 2.0, 3.0),
-    ///     PointXYZ::new(4.0, 5.0, 6.0),
-    /// ];
-    ///
-    // let msg_out = PointCloud2Msg::try_from_iter(cloud_points).unwrap();
+    /// let point = PointXYZINormal::new(1.0, 2.0, 3.0, 0.5, 0.0, 0.0, 1.0);
+    /// let msg = PointCloud2Msg::try_from_vec(vec![point]).unwrap();
+    /// assert_eq!(msg.layout_matches::<7, PointXYZINormal>().unwrap(), MatchKind::Exact);
+    /// assert_eq!(msg.layout_matches::<4, PointXYZI>().unwrap(), MatchKind::Strided);
+    /// assert_eq!(msg.layout_matches::<4, PointXYZL>().unwrap(), MatchKind::RequiresConversion);
     /// ```
-    pub fn try_from_iter<const N: usize, C>(
-        iterable: impl IntoIterator<Item = C>,
-    ) -> Result<Self, MsgConversionError>
+    ///
+    /// # Errors
+    /// Returns an error if the layout of `C` is invalid or the message has fewer fields than `C`.
+    pub fn layout_matches<const N: usize, C>(&self) -> Result<MatchKind, MsgConversionError>
     where
         C: PointConvertible<N>,
     {
-        let (mut cloud, point_step) = {
-            let point: RPCL2Point<N> = C::default().into();
-            debug_assert!(point.fields.len() == N);
+        if system_endian() != self.endian {
+            return Ok(MatchKind::RequiresConversion);
+        }
 
-            let field_names = crate::ordered_field_names::<N, C>();
-            debug_assert!(field_names.len() == N);
+        self.byte_similarity::<N, C>()
+    }
 
-            let mut pdata_offsets_acc: u32 = 0;
-            let mut fields = vec![PointFieldMsg::default(); N];
-            let field_count: u32 = 1;
-            for ((pdata_entry, field_name), field_val) in point
-                .fields
-                .into_iter()
-                .zip(field_names.into_iter())
-                .zip(fields.iter_mut())
-            {
-                let datatype_code = pdata_entry.datatype.into();
-                let _ = FieldDatatype::try_from(datatype_code)?;
+    /// Number of points in the cloud, which is `width * height` of the [`dimensions`](PointCloud2Msg::dimensions).
+    ///
+    /// This counts points, not bytes. Use [`byte_len`](PointCloud2Msg::byte_len) for the size of the buffer.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let msg = PointCloud2Msg::try_from_vec(vec![PointXYZ::new(1.0, 2.0, 3.0); 3]).unwrap();
+    /// assert_eq!(msg.len(), 3);
+    /// assert_eq!(msg.byte_len(), 3 * msg.point_step as usize);
+    /// ```
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.dimensions.len()
+    }
 
-                *field_val = PointFieldMsg {
-                    name: field_name,
-                    offset: pdata_offsets_acc,
-                    datatype: datatype_code,
-                    count: 1,
-                };
+    /// Whether the cloud has no points.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.dimensions.is_empty()
+    }
 
-                pdata_offsets_acc += field_count * pdata_entry.datatype.size() as u32;
-            }
+    /// Number of bytes in the data buffer, including the padding between fields and rows.
+    #[must_use]
+    pub fn byte_len(&self) -> usize {
+        self.data.len()
+    }
 
-            (
-                PointCloud2MsgBuilder::new()
-                    .fields(fields)
-                    .point_step(pdata_offsets_acc),
-                pdata_offsets_acc,
-            )
-        };
-        let mut cloud_width = 0;
+    /// Number of bytes in the buffer after the points of the dimensions, e.g. padding added by a recorder.
+    ///
+    /// The conversions only read the declared points and ignore these bytes, so this allows detecting and deciding about
+    /// slightly malformed messages. A buffer that is too short for the dimensions has no trailing bytes and fails to convert.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let mut msg = PointCloud2Msg::try_from_vec(vec![PointXYZ::new(1.0, 2.0, 3.0)]).unwrap();
+    /// assert_eq!(msg.trailing_bytes(), 0);
+    ///
+    /// msg.data.extend_from_slice(&[0; 5]);
+    /// assert_eq!(msg.trailing_bytes(), 5);
+    /// assert_eq!(msg.try_into_iter().unwrap().collect::<Vec<PointXYZ>>().len(), 1);
+    /// ```
+    #[must_use]
+    pub fn trailing_bytes(&self) -> usize {
+        self.required_data_len()
+            .map_or(0, |required| self.data.len().saturating_sub(required))
+    }
 
-        iterable.into_iter().for_each(|pointdata| {
-            let point: RPCL2Point<N> = pointdata.into();
+    /// Number of points described by the dimensions of the message.
+    #[inline]
+    fn point_count(&self) -> usize {
+        self.dimensions.len()
+    }
 
-            point.fields.iter().for_each(|pdata| {
-                let truncated_bytes = unsafe {
-                    core::slice::from_raw_parts(pdata.bytes.as_ptr(), pdata.datatype.size())
-                };
-                cloud.data.extend_from_slice(truncated_bytes);
-            });
+    /// Whether the rows of an organized cloud have padding bytes after the last point.
+    #[inline]
+    fn has_row_padding(&self) -> bool {
+        self.dimensions.height > 1
+            && Some(self.row_step as usize)
+                != (self.dimensions.width as usize).checked_mul(self.point_step as usize)
+    }
 
-            cloud_width += 1;
-        });
+    /// Number of bytes of a row in the buffer. Rows of unorganized clouds are always packed.
+    #[inline]
+    fn row_len(&self) -> usize {
+        if self.has_row_padding() {
+            self.row_step as usize
+        } else {
+            self.dimensions.width as usize * self.point_step as usize
+        }
+    }
 
-        cloud = cloud.width(cloud_width);
-        cloud = cloud.row_step(cloud_width * point_step);
+    /// Byte offset of the point at `idx` in the buffer, skipping the padding between rows.
+    #[inline]
+    fn point_offset(&self, idx: usize) -> usize {
+        if self.has_row_padding() {
+            let width = self.dimensions.width as usize;
+            (idx / width) * self.row_step as usize + (idx % width) * self.point_step as usize
+        } else {
+            idx * self.point_step as usize
+        }
+    }
 
-        cloud.build()
+    /// Find a field by its name.
+    fn field_by_name(&self, name: &str) -> Result<&PointFieldMsg, MsgConversionError> {
+        self.fields
+            .iter()
+            .find(|field| field.name == name)
+            .ok_or_else(|| MsgConversionError::FieldsNotFound(vec![String::from(name).into()]))
     }
 
-    /// Create a PointCloud2Msg from a parallel iterator. Requires the `rayon` and `derive` feature to be enabled.
-    #[cfg(feature = "rayon")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
-    pub fn try_from_par_iter<const N: usize, C>(
-        iterable: impl rayon::iter::ParallelIterator<Item = C>,
-    ) -> Result<Self, MsgConversionError>
+    /// Byte offset of a field inside a point after checking that it is stored as `expected`.
+    fn field_offset_checked(
+        &self,
+        name: &str,
+        expected: FieldDatatype,
+    ) -> Result<usize, MsgConversionError> {
+        let field = self.field_by_name(name)?;
+        if !expected.matches_code(field.datatype) {
+            return Err(MsgConversionError::InvalidFieldFormat);
+        }
+
+        let offset = field.offset as usize;
+        if offset + expected.size() > self.point_step as usize {
+            return Err(MsgConversionError::DataLengthMismatch);
+        }
+
+        Ok(offset)
+    }
+
+    /// Offsets of the `x`, `y` and `z` fields, which need to be `f32`.
+    /// Also checks that the buffer holds all points of the dimensions.
+    fn xyz_f32_offsets(&self) -> Result<[usize; 3], MsgConversionError> {
+        let offsets = [
+            self.field_offset_checked("x", FieldDatatype::F32)?,
+            self.field_offset_checked("y", FieldDatatype::F32)?,
+            self.field_offset_checked("z", FieldDatatype::F32)?,
+        ];
+        self.check_data_len()?;
+
+        Ok(offsets)
+    }
+
+    /// Check that the buffer holds all points of the dimensions.
+    ///
+    /// Dimensions that overflow `usize` return [`MsgConversionError::NumberConversion`] instead of wrapping to a short length.
+    #[inline]
+    fn check_data_len(&self) -> Result<(), MsgConversionError> {
+        if self.data.len() < self.required_data_len()? {
+            return Err(MsgConversionError::DataLengthMismatch);
+        }
+
+        Ok(())
+    }
+
+    /// Number of bytes that hold the points of the dimensions, including the padding between rows.
+    fn required_data_len(&self) -> Result<usize, MsgConversionError> {
+        let point_step = self.point_step as usize;
+        if point_step == 0 && self.point_count() > 0 {
+            return Err(MsgConversionError::ZeroPointStep);
+        }
+
+        let required = if self.has_row_padding() {
+            let row_step = self.row_step as usize;
+            let packed_row_step = (self.dimensions.width as usize)
+                .checked_mul(point_step)
+                .ok_or(MsgConversionError::NumberConversion)?;
+            if row_step < packed_row_step {
+                return Err(MsgConversionError::DataLengthMismatch);
+            }
+            row_step.checked_mul(self.dimensions.height as usize)
+        } else {
+            self.dimensions
+                .checked_len()
+                .and_then(|len| len.checked_mul(point_step))
+        }
+        .ok_or(MsgConversionError::NumberConversion)?;
+
+        Ok(required)
+    }
+
+    /// Iterate over the values of a single field without decoding the other fields.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let cloud_points = vec![
+    ///     PointXYZI::new(1.0, 2.0, 3.0, 0.5),
+    ///     PointXYZI::new(4.0, 5.0, 6.0, 1.5),
+    /// ];
+    /// let msg = PointCloud2Msg::try_from_vec(cloud_points).unwrap();
+    ///
+    /// let intensities: Vec<f32> = msg.field_iter("intensity").unwrap().collect();
+    /// assert_eq!(intensities, vec![0.5, 1.5]);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the field is missing, `T` does not match its datatype or the buffer is too short.
+    pub fn field_iter<T: FromBytes>(
+        &self,
+        name: &str,
+    ) -> Result<impl Iterator<Item = T> + '_, MsgConversionError> {
+        let datatype = T::field_datatype();
+        let offset = self.field_offset_checked(name, datatype)?;
+        self.check_data_len()?;
+
+        Ok((0..self.point_count()).map(move |idx| {
+            PointData::from_buffer(
+                &self.data,
+                self.point_offset(idx) + offset,
+                datatype,
+                self.endian,
+            )
+            .get()
+        }))
+    }
+
+    /// Fold the values of a single field over all points, e.g. for sums, counts or histograms, without collecting them first.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let msg = PointCloud2Msg::try_from_vec(vec![
+    ///     PointXYZI::new(1.0, 2.0, 3.0, 0.5),
+    ///     PointXYZI::new(4.0, 5.0, 6.0, 1.5),
+    ///     PointXYZI::new(7.0, 8.0, 9.0, 2.5),
+    /// ])
+    /// .unwrap();
+    ///
+    /// let total = msg.reduce_field("intensity", 0.0, |sum, i: f32| sum + i).unwrap();
+    /// assert_eq!(total, 4.5);
+    ///
+    /// let bright = msg.reduce_field("intensity", 0, |count, i: f32| count + (i > 1.0) as usize).unwrap();
+    /// assert_eq!(bright, 2);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the field is missing, `T` does not match its datatype or the buffer is too short.
+    pub fn reduce_field<T: FromBytes, A>(
+        &self,
+        name: &str,
+        init: A,
+        f: impl FnMut(A, T) -> A,
+    ) -> Result<A, MsgConversionError> {
+        Ok(self.field_iter(name)?.fold(init, f))
+    }
+
+    /// Iterate over the values of all fields of every point when the point type is only known at runtime. Requires the `smallvec` feature.
+    ///
+    /// The values are in the order of the fields in the message and array fields yield one value per element.
+    /// Points with up to 8 values are stored inline, so the iteration does not allocate for the common point types.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let msg = PointCloud2Msg::try_from_vec(vec![PointXYZL::new(1.0, 2.0, 3.0, 7)]).unwrap();
+    ///
+    /// for point in msg.dynamic_iter().unwrap() {
+    ///     assert!(!point.spilled());
+    ///     assert_eq!(point[3].datatype(), FieldDatatype::U32);
+    ///     assert_eq!(point[3].get::<u32>(), 7);
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if a field has an unsupported datatype, does not fit into the point step or the buffer is too short.
+    #[cfg(feature = "smallvec")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "smallvec")))]
+    pub fn dynamic_iter(
+        &self,
+    ) -> Result<impl Iterator<Item = DynamicPoint> + '_, MsgConversionError> {
+        self.check_data_len()?;
+
+        let mut columns = Vec::new();
+        for field in self.fields.iter() {
+            let datatype = FieldDatatype::try_from(field)?;
+            for element in 0..field.count as usize {
+                let offset = field.offset as usize + element * datatype.size();
+                if offset + datatype.size() > self.point_step as usize {
+                    return Err(MsgConversionError::DataLengthMismatch);
+                }
+                columns.push((offset, datatype));
+            }
+        }
+
+        Ok((0..self.point_count()).map(move |idx| {
+            let start = self.point_offset(idx);
+            columns
+                .iter()
+                .map(|&(offset, datatype)| {
+                    PointData::from_buffer(&self.data, start + offset, datatype, self.endian)
+                })
+                .collect()
+        }))
+    }
+
+    /// Read the value of a single field of the point at `point_index`.
+    ///
+    /// Returns `Ok(None)` if the message has no field with this name, so consumers can handle optional fields
+    /// separately from malformed data.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let msg = PointCloud2Msg::try_from_vec(vec![PointXYZI::new(1.0, 2.0, 3.0, 0.5)]).unwrap();
+    ///
+    /// assert_eq!(msg.try_field_value::<f32>(0, "intensity").unwrap(), Some(0.5));
+    /// assert_eq!(msg.try_field_value::<f32>(0, "ring").unwrap(), None);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::InvalidArgument`] if the index is out of range,
+    /// [`MsgConversionError::InvalidFieldFormat`] if `T` does not match the datatype of the field
+    /// or an error if the buffer is too short.
+    pub fn try_field_value<T: FromBytes>(
+        &self,
+        point_index: usize,
+        name: &str,
+    ) -> Result<Option<T>, MsgConversionError> {
+        if point_index >= self.point_count() {
+            return Err(MsgConversionError::InvalidArgument);
+        }
+
+        if self.fields.iter().all(|field| field.name != name) {
+            return Ok(None);
+        }
+
+        let datatype = T::field_datatype();
+        let offset = self.field_offset_checked(name, datatype)?;
+        self.check_data_len()?;
+
+        Ok(Some(
+            PointData::from_buffer(
+                &self.data,
+                self.point_offset(point_index) + offset,
+                datatype,
+                self.endian,
+            )
+            .get(),
+        ))
+    }
+
+    /// Gather the raw bytes of one field of all points into a contiguous buffer.
+    ///
+    /// The bytes are copied as stored, so they keep the endian of the message. This is the building block
+    /// for column-oriented storage, where values of the same field compress much better than interleaved points.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let cloud_points = vec![
+    ///     PointXYZI::new(1.0, 2.0, 3.0, 0.5),
+    ///     PointXYZI::new(4.0, 5.0, 6.0, 1.5),
+    /// ];
+    /// let msg = PointCloud2Msg::try_from_vec(cloud_points).unwrap();
+    ///
+    /// let column = msg.field_bytes("intensity").unwrap();
+    /// assert_eq!(column.len(), 8);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the field is missing, has an unsupported datatype, does not fit into the point step or the buffer is too short.
+    pub fn field_bytes(&self, name: &str) -> Result<Vec<u8>, MsgConversionError> {
+        let field = self.field_by_name(name)?;
+        let size = FieldDatatype::try_from(field)?.size() * field.count as usize;
+        let offset = field.offset as usize;
+        if offset + size > self.point_step as usize {
+            return Err(MsgConversionError::DataLengthMismatch);
+        }
+        self.check_data_len()?;
+
+        let point_count = self.point_count();
+        let mut column = Vec::with_capacity(point_count * size);
+        for idx in 0..point_count {
+            let start = self.point_offset(idx) + offset;
+            column.extend_from_slice(&self.data[start..start + size]);
+        }
+
+        Ok(column)
+    }
+
+    /// Attach a scale and offset to a field, replacing a previous one for the same field.
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::FieldsNotFound`] if there is no field with the name.
+    pub fn set_field_transform(
+        &mut self,
+        name: &str,
+        scale: f64,
+        offset: f64,
+    ) -> Result<(), MsgConversionError> {
+        self.field_by_name(name)?;
+
+        let transform = FieldTransform {
+            name: String::from(name),
+            scale,
+            offset,
+        };
+        match self.field_transforms.iter_mut().find(|t| t.name == name) {
+            Some(existing) => *existing = transform,
+            None => self.field_transforms.push(transform),
+        }
+
+        Ok(())
+    }
+
+    /// Read a numeric field of all points as `f64` with its [`FieldTransform`] applied, e.g. LAS coordinates stored as integers.
+    ///
+    /// Fields without a transform are read unscaled. Values of array fields are returned one after another.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let cloud_points: Vec<(i32,)> = vec![(1500,), (-250,)];
+    /// let mut msg = PointCloud2Msg::try_from_iter(cloud_points).unwrap();
+    /// msg.rename_field("0", "x").unwrap();
+    /// msg.set_field_transform("x", 0.01, 1000.0).unwrap();
+    ///
+    /// assert_eq!(msg.decode_scaled_field_f64("x").unwrap(), vec![1015.0, 997.5]);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the field is missing, has an unsupported datatype, does not fit into the point step or the buffer is too short.
+    pub fn decode_scaled_field_f64(&self, name: &str) -> Result<Vec<f64>, MsgConversionError> {
+        let field = self.field_by_name(name)?;
+        let datatype = FieldDatatype::try_from(field)?;
+        let count = field.count as usize;
+        let offset = field.offset as usize;
+        if offset + datatype.size() * count > self.point_step as usize {
+            return Err(MsgConversionError::DataLengthMismatch);
+        }
+        self.check_data_len()?;
+
+        let (scale, bias) = self
+            .field_transforms
+            .iter()
+            .find(|t| t.name == name)
+            .map_or((1.0, 0.0), |t| (t.scale, t.offset));
+
+        let point_count = self.point_count();
+        let mut values = Vec::with_capacity(point_count * count);
+        for idx in 0..point_count {
+            let start = self.point_offset(idx) + offset;
+            for element in 0..count {
+                let value = PointData::from_buffer(
+                    &self.data,
+                    start + element * datatype.size(),
+                    datatype,
+                    self.endian,
+                );
+                values.push(value.to_f64() * scale + bias);
+            }
+        }
+
+        Ok(values)
+    }
+
+    /// Parallel version of [`field_iter`](PointCloud2Msg::field_iter). Requires the `rayon` feature to be enabled.
+    ///
+    /// This is useful for statistics over a single field of a large cloud, e.g. the mean intensity.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let cloud_points = vec![
+    ///     PointXYZI::new(1.0, 2.0, 3.0, 0.5),
+    ///     PointXYZI::new(4.0, 5.0, 6.0, 1.5),
+    /// ];
+    /// let msg = PointCloud2Msg::try_from_vec(cloud_points).unwrap();
+    ///
+    /// let sum: f32 = msg.par_field_iter::<f32>("intensity").unwrap().sum();
+    /// assert_eq!(sum, 2.0);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the field is missing, `T` does not match its datatype or the buffer is too short.
+    #[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+    #[cfg(feature = "rayon")]
+    pub fn par_field_iter<T: FromBytes + Send>(
+        &self,
+        name: &str,
+    ) -> Result<impl rayon::iter::IndexedParallelIterator<Item = T> + '_, MsgConversionError> {
+        use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+        let datatype = T::field_datatype();
+        let offset = self.field_offset_checked(name, datatype)?;
+        self.check_data_len()?;
+
+        Ok((0..self.point_count()).into_par_iter().map(move |idx| {
+            PointData::from_buffer(
+                &self.data,
+                self.point_offset(idx) + offset,
+                datatype,
+                self.endian,
+            )
+            .get()
+        }))
+    }
+
+    /// Apply `value * scale + bias` to a `f32` field of every point in place, e.g. to calibrate intensities.
+    ///
+    /// The buffer is edited directly without decoding the points.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let cloud_points = vec![PointXYZI::new(1.0, 2.0, 3.0, 0.5), PointXYZI::new(4.0, 5.0, 6.0, 1.5)];
+    /// let mut msg = PointCloud2Msg::try_from_vec(cloud_points).unwrap();
+    ///
+    /// msg.affine_field_f32("intensity", 2.0, 1.0).unwrap();
+    /// let intensities: Vec<f32> = msg.field_iter("intensity").unwrap().collect();
+    /// assert_eq!(intensities, vec![2.0, 4.0]);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the field is missing, not `f32` or the buffer is too short.
+    pub fn affine_field_f32(
+        &mut self,
+        name: &str,
+        scale: f32,
+        bias: f32,
+    ) -> Result<(), MsgConversionError> {
+        let offset = self.field_offset_checked(name, FieldDatatype::F32)?;
+        self.check_data_len()?;
+
+        for idx in 0..self.point_count() {
+            let byte_offset = self.point_offset(idx) + offset;
+            let value = self.read_f32_at(byte_offset) * scale + bias;
+            let bytes = match self.endian {
+                Endian::Big => value.to_be_bytes(),
+                Endian::Little => value.to_le_bytes(),
+            };
+            self.data[byte_offset..byte_offset + bytes.len()].copy_from_slice(&bytes);
+        }
+
+        Ok(())
+    }
+
+    /// Permute and negate the `x`, `y` and `z` values of every point in place, e.g. to convert from a z-forward camera frame
+    /// to an x-forward robot frame.
+    ///
+    /// The new value of each axis is the old value of the axis in `mapping` multiplied by the entry in `signs`,
+    /// so `([Axis::Z, Axis::X, Axis::Y], [1.0, -1.0, -1.0])` stores `z` in `x`, `-x` in `y` and `-y` in `z`.
+    /// The buffer is edited directly without decoding the points.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let mut msg = PointCloud2Msg::try_from_vec(vec![PointXYZ::new(1.0, 2.0, 3.0)]).unwrap();
+    ///
+    /// msg.remap_axes([Axis::Z, Axis::X, Axis::Y], [1.0, -1.0, -1.0]).unwrap();
+    /// let points: Vec<PointXYZ> = msg.try_into_vec().unwrap();
+    /// assert_eq!(points, vec![PointXYZ::new(3.0, -1.0, -2.0)]);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the `x`, `y` or `z` fields are missing or not `f32` or the buffer is too short.
+    pub fn remap_axes(
+        &mut self,
+        mapping: [Axis; 3],
+        signs: [f32; 3],
+    ) -> Result<(), MsgConversionError> {
+        let offsets = self.xyz_f32_offsets()?;
+
+        for idx in 0..self.point_count() {
+            let old = self.xyz_at(idx, &offsets);
+            let point_offset = self.point_offset(idx);
+            for ((axis, sign), offset) in mapping.iter().zip(signs).zip(offsets) {
+                let value = old[*axis as usize] * sign;
+                let bytes = match self.endian {
+                    Endian::Big => value.to_be_bytes(),
+                    Endian::Little => value.to_le_bytes(),
+                };
+                let byte_offset = point_offset + offset;
+                self.data[byte_offset..byte_offset + bytes.len()].copy_from_slice(&bytes);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Rename a field without touching the data, e.g. to match the field names a consumer expects.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let mut msg = PointCloud2Msg::try_from_vec(vec![PointXYZI::new(1.0, 2.0, 3.0, 0.5)]).unwrap();
+    /// msg.rename_field("intensity", "i").unwrap();
+    ///
+    /// let values: Vec<f32> = msg.field_iter("i").unwrap().collect();
+    /// assert_eq!(values, vec![0.5]);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::FieldsNotFound`] if there is no field named `from`
+    /// and [`MsgConversionError::InvalidArgument`] if another field is already named `to`.
+    pub fn rename_field(&mut self, from: &str, to: &str) -> Result<(), MsgConversionError> {
+        let idx = self
+            .fields
+            .iter()
+            .position(|field| field.name == from)
+            .ok_or_else(|| MsgConversionError::FieldsNotFound(vec![String::from(from).into()]))?;
+
+        if from != to && self.fields.iter().any(|field| field.name == to) {
+            return Err(MsgConversionError::InvalidArgument);
+        }
+
+        self.fields[idx].name = to.into();
+        if let Some(transform) = self.field_transforms.iter_mut().find(|t| t.name == from) {
+            transform.name = String::from(to);
+        }
+        Ok(())
+    }
+
+    /// Iterate over all points and yield every field value together with its name.
+    ///
+    /// This is the most generic way to read a cloud when the point type is not known at compile time,
+    /// e.g. for logging or inspection tools. Fields with a count greater than 1 yield one entry per element.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let msg = PointCloud2Msg::try_from_vec(vec![PointXYZI::new(1.0, 2.0, 3.0, 0.5)]).unwrap();
+    ///
+    /// let point = msg.try_into_named_iter().unwrap().next().unwrap();
+    /// let names: Vec<&str> = point.iter().map(|(name, _)| *name).collect();
+    /// assert_eq!(names, vec!["x", "y", "z", "intensity"]);
+    /// assert_eq!(point[3].1.get::<f32>(), 0.5);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if a field has an unsupported datatype, does not fit into the point step or the buffer is too short.
+    pub fn try_into_named_iter(
+        &self,
+    ) -> Result<impl Iterator<Item = Vec<(&str, PointData)>> + '_, MsgConversionError> {
+        let mut columns = Vec::with_capacity(self.fields.len());
+        for field in self.fields.iter() {
+            let datatype = FieldDatatype::try_from(field)?;
+            for element in 0..field.count as usize {
+                let offset = field.offset as usize + element * datatype.size();
+                if offset + datatype.size() > self.point_step as usize {
+                    return Err(MsgConversionError::DataLengthMismatch);
+                }
+                columns.push((field.name.as_str(), offset, datatype));
+            }
+        }
+        self.check_data_len()?;
+
+        Ok((0..self.point_count()).map(move |idx| {
+            let point_offset = self.point_offset(idx);
+            columns
+                .iter()
+                .map(|&(name, offset, datatype)| {
+                    let value = PointData::from_buffer(
+                        &self.data,
+                        point_offset + offset,
+                        datatype,
+                        self.endian,
+                    );
+                    (name, value)
+                })
+                .collect()
+        }))
+    }
+
+    /// Iterate over the first `K` `f32` values of each point as an array without defining a point type.
+    ///
+    /// The `f32` fields are taken in the order of [`fields`](PointCloud2Msg::fields) and fields of other datatypes are skipped.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let msg = PointCloud2Msg::try_from_vec(vec![PointXYZL::new(1.0, 2.0, 3.0, 7)]).unwrap();
+    ///
+    /// let points: Vec<[f32; 3]> = msg.try_into_array_iter().unwrap().collect();
+    /// assert_eq!(points, vec![[1.0, 2.0, 3.0]]);
+    /// assert!(msg.try_into_array_iter::<4>().is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::ExhaustedSource`] if the points have fewer than `K` `f32` values
+    /// or an error if the buffer is too short.
+    pub fn try_into_array_iter<const K: usize>(
+        &self,
+    ) -> Result<impl Iterator<Item = [f32; K]> + '_, MsgConversionError> {
+        let mut offsets = [0; K];
+        let mut found = 0;
+        'fields: for field in self.fields.iter() {
+            if !FieldDatatype::F32.matches_code(field.datatype) {
+                continue;
+            }
+
+            for element in 0..field.count as usize {
+                if found == K {
+                    break 'fields;
+                }
+
+                let offset = field.offset as usize + element * FieldDatatype::F32.size();
+                if offset + FieldDatatype::F32.size() > self.point_step as usize {
+                    return Err(MsgConversionError::DataLengthMismatch);
+                }
+                offsets[found] = offset;
+                found += 1;
+            }
+        }
+
+        if found < K {
+            return Err(MsgConversionError::ExhaustedSource);
+        }
+        self.check_data_len()?;
+
+        Ok((0..self.point_count()).map(move |idx| {
+            let point_offset = self.point_offset(idx);
+            let mut values = [0.0; K];
+            for (value, offset) in values.iter_mut().zip(offsets.iter()) {
+                *value = self.read_f32_at(point_offset + offset);
+            }
+            values
+        }))
+    }
+
+    #[inline]
+    fn read_f32_at(&self, byte_offset: usize) -> f32 {
+        PointData::from_buffer(&self.data, byte_offset, FieldDatatype::F32, self.endian).get()
+    }
+
+    #[inline]
+    fn xyz_at(&self, idx: usize, offsets: &[usize; 3]) -> [f32; 3] {
+        let point_offset = self.point_offset(idx);
+        [
+            self.read_f32_at(point_offset + offsets[0]),
+            self.read_f32_at(point_offset + offsets[1]),
+            self.read_f32_at(point_offset + offsets[2]),
+        ]
+    }
+
+    /// Check if both messages describe their points in the same way, ignoring header, dimensions and data.
+    ///
+    /// The fields are compared by name, offset, datatype and count in any order, together with the point step and endian.
+    /// Messages with the same schema can be decoded with the same point type and their buffers can be concatenated.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let a = PointCloud2Msg::try_from_vec(vec![PointXYZ::new(1.0, 2.0, 3.0)]).unwrap();
+    /// let b = PointCloud2Msg::try_from_vec(vec![PointXYZ::new(4.0, 5.0, 6.0); 10]).unwrap();
+    /// let c = PointCloud2Msg::try_from_vec(vec![PointXYZI::new(4.0, 5.0, 6.0, 0.1)]).unwrap();
+    ///
+    /// assert!(a.same_schema(&b));
+    /// assert!(!a.same_schema(&c));
+    /// ```
+    #[must_use]
+    pub fn same_schema(&self, other: &PointCloud2Msg) -> bool {
+        self.point_step == other.point_step
+            && self.endian == other.endian
+            && self.fields.len() == other.fields.len()
+            && self.fields.iter().all(|field| {
+                other.fields.iter().any(|o| {
+                    o.name == field.name
+                        && o.offset == field.offset
+                        && o.datatype == field.datatype
+                        && o.count == field.count
+                })
+            })
+    }
+
+    /// Name of the predefined point type in [`points`] that has exactly the fields of the message, or `None` if no type matches.
+    ///
+    /// The fields are compared by name, datatype and count in any order, so offsets and padding may differ from the point type,
+    /// e.g. after [`repack_tight`](PointCloud2Msg::repack_tight). This lets generic tools pick a decoder for incoming clouds.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let msg = PointCloud2Msg::try_from_vec(vec![PointXYZRGB::new(1.0, 2.0, 3.0, 255, 0, 0)]).unwrap();
+    /// assert_eq!(msg.detect_point_type(), Some("PointXYZRGB"));
+    ///
+    /// let custom = PointCloud2Msg::try_from_vec(vec![(1.0f32, 2.0f32)]).unwrap();
+    /// assert_eq!(custom.detect_point_type(), None);
+    /// ```
+    #[must_use]
+    pub fn detect_point_type(&self) -> Option<&'static str> {
+        use points::*;
+        type HasFields = fn(&PointCloud2Msg) -> bool;
+
+        let candidates: [(&'static str, HasFields); 12] = [
+            ("PointXYZ", Self::has_fields_of::<3, PointXYZ>),
+            ("PointXYZI", Self::has_fields_of::<4, PointXYZI>),
+            ("PointXYZL", Self::has_fields_of::<4, PointXYZL>),
+            ("PointXYZRGB", Self::has_fields_of::<4, PointXYZRGB>),
+            ("PointXYZRGBA", Self::has_fields_of::<5, PointXYZRGBA>),
+            ("PointXYZRGBL", Self::has_fields_of::<5, PointXYZRGBL>),
+            ("PointXYZNormal", Self::has_fields_of::<6, PointXYZNormal>),
+            ("PointXYZINormal", Self::has_fields_of::<7, PointXYZINormal>),
+            (
+                "PointXYZRGBNormal",
+                Self::has_fields_of::<7, PointXYZRGBNormal>,
+            ),
+            ("PointXYZLNormal", Self::has_fields_of::<8, PointXYZLNormal>),
+            ("PointNormal", Self::has_fields_of::<4, PointNormal>),
+            (
+                "PointXYZOctNormal",
+                Self::has_fields_of::<5, PointXYZOctNormal>,
+            ),
+        ];
+        candidates
+            .into_iter()
+            .find(|(_, has_fields)| has_fields(self))
+            .map(|(name, _)| name)
+    }
+
+    /// Check if the message has exactly the fields of `C` by name, datatype and count, ignoring offsets.
+    fn has_fields_of<const N: usize, C: PointConvertible<N>>(&self) -> bool {
+        let Ok(layout) = KnownLayoutInfo::try_from(C::layout()) else {
+            return false;
+        };
+        let names = ordered_layout_fields::<N, C>();
+        let datatypes = layout.fields.iter().filter_map(|field| match field {
+            PointField::Field {
+                datatype, count, ..
+            } => Some((*datatype, *count)),
+            PointField::Padding(_) => None,
+        });
+
+        names.len() == self.fields.len()
+            && names
+                .iter()
+                .zip(datatypes)
+                .all(|((name, _), (datatype, count))| {
+                    self.fields.iter().any(|field| {
+                        field.name == *name
+                            && datatype.matches_code(field.datatype)
+                            && field.count == count
+                    })
+                })
+    }
+
+    /// Check if both messages contain the same points when decoded as `C`, allowing float values to differ by up to `eps`.
+    ///
+    /// Integer values have to be equal and NaN values are equal to each other. The layout and endian of the messages may differ,
+    /// so round-trip tests can compare clouds without being fragile about float precision.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let a = PointCloud2Msg::try_from_vec(vec![PointXYZI::new(1.0, 2.0, 3.0, 0.5)]).unwrap();
+    /// let b = PointCloud2Msg::try_from_vec(vec![PointXYZI::new(1.0, 2.0, 3.000001, 0.5)]).unwrap();
+    ///
+    /// assert!(a.approx_eq::<4, PointXYZI>(&b, 1e-5).unwrap());
+    /// assert!(!a.approx_eq::<4, PointXYZI>(&b, 0.0).unwrap());
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if one of the messages can not be decoded as `C`.
+    pub fn approx_eq<const N: usize, C>(
+        &self,
+        other: &PointCloud2Msg,
+        eps: f32,
+    ) -> Result<bool, MsgConversionError>
+    where
+        C: PointConvertible<N>,
+    {
+        let within = |a: f64, b: f64| {
+            let diff = a - b;
+            (a.is_nan() && b.is_nan()) || (diff <= eps as f64 && -diff <= eps as f64)
+        };
+
+        let ours = self.clone().try_into_iter::<N, C>()?;
+        let theirs = other.clone().try_into_iter::<N, C>()?;
+        if self.point_count() != other.point_count() {
+            return Ok(false);
+        }
+
+        for (a, b) in ours.zip(theirs) {
+            let (a, b): (RPCL2Point<N>, RPCL2Point<N>) = (a.into(), b.into());
+            for (a, b) in a.fields.iter().zip(b.fields.iter()) {
+                let equal = match (a.datatype, b.datatype) {
+                    (FieldDatatype::F32, FieldDatatype::F32) => {
+                        within(a.get::<f32>() as f64, b.get::<f32>() as f64)
+                    }
+                    (FieldDatatype::F64, FieldDatatype::F64) => {
+                        within(a.get::<f64>(), b.get::<f64>())
+                    }
+                    _ => a.datatype == b.datatype && a.bytes == b.bytes,
+                };
+                if !equal {
+                    return Ok(false);
+                }
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Change the point type of the cloud from `CI` to `CO` with the [`From`] conversion between them.
+    ///
+    /// The conversion decides what happens to the fields. The predefined lossy conversions like `PointXYZRGB → PointXYZ`
+    /// drop the fields that are missing in the target and widening ones like `PointXYZ → PointXYZI` fill them with defaults.
+    /// The header, dimensions and dense flag are kept, rows of organized clouds are packed.
+    ///
+    /// Every point is decoded as `CI` and converted with `CO::from`, so custom conversions that change values are always applied.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let msg = PointCloud2Msg::try_from_vec(vec![PointXYZ::new(1.0, 2.0, 3.0)]).unwrap();
+    /// let msg = msg.cast::<3, PointXYZ, 4, PointXYZI>().unwrap();
+    /// assert_eq!(msg.fields.len(), 4);
+    ///
+    /// let points: Vec<PointXYZI> = msg.try_into_iter().unwrap().collect();
+    /// assert_eq!(points, vec![PointXYZI::new(1.0, 2.0, 3.0, 0.0)]);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the message can not be decoded as `CI` or `CO` can not be described by the message.
+    pub fn cast<const NI: usize, CI, const NO: usize, CO>(
+        &self,
+    ) -> Result<PointCloud2Msg, MsgConversionError>
+    where
+        CI: PointConvertible<NI>,
+        CO: PointConvertible<NO> + From<CI>,
+    {
+        let mut writer = PointCloud2Writer::<NO, CO>::new()?;
+
+        for point in self.borrowed_iter::<NI, CI>(false)? {
+            writer.push(CO::from(point))?;
+        }
+
+        let mut msg = writer.finish()?;
+        msg.keep_metadata_of(self)?;
+        Ok(msg)
+    }
+
+    /// Create a copy of the cloud with all fields of `C`, e.g. to publish [`PointXYZI`](points::PointXYZI) on a fixed-schema topic
+    /// when only [`PointXYZ`](points::PointXYZ) is available.
+    ///
+    /// The fields of `C` that exist in the message are copied and the others are filled with zero like in
+    /// [`try_into_iter_lossy`](PointCloud2Msg::try_into_iter_lossy). Fields that are not part of `C` are dropped.
+    /// The header, dimensions and dense flag are kept, rows of organized clouds are packed.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let msg = PointCloud2Msg::try_from_vec(vec![PointXYZ::new(1.0, 2.0, 3.0)]).unwrap();
+    /// let wide = msg.widen_to::<4, PointXYZI>().unwrap();
+    ///
+    /// assert_eq!(wide.fields.len(), 4);
+    /// let points: Vec<PointXYZI> = wide.try_into_iter().unwrap().collect();
+    /// assert_eq!(points, vec![PointXYZI::new(1.0, 2.0, 3.0, 0.0)]);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the message has no `x`, `y` and `z` fields, a shared field has another datatype or the buffer is too short.
+    pub fn widen_to<const N: usize, C>(&self) -> Result<PointCloud2Msg, MsgConversionError>
+    where
+        C: PointConvertible<N>,
+    {
+        let mut msg = Self::try_from_iter(self.borrowed_iter::<N, C>(true)?)?;
+        msg.keep_metadata_of(self)?;
+        Ok(msg)
+    }
+
+    /// Take the header, dimensions, dense flag and the transforms of the remaining fields from the cloud this one was converted from.
+    /// The rows are packed.
+    fn keep_metadata_of(&mut self, source: &PointCloud2Msg) -> Result<(), MsgConversionError> {
+        self.row_step = source
+            .dimensions
+            .width
+            .checked_mul(self.point_step)
+            .ok_or(MsgConversionError::NumberConversion)?;
+        self.header = source.header.clone();
+        self.dimensions = source.dimensions.clone();
+        self.dense = source.dense;
+        self.field_transforms = source
+            .field_transforms
+            .iter()
+            .filter(|transform| self.fields.iter().any(|field| field.name == transform.name))
+            .cloned()
+            .collect();
+
+        Ok(())
+    }
+
+    /// Check that the message has exactly the given fields with their datatypes in any order.
+    ///
+    /// This is useful to validate the first message of a topic before committing to a point type.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let msg = PointCloud2Msg::try_from_vec(vec![PointXYZI::new(1.0, 2.0, 3.0, 0.5)]).unwrap();
+    /// assert!(msg
+    ///     .expect_schema(&[
+    ///         ("intensity", FieldDatatype::F32),
+    ///         ("x", FieldDatatype::F32),
+    ///         ("y", FieldDatatype::F32),
+    ///         ("z", FieldDatatype::F32),
+    ///     ])
+    ///     .is_ok());
+    /// assert!(msg.expect_schema(&[("x", FieldDatatype::F32)]).is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::SchemaMismatch`] with the names of all missing, unexpected and differently typed fields.
+    pub fn expect_schema(
+        &self,
+        expected: &[(&str, FieldDatatype)],
+    ) -> Result<(), MsgConversionError> {
+        let mut missing = Vec::new();
+        let mut mismatched = Vec::new();
+        for (name, datatype) in expected.iter() {
+            match self.fields.iter().find(|field| field.name == *name) {
+                None => missing.push(String::from(*name)),
+                Some(field) if !datatype.matches_code(field.datatype) => {
+                    mismatched.push(String::from(*name));
+                }
+                Some(_) => {}
+            }
+        }
+
+        let extra: Vec<String> = self
+            .fields
+            .iter()
+            .filter(|field| !expected.iter().any(|(name, _)| field.name == *name))
+            .map(|field| field.name.clone())
+            .collect();
+
+        if missing.is_empty() && extra.is_empty() && mismatched.is_empty() {
+            Ok(())
+        } else {
+            Err(MsgConversionError::SchemaMismatch {
+                missing,
+                extra,
+                mismatched,
+            })
+        }
+    }
+
+    /// Create an unorganized copy of the cloud that contains the points at the given indices in that order.
+    fn select_points(&self, indices: &[usize]) -> Result<PointCloud2Msg, MsgConversionError> {
+        let point_step = self.point_step as usize;
+        let mut data = Vec::with_capacity(indices.len() * point_step);
+        for &idx in indices.iter() {
+            let offset = self.point_offset(idx);
+            let point = self
+                .data
+                .get(offset..offset + point_step)
+                .ok_or(MsgConversionError::DataLengthMismatch)?;
+            data.extend_from_slice(point);
+        }
+
+        Ok(PointCloud2Msg {
+            header: self.header.clone(),
+            dimensions: CloudDimensionsBuilder::new_with_width(indices.len()).build()?,
+            fields: self.fields.clone(),
+            endian: self.endian,
+            point_step: self.point_step,
+            row_step: u32::try_from(data.len())?,
+            data,
+            dense: self.dense,
+            field_transforms: self.field_transforms.clone(),
+        })
+    }
+
+    /// Randomly keep `keep` points with a deterministic sampling for the given `seed`.
+    ///
+    /// The points are chosen with reservoir sampling, keep their relative order and are copied with all fields into a new unorganized cloud.
+    /// If the cloud has `keep` or fewer points, all points are kept.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let cloud_points = (0..100).map(|i| PointXYZ::new(i as f32, 0.0, 0.0)).collect::<Vec<_>>();
+    /// let msg = PointCloud2Msg::try_from_vec(cloud_points).unwrap();
+    ///
+    /// let preview = msg.random_subsample(10, 42).unwrap();
+    /// assert_eq!(preview.dimensions.width, 10);
+    /// assert_eq!(preview.data, msg.random_subsample(10, 42).unwrap().data);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the buffer is too short for the dimensions of the cloud.
+    pub fn random_subsample(
+        &self,
+        keep: usize,
+        seed: u64,
+    ) -> Result<PointCloud2Msg, MsgConversionError> {
+        let point_count = self.point_count();
+        let mut rng = SplitMix64::new(seed);
+
+        let mut reservoir: Vec<usize> = (0..keep.min(point_count)).collect();
+        for idx in keep..point_count {
+            let candidate = rng.next_below(idx as u64 + 1) as usize;
+            if candidate < keep {
+                reservoir[candidate] = idx;
+            }
+        }
+        reservoir.sort_unstable();
+
+        self.select_points(&reservoir)
+    }
+
+    /// Keep every `step`-th point, starting with the first one, e.g. to throttle a topic that is too heavy for the network.
+    ///
+    /// The points are copied with all fields into a new unorganized cloud. Organized clouds are traversed row by row.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let cloud_points = (0..10).map(|i| PointXYZ::new(i as f32, 0.0, 0.0)).collect::<Vec<_>>();
+    /// let msg = PointCloud2Msg::try_from_vec(cloud_points).unwrap();
+    ///
+    /// let decimated: Vec<PointXYZ> = msg.decimate(4).unwrap().try_into_vec().unwrap();
+    /// assert_eq!(decimated.iter().map(|p| p.x).collect::<Vec<_>>(), vec![0.0, 4.0, 8.0]);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::InvalidArgument`] if `step` is 0 and an error if the buffer is too short.
+    pub fn decimate(&self, step: usize) -> Result<PointCloud2Msg, MsgConversionError> {
+        if step == 0 {
+            return Err(MsgConversionError::InvalidArgument);
+        }
+
+        let indices: Vec<usize> = (0..self.point_count()).step_by(step).collect();
+        self.select_points(&indices)
+    }
+
+    /// Shrink the cloud to at most the first `max_points` points, e.g. for previews or bandwidth throttling.
+    ///
+    /// Organized clouds are cut at row boundaries, so only the complete rows that fit into `max_points` are kept
+    /// and the width stays the same. If not even one row fits, the cloud becomes an empty unorganized cloud.
+    /// Clouds with `max_points` or fewer points are not changed.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let cloud_points = (0..10).map(|i| PointXYZ::new(i as f32, 0.0, 0.0)).collect::<Vec<_>>();
+    /// let mut msg = PointCloud2Msg::try_from_vec(cloud_points).unwrap();
+    ///
+    /// msg.truncate(3);
+    /// assert_eq!(msg.dimensions.width, 3);
+    /// assert_eq!(msg.data.len(), 3 * msg.point_step as usize);
+    /// ```
+    pub fn truncate(&mut self, max_points: usize) {
+        if self.point_count() <= max_points {
+            return;
+        }
+
+        let point_step = self.point_step as usize;
+        if self.dimensions.height > 1 {
+            let rows = max_points / self.dimensions.width as usize;
+            if rows > 1 {
+                let row_len = self.row_len();
+                self.dimensions.height = rows as u32;
+                self.data.truncate(rows * row_len);
+                return;
+            }
+
+            if rows == 0 {
+                self.dimensions.width = 0;
+            }
+            self.dimensions.height = 1;
+        } else {
+            self.dimensions.width = max_points as u32;
+        }
+
+        // A single row is stored packed like any unorganized cloud.
+        let len = self.dimensions.width as usize * point_step;
+        self.row_step = len as u32;
+        self.data.truncate(len);
+    }
+
+    /// Permute the points in place with a deterministic Fisher–Yates shuffle for the given `seed`.
+    ///
+    /// Whole points are swapped, so all fields are kept without decoding them. This breaks the spatial order of the points,
+    /// e.g. for models that expect unordered input. Organized clouds become unorganized.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let cloud_points = (0..10).map(|i| PointXYZ::new(i as f32, 0.0, 0.0)).collect::<Vec<_>>();
+    /// let mut msg = PointCloud2Msg::try_from_vec(cloud_points).unwrap();
+    /// let mut same_seed = msg.clone();
+    ///
+    /// msg.shuffle(42).unwrap();
+    /// same_seed.shuffle(42).unwrap();
+    /// assert_eq!(msg.data, same_seed.data);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the buffer is too short for the dimensions of the cloud.
+    pub fn shuffle(&mut self, seed: u64) -> Result<(), MsgConversionError> {
+        self.check_data_len()?;
+
+        let point_count = self.point_count();
+        let dimensions = CloudDimensionsBuilder::new_with_width(point_count).build()?;
+        let point_step = self.point_step as usize;
+        if self.has_row_padding() {
+            let mut data = Vec::with_capacity(point_count * point_step);
+            for idx in 0..point_count {
+                let offset = self.point_offset(idx);
+                data.extend_from_slice(&self.data[offset..offset + point_step]);
+            }
+            self.data = data;
+        } else {
+            self.data.truncate(point_count * point_step);
+        }
+
+        let mut rng = SplitMix64::new(seed);
+        for idx in (1..point_count).rev() {
+            let other = rng.next_below(idx as u64 + 1) as usize;
+            if other != idx {
+                let (head, tail) = self.data.split_at_mut(idx * point_step);
+                head[other * point_step..(other + 1) * point_step]
+                    .swap_with_slice(&mut tail[..point_step]);
+            }
+        }
+
+        self.row_step = dimensions.width * self.point_step;
+        self.dimensions = dimensions;
+        Ok(())
+    }
+
+    /// Append the points of `other` to this cloud, converting them into the layout of this cloud if necessary.
+    ///
+    /// Both clouds need the same field names with the same datatypes and counts, but the offsets, padding and endian may differ,
+    /// e.g. for clouds of two drivers that emit the same fields. Clouds with the same schema are appended by direct copy.
+    /// The result is unorganized and keeps the header of this cloud.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let mut padded = PointCloud2Msg::try_from_vec(vec![PointXYZ::new(1.0, 2.0, 3.0)]).unwrap();
+    /// let tight = PointCloud2Msg::try_from_vec(vec![PointXYZ::new(4.0, 5.0, 6.0)])
+    ///     .unwrap()
+    ///     .repack_tight()
+    ///     .unwrap();
+    ///
+    /// padded.try_append_reproject(&tight).unwrap();
+    /// let points: Vec<PointXYZ> = padded.try_into_vec().unwrap();
+    /// assert_eq!(points, vec![PointXYZ::new(1.0, 2.0, 3.0), PointXYZ::new(4.0, 5.0, 6.0)]);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::SchemaMismatch`] if the field names, datatypes or counts differ.
+    /// Also returns an error if a datatype is unsupported or a buffer is too short.
+    pub fn try_append_reproject(
+        &mut self,
+        other: &PointCloud2Msg,
+    ) -> Result<(), MsgConversionError> {
+        let missing: Vec<String> = self
+            .fields
+            .iter()
+            .filter(|field| !other.fields.iter().any(|f| f.name == field.name))
+            .map(|field| field.name.clone())
+            .collect();
+        let extra: Vec<String> = other
+            .fields
+            .iter()
+            .filter(|field| !self.fields.iter().any(|f| f.name == field.name))
+            .map(|field| field.name.clone())
+            .collect();
+        let mismatched: Vec<String> = other
+            .fields
+            .iter()
+            .filter(|field| {
+                self.fields.iter().any(|f| {
+                    f.name == field.name && (f.datatype != field.datatype || f.count != field.count)
+                })
+            })
+            .map(|field| field.name.clone())
+            .collect();
+
+        if !missing.is_empty() || !extra.is_empty() || !mismatched.is_empty() {
+            return Err(MsgConversionError::SchemaMismatch {
+                missing,
+                extra,
+                mismatched,
+            });
+        }
+
+        self.check_data_len()?;
+        other.check_data_len()?;
+
+        // (target offset, source offset, element size, count) of every field
+        let mut columns = Vec::with_capacity(self.fields.len());
+        for field in self.fields.iter() {
+            let size = FieldDatatype::try_from(field)?.size();
+            let source = other.field_by_name(&field.name)?;
+            let count = field.count as usize;
+            if source.offset as usize + size * count > other.point_step as usize {
+                return Err(MsgConversionError::DataLengthMismatch);
+            }
+            columns.push((field.offset as usize, source.offset as usize, size, count));
+        }
+
+        let point_step = self.point_step as usize;
+        let own_count = self.point_count();
+        let other_count = other.point_count();
+        let total = own_count
+            .checked_add(other_count)
+            .ok_or(MsgConversionError::NumberConversion)?;
+
+        let mut data = Vec::with_capacity(total * point_step);
+        if self.has_row_padding() {
+            for idx in 0..own_count {
+                let offset = self.point_offset(idx);
+                data.extend_from_slice(&self.data[offset..offset + point_step]);
+            }
+        } else {
+            data.extend_from_slice(&self.data[..own_count * point_step]);
+        }
+
+        if self.same_schema(other) && !other.has_row_padding() {
+            data.extend_from_slice(&other.data[..other_count * point_step]);
+        } else {
+            let swap = self.endian != other.endian;
+            for idx in 0..other_count {
+                let source = other.point_offset(idx);
+                let target = data.len();
+                data.resize(target + point_step, 0);
+                for &(target_offset, source_offset, size, count) in columns.iter() {
+                    for element in 0..count {
+                        let from = source + source_offset + element * size;
+                        let to = target + target_offset + element * size;
+                        data[to..to + size].copy_from_slice(&other.data[from..from + size]);
+                        if swap {
+                            data[to..to + size].reverse();
+                        }
+                    }
+                }
+            }
+        }
+
+        self.dimensions = CloudDimensionsBuilder::new_with_width(total).build()?;
+        self.row_step = u32::try_from(data.len())?;
+        self.data = data;
+        if other.dense == Denseness::Sparse {
+            self.dense = Denseness::Sparse;
+        }
+
+        Ok(())
+    }
+
+    /// A reproducible hash of the cloud without the header, e.g. to detect that a cloud did not change since the last frame
+    /// or as a key for content-addressed storage.
+    ///
+    /// The hash covers the fields in their order, the point step, row step, endian, dimensions and the bytes of the buffer including padding.
+    /// It uses 64 bit FNV-1a, so the value is the same across runs, platforms and versions of this crate.
+    /// It is not a cryptographic hash.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let mut a = PointCloud2Msg::try_from_vec(vec![PointXYZ::new(1.0, 2.0, 3.0)]).unwrap();
+    /// let b = a.clone();
+    /// a.header.stamp.sec = 42;
+    /// assert_eq!(a.data_fingerprint(), b.data_fingerprint());
+    ///
+    /// let c = PointCloud2Msg::try_from_vec(vec![PointXYZ::new(1.0, 2.0, 4.0)]).unwrap();
+    /// assert_ne!(a.data_fingerprint(), c.data_fingerprint());
+    /// ```
+    #[must_use]
+    pub fn data_fingerprint(&self) -> u64 {
+        let mut hasher = Fnv1a64::new();
+        hasher.write_u32(self.fields.len() as u32);
+        for field in self.fields.iter() {
+            hasher.write_u32(field.name.len() as u32);
+            hasher.write(field.name.as_bytes());
+            hasher.write_u32(field.offset);
+            hasher.write(&[field.datatype]);
+            hasher.write_u32(field.count);
+        }
+        hasher.write_u32(self.point_step);
+        hasher.write_u32(self.row_step);
+        hasher.write(&[self.endian.to_ros_bool() as u8]);
+        hasher.write_u32(self.dimensions.width);
+        hasher.write_u32(self.dimensions.height);
+        hasher.write(&self.data);
+        hasher.finish()
+    }
+
+    /// Append the rows of the organized cloud `other` below the rows of this cloud, e.g. to assemble a frame from scan-line batches.
+    ///
+    /// Unlike [`try_append_reproject`](PointCloud2Msg::try_append_reproject), the result stays organized with the combined height.
+    /// The padding at the end of the rows is written as zeros and the header of this cloud is kept.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let mut frame = PointCloud2Msg::try_from_vec(vec![PointXYZ::new(0.0, 0.0, 1.0); 4]).unwrap();
+    /// let batch = PointCloud2Msg::try_from_vec(vec![PointXYZ::new(0.0, 1.0, 1.0); 4]).unwrap();
+    ///
+    /// frame.stack_rows(&batch).unwrap();
+    /// frame.stack_rows(&batch).unwrap();
+    /// assert_eq!(frame.dimensions.width, 4);
+    /// assert_eq!(frame.dimensions.height, 3);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::InvalidArgument`] if the width or row step differ and [`MsgConversionError::InvalidFieldFormat`]
+    /// if the clouds do not have the [same schema](PointCloud2Msg::same_schema).
+    /// Also returns an error if a buffer is too short or the row step is smaller than a row of points.
+    pub fn stack_rows(&mut self, other: &PointCloud2Msg) -> Result<(), MsgConversionError> {
+        if self.dimensions.width != other.dimensions.width || self.row_step != other.row_step {
+            return Err(MsgConversionError::InvalidArgument);
+        }
+        if !self.same_schema(other) {
+            return Err(MsgConversionError::InvalidFieldFormat);
+        }
+
+        self.check_data_len()?;
+        other.check_data_len()?;
+
+        let row_step = self.row_step as usize;
+        let packed_row_len = self.dimensions.width as usize * self.point_step as usize;
+        if row_step < packed_row_len {
+            return Err(MsgConversionError::DataLengthMismatch);
+        }
+
+        let height = self
+            .dimensions
+            .height
+            .checked_add(other.dimensions.height)
+            .ok_or(MsgConversionError::NumberConversion)?;
+        let mut data = Vec::with_capacity(height as usize * row_step);
+        for cloud in [&*self, other] {
+            let row_len = cloud.row_len();
+            for row in 0..cloud.dimensions.height as usize {
+                let start = row * row_len;
+                data.extend_from_slice(&cloud.data[start..start + packed_row_len]);
+                data.resize(data.len() + row_step - packed_row_len, 0);
+            }
+        }
+
+        self.dimensions.height = height;
+        self.data = data;
+        if other.dense == Denseness::Sparse {
+            self.dense = Denseness::Sparse;
+        }
+
+        Ok(())
+    }
+
+    /// Concatenate clouds with identical schemas and drop points that are within `eps` of an already kept point.
+    ///
+    /// This removes the duplicates at the seams of overlapping scans. The points are compared with a voxel hash of resolution `eps`,
+    /// so only the neighboring voxels are searched. All fields of the kept points are copied and points with non-finite coordinates are always kept.
+    /// The result is unorganized and uses the header of the first cloud.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let scan_a = PointCloud2Msg::try_from_vec(vec![PointXYZ::new(0.0, 0.0, 0.0), PointXYZ::new(1.0, 0.0, 0.0)]).unwrap();
+    /// let scan_b = PointCloud2Msg::try_from_vec(vec![PointXYZ::new(1.0, 0.0, 0.001), PointXYZ::new(2.0, 0.0, 0.0)]).unwrap();
+    ///
+    /// let merged = PointCloud2Msg::merge_dedup(&[&scan_a, &scan_b], 0.01).unwrap();
+    /// assert_eq!(merged.dimensions.width, 3);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::SchemaMismatch`] if the fields of the clouds differ, [`MsgConversionError::InvalidFieldFormat`]
+    /// if the point step or endian differ and [`MsgConversionError::InvalidArgument`] if there are no clouds or `eps` is not positive.
+    /// Also returns an error if the `x`, `y` or `z` fields are missing or not `f32` or a buffer is too short.
+    pub fn merge_dedup(
+        clouds: &[&PointCloud2Msg],
+        eps: f32,
+    ) -> Result<PointCloud2Msg, MsgConversionError> {
+        let first = clouds.first().ok_or(MsgConversionError::InvalidArgument)?;
+        if !(eps > 0.0 && eps.is_finite()) {
+            return Err(MsgConversionError::InvalidArgument);
+        }
+
+        for cloud in clouds.iter().skip(1) {
+            let missing: Vec<String> = first
+                .fields
+                .iter()
+                .filter(|field| !cloud.fields.iter().any(|f| f.name == field.name))
+                .map(|field| field.name.clone())
+                .collect();
+            let extra: Vec<String> = cloud
+                .fields
+                .iter()
+                .filter(|field| !first.fields.iter().any(|f| f.name == field.name))
+                .map(|field| field.name.clone())
+                .collect();
+            let mismatched: Vec<String> = cloud
+                .fields
+                .iter()
+                .filter(|field| {
+                    first.fields.iter().any(|f| {
+                        f.name == field.name
+                            && (f.offset != field.offset
+                                || f.datatype != field.datatype
+                                || f.count != field.count)
+                    })
+                })
+                .map(|field| field.name.clone())
+                .collect();
+
+            if !missing.is_empty() || !extra.is_empty() || !mismatched.is_empty() {
+                return Err(MsgConversionError::SchemaMismatch {
+                    missing,
+                    extra,
+                    mismatched,
+                });
+            }
+
+            if cloud.point_step != first.point_step || cloud.endian != first.endian {
+                return Err(MsgConversionError::InvalidFieldFormat);
+            }
+        }
+
+        let point_step = first.point_step as usize;
+        let eps_sq = eps * eps;
+        let mut voxels: alloc::collections::BTreeMap<[i64; 3], Vec<[f32; 3]>> =
+            alloc::collections::BTreeMap::new();
+        let mut data = Vec::new();
+        let mut kept = 0;
+        let mut dense = Denseness::Dense;
+        for cloud in clouds.iter() {
+            let offsets = cloud.xyz_f32_offsets()?;
+            if cloud.dense == Denseness::Sparse {
+                dense = Denseness::Sparse;
+            }
+
+            for idx in 0..cloud.point_count() {
+                let point = cloud.xyz_at(idx, &offsets);
+                if point.iter().all(|coord| coord.is_finite()) {
+                    let voxel = point.map(|coord| floor_to_i64(coord / eps));
+                    let mut duplicate = false;
+                    'search: for dx in -1..=1 {
+                        for dy in -1..=1 {
+                            for dz in -1..=1 {
+                                let neighbor = [voxel[0] + dx, voxel[1] + dy, voxel[2] + dz];
+                                let Some(candidates) = voxels.get(&neighbor) else {
+                                    continue;
+                                };
+                                if candidates.iter().any(|other| {
+                                    let d = [
+                                        point[0] - other[0],
+                                        point[1] - other[1],
+                                        point[2] - other[2],
+                                    ];
+                                    d[0] * d[0] + d[1] * d[1] + d[2] * d[2] <= eps_sq
+                                }) {
+                                    duplicate = true;
+                                    break 'search;
+                                }
+                            }
+                        }
+                    }
+
+                    if duplicate {
+                        continue;
+                    }
+                    voxels.entry(voxel).or_default().push(point);
+                }
+
+                let offset = cloud.point_offset(idx);
+                data.extend_from_slice(&cloud.data[offset..offset + point_step]);
+                kept += 1;
+            }
+        }
+
+        Ok(PointCloud2Msg {
+            header: first.header.clone(),
+            dimensions: CloudDimensionsBuilder::new_with_width(kept).build()?,
+            fields: first.fields.clone(),
+            endian: first.endian,
+            point_step: first.point_step,
+            row_step: u32::try_from(data.len())?,
+            data,
+            dense,
+            field_transforms: first.field_transforms.clone(),
+        })
+    }
+
+    /// Iterate over the rows of the cloud as raw bytes, e.g. the rings of a rotating LiDAR organized by ring.
+    ///
+    /// Each slice spans `row_step` bytes including the padding after the last point of the row.
+    /// Unorganized clouds yield a single row.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let mut msg = PointCloud2Msg::try_from_vec(vec![PointXYZ::new(1.0, 2.0, 3.0); 6]).unwrap();
+    /// msg.dimensions.width = 3;
+    /// msg.dimensions.height = 2;
+    /// msg.row_step = 3 * msg.point_step;
+    ///
+    /// assert_eq!(msg.rows().unwrap().count(), 2);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the buffer is too short for the dimensions.
+    pub fn rows(&self) -> Result<impl Iterator<Item = &[u8]> + '_, MsgConversionError> {
+        self.check_data_len()?;
+        let row_step = self.row_len();
+
+        Ok((0..self.dimensions.height as usize)
+            .map(move |row| &self.data[row * row_step..(row + 1) * row_step]))
+    }
+
+    /// Decode the points of a single row of an organized cloud.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let cloud_points = vec![
+    ///     PointXYZ::new(0.0, 0.0, 0.0),
+    ///     PointXYZ::new(1.0, 0.0, 0.0),
+    ///     PointXYZ::new(0.0, 1.0, 0.0),
+    ///     PointXYZ::new(1.0, 1.0, 0.0),
+    /// ];
+    /// let mut msg = PointCloud2Msg::try_from_vec(cloud_points.clone()).unwrap();
+    /// msg.dimensions.width = 2;
+    /// msg.dimensions.height = 2;
+    /// msg.row_step = 2 * msg.point_step;
+    ///
+    /// let second: Vec<PointXYZ> = msg.row_points(1).unwrap();
+    /// assert_eq!(second, cloud_points[2..]);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::InvalidArgument`] if the row is out of range.
+    /// Otherwise, the same errors as [`try_into_iter`](PointCloud2Msg::try_into_iter) are returned.
+    pub fn row_points<const N: usize, C>(&self, row: usize) -> Result<Vec<C>, MsgConversionError>
+    where
+        C: PointConvertible<N>,
+    {
+        let bytes = self
+            .rows()?
+            .nth(row)
+            .ok_or(MsgConversionError::InvalidArgument)?;
+        let packed_len = self.dimensions.width as usize * self.point_step as usize;
+
+        let row_cloud = PointCloud2Msg {
+            header: self.header.clone(),
+            dimensions: CloudDimensionsBuilder::new_with_width(self.dimensions.width as usize)
+                .build()?,
+            fields: self.fields.clone(),
+            endian: self.endian,
+            point_step: self.point_step,
+            row_step: u32::try_from(packed_len)?,
+            data: bytes[..packed_len].to_vec(),
+            dense: self.dense,
+            field_transforms: self.field_transforms.clone(),
+        };
+
+        Ok(row_cloud.try_into_iter()?.collect())
+    }
+
+    /// Extract the color image of an organized cloud with an `rgb` field.
+    ///
+    /// Returns width, height and the pixels as row-major RGB8 bytes, which can be handed to image libraries directly.
+    /// Padding between rows is skipped.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let cloud_points = vec![
+    ///     PointXYZRGB::new(0.0, 0.0, 1.0, 255, 0, 0),
+    ///     PointXYZRGB::new(1.0, 0.0, 1.0, 0, 255, 0),
+    ///     PointXYZRGB::new(0.0, 1.0, 1.0, 0, 0, 255),
+    ///     PointXYZRGB::new(1.0, 1.0, 1.0, 1, 2, 3),
+    /// ];
+    /// let mut msg = PointCloud2Msg::try_from_vec(cloud_points).unwrap();
+    /// msg.dimensions.width = 2;
+    /// msg.dimensions.height = 2;
+    /// msg.row_step = 2 * msg.point_step;
+    ///
+    /// let (width, height, pixels) = msg.to_rgb_image().unwrap();
+    /// assert_eq!((width, height), (2, 2));
+    /// assert_eq!(&pixels[9..12], &[1, 2, 3]);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::InvalidArgument`] if the cloud is not organized.
+    /// Returns an error if the `rgb` field is missing, has a wrong datatype or the buffer is too short.
+    pub fn to_rgb_image(&self) -> Result<(u32, u32, Vec<u8>), MsgConversionError> {
+        if self.dimensions.height <= 1 {
+            return Err(MsgConversionError::InvalidArgument);
+        }
+
+        let colors = self.field_iter::<points::RGB>("rgb")?;
+        let mut pixels = Vec::with_capacity(self.point_count() * 3);
+        for color in colors {
+            pixels.extend_from_slice(&[color.r(), color.g(), color.b()]);
+        }
+
+        Ok((self.dimensions.width, self.dimensions.height, pixels))
+    }
+
+    /// Split a labeled cloud into one unorganized cloud per distinct label, e.g. the output of a segmentation.
+    ///
+    /// The label field needs an unsigned integer datatype. All fields are kept and the points keep their relative order.
+    /// A `BTreeMap` is returned so the grouping also works without `std` and iterates the labels in ascending order.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let cloud_points = vec![
+    ///     PointXYZL::new(0.0, 0.0, 0.0, 1),
+    ///     PointXYZL::new(1.0, 0.0, 0.0, 2),
+    ///     PointXYZL::new(2.0, 0.0, 0.0, 1),
+    /// ];
+    /// let msg = PointCloud2Msg::try_from_vec(cloud_points).unwrap();
+    ///
+    /// let objects = msg.group_by_label("label").unwrap();
+    /// assert_eq!(objects.len(), 2);
+    /// assert_eq!(objects[&1].dimensions.width, 2);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the field is missing, not an unsigned integer or the buffer is too short.
+    pub fn group_by_label(
+        &self,
+        name: &str,
+    ) -> Result<alloc::collections::BTreeMap<u32, PointCloud2Msg>, MsgConversionError> {
+        let labels: Vec<u32> = match FieldDatatype::try_from(self.field_by_name(name)?)? {
+            FieldDatatype::U8 => self.field_iter::<u8>(name)?.map(u32::from).collect(),
+            FieldDatatype::U16 => self.field_iter::<u16>(name)?.map(u32::from).collect(),
+            FieldDatatype::U32 => self.field_iter::<u32>(name)?.collect(),
+            _ => return Err(MsgConversionError::InvalidFieldFormat),
+        };
+
+        let mut groups: alloc::collections::BTreeMap<u32, Vec<usize>> =
+            alloc::collections::BTreeMap::new();
+        for (idx, label) in labels.into_iter().enumerate() {
+            groups.entry(label).or_default().push(idx);
+        }
+
+        groups
+            .into_iter()
+            .map(|(label, indices)| Ok((label, self.select_points(&indices)?)))
+            .collect()
+    }
+
+    /// Sort the points by the value of a `f32` field, e.g. by depth for rendering.
+    ///
+    /// The sort is stable and copies whole points, so all fields are kept without decoding them.
+    /// NaN values are ordered after all numbers when ascending. The result is an unorganized cloud.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let cloud_points = vec![
+    ///     PointXYZI::new(0.0, 0.0, 3.0, 0.1),
+    ///     PointXYZI::new(0.0, 0.0, 1.0, 0.2),
+    ///     PointXYZI::new(0.0, 0.0, 2.0, 0.3),
+    /// ];
+    /// let msg = PointCloud2Msg::try_from_vec(cloud_points).unwrap();
+    ///
+    /// let sorted = msg.sort_by_field_f32("z", true).unwrap();
+    /// let intensities: Vec<f32> = sorted.field_iter("intensity").unwrap().collect();
+    /// assert_eq!(intensities, vec![0.2, 0.3, 0.1]);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the field is missing, not `f32` or the buffer is too short.
+    pub fn sort_by_field_f32(
+        &self,
+        name: &str,
+        ascending: bool,
+    ) -> Result<PointCloud2Msg, MsgConversionError> {
+        let keys: Vec<f32> = self.field_iter(name)?.collect();
+
+        let mut indices: Vec<usize> = (0..keys.len()).collect();
+        if ascending {
+            indices.sort_by(|&a, &b| keys[a].total_cmp(&keys[b]));
+        } else {
+            indices.sort_by(|&a, &b| keys[b].total_cmp(&keys[a]));
+        }
+
+        self.select_points(&indices)
+    }
+
+    /// Number of bytes per point that are not covered by any field.
+    ///
+    /// Clouds from C++ sources often carry padding to align the points, which is sent over the network as well.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let msg = PointCloud2Msg::try_from_vec(vec![PointXYZ::new(1.0, 2.0, 3.0)]).unwrap();
+    /// assert_eq!(msg.padding_bytes_per_point().unwrap(), 4);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if a field has an unsupported datatype or the fields need more bytes than the point step.
+    pub fn padding_bytes_per_point(&self) -> Result<u32, MsgConversionError> {
+        let mut fields_size: u32 = 0;
+        for field in self.fields.iter() {
+            fields_size += FieldDatatype::try_from(field)?.size() as u32 * field.count;
+        }
+
+        self.point_step
+            .checked_sub(fields_size)
+            .ok_or(MsgConversionError::InvalidFieldFormat)
+    }
+
+    /// Create a copy of the cloud without any padding between or after the fields.
+    ///
+    /// The fields keep their order in the point and the new point step is the sum of the field sizes.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let cloud_points = vec![PointXYZ::new(1.0, 2.0, 3.0), PointXYZ::new(4.0, 5.0, 6.0)];
+    /// let msg = PointCloud2Msg::try_from_vec(cloud_points.clone()).unwrap();
+    ///
+    /// let tight = msg.repack_tight().unwrap();
+    /// assert_eq!(tight.point_step, 12);
+    /// assert_eq!(tight.padding_bytes_per_point().unwrap(), 0);
+    /// let points: Vec<PointXYZ> = tight.try_into_iter().unwrap().collect();
+    /// assert_eq!(points, cloud_points);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if a field has an unsupported datatype, fields overlap or the buffer is too short.
+    pub fn repack_tight(&self) -> Result<PointCloud2Msg, MsgConversionError> {
+        let mut order: Vec<usize> = (0..self.fields.len()).collect();
+        order.sort_by_key(|&i| self.fields[i].offset);
+
+        let mut fields = self.fields.clone();
+        let mut copies = Vec::with_capacity(fields.len()); // (source offset, size)
+        let mut point_step: u32 = 0;
+        let mut previous_end: u32 = 0;
+        for i in order {
+            let field = &self.fields[i];
+            let size = (FieldDatatype::try_from(field)?.size() as u32)
+                .checked_mul(field.count)
+                .ok_or(MsgConversionError::NumberConversion)?;
+            let end = field
+                .offset
+                .checked_add(size)
+                .ok_or(MsgConversionError::NumberConversion)?;
+            if field.offset < previous_end || end > self.point_step {
+                return Err(MsgConversionError::InvalidFieldFormat);
+            }
+            previous_end = end;
+
+            fields[i].offset = point_step;
+            copies.push((field.offset as usize, size as usize));
+            point_step += size;
+        }
+
+        let point_count = self.point_count();
+        self.check_data_len()?;
+
+        let mut data = Vec::with_capacity(point_count * point_step as usize);
+        for idx in 0..point_count {
+            let point_offset = self.point_offset(idx);
+            for &(offset, size) in copies.iter() {
+                let start = point_offset + offset;
+                data.extend_from_slice(&self.data[start..start + size]);
+            }
+        }
+
+        Ok(PointCloud2Msg {
+            header: self.header.clone(),
+            dimensions: self.dimensions.clone(),
+            fields,
+            endian: self.endian,
+            point_step,
+            row_step: self
+                .dimensions
+                .width
+                .checked_mul(point_step)
+                .ok_or(MsgConversionError::NumberConversion)?,
+            data,
+            dense: self.dense,
+            field_transforms: self.field_transforms.clone(),
+        })
+    }
+
+    /// Create a copy of the cloud with an additional `f32` field that holds one externally computed value per point,
+    /// e.g. normals or curvatures from a separate processing pass.
+    ///
+    /// The new field is appended after the end of each point and the rows of the copy are packed.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let cloud_points = vec![PointXYZ::new(1.0, 2.0, 3.0), PointXYZ::new(4.0, 5.0, 6.0)];
+    /// let msg = PointCloud2Msg::try_from_vec(cloud_points).unwrap();
+    ///
+    /// let with_curvature = msg.attach_field_f32("curvature", &[0.1, 0.2]).unwrap();
+    /// let values: Vec<f32> = with_curvature.field_iter("curvature").unwrap().collect();
+    /// assert_eq!(values, vec![0.1, 0.2]);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::DataLengthMismatch`] if the number of values differs from the number of points
+    /// or the buffer is too short and [`MsgConversionError::InvalidArgument`] if a field with the name already exists.
+    pub fn attach_field_f32(
+        &self,
+        name: &str,
+        values: &[f32],
+    ) -> Result<PointCloud2Msg, MsgConversionError> {
+        let point_count = self.point_count();
+        if values.len() != point_count {
+            return Err(MsgConversionError::DataLengthMismatch);
+        }
+        if self.fields.iter().any(|field| field.name == name) {
+            return Err(MsgConversionError::InvalidArgument);
+        }
+        self.check_data_len()?;
+
+        let old_step = self.point_step as usize;
+        let point_step = self
+            .point_step
+            .checked_add(FieldDatatype::F32.size() as u32)
+            .ok_or(MsgConversionError::NumberConversion)?;
+
+        let mut fields = self.fields.clone();
+        fields.push(PointFieldMsg {
+            name: String::from(name),
+            offset: self.point_step,
+            datatype: FieldDatatype::F32.into(),
+            count: 1,
+        });
+
+        let mut data = Vec::with_capacity(point_count * point_step as usize);
+        for (idx, value) in values.iter().enumerate() {
+            let point_offset = self.point_offset(idx);
+            data.extend_from_slice(&self.data[point_offset..point_offset + old_step]);
+            let bytes = match self.endian {
+                Endian::Big => value.to_be_bytes(),
+                Endian::Little => value.to_le_bytes(),
+            };
+            data.extend_from_slice(&bytes);
+        }
+
+        Ok(PointCloud2Msg {
+            header: self.header.clone(),
+            dimensions: self.dimensions.clone(),
+            fields,
+            endian: self.endian,
+            point_step,
+            row_step: self
+                .dimensions
+                .width
+                .checked_mul(point_step)
+                .ok_or(MsgConversionError::NumberConversion)?,
+            data,
+            dense: self.dense,
+            field_transforms: self.field_transforms.clone(),
+        })
+    }
+
+    /// Overwrite the values of an existing `f32` field in place, e.g. recomputed intensities of a calibration node.
+    ///
+    /// The other fields and the padding are not touched, so this is much cheaper than rebuilding the cloud.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let cloud_points = vec![PointXYZI::new(1.0, 2.0, 3.0, 0.5), PointXYZI::new(4.0, 5.0, 6.0, 0.5)];
+    /// let mut msg = PointCloud2Msg::try_from_vec(cloud_points).unwrap();
+    ///
+    /// msg.set_field_f32("intensity", &[0.1, 0.9]).unwrap();
+    /// let values: Vec<f32> = msg.field_iter("intensity").unwrap().collect();
+    /// assert_eq!(values, vec![0.1, 0.9]);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::DataLengthMismatch`] if the number of values differs from the number of points
+    /// or the buffer is too short, [`MsgConversionError::FieldsNotFound`] if the field is missing
+    /// and [`MsgConversionError::InvalidFieldFormat`] if it is not `f32`.
+    pub fn set_field_f32(&mut self, name: &str, values: &[f32]) -> Result<(), MsgConversionError> {
+        if values.len() != self.point_count() {
+            return Err(MsgConversionError::DataLengthMismatch);
+        }
+        let offset = self.field_offset_checked(name, FieldDatatype::F32)?;
+        self.check_data_len()?;
+
+        for (idx, value) in values.iter().enumerate() {
+            let start = self.point_offset(idx) + offset;
+            let bytes = match self.endian {
+                Endian::Big => value.to_be_bytes(),
+                Endian::Little => value.to_le_bytes(),
+            };
+            self.data[start..start + bytes.len()].copy_from_slice(&bytes);
+        }
+
+        Ok(())
+    }
+
+    /// Decode every point as `C`, modify it with `f` and write it back to the same position in the buffer.
+    ///
+    /// The fields are found by name, so this works for every layout that `C` can be read from, e.g. a different field order,
+    /// where [`try_borrow_slice`](PointCloud2Msg::try_borrow_slice) fails. Fields of the message that `C` does not model
+    /// and the padding are not touched.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let cloud_points = vec![PointXYZI::new(1.0, 2.0, 3.0, 0.5), PointXYZI::new(4.0, 5.0, 6.0, 0.5)];
+    /// let mut msg = PointCloud2Msg::try_from_vec(cloud_points).unwrap();
+    ///
+    /// msg.try_for_each_mut(|point: &mut PointXYZ| point.z += 10.0).unwrap();
+    /// let points: Vec<PointXYZI> = msg.try_into_vec().unwrap();
+    /// assert_eq!(points[1], PointXYZI::new(4.0, 5.0, 16.0, 0.5));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::InvalidFieldFormat`] if `C` yields other datatypes than the fields of the message.
+    /// This is checked with the default point before any point is written and again for every point, which hints
+    /// at a faulty manual [`PointConvertible`] implementation. The points before a failing one stay modified.
+    /// Also returns an error if a field of `C` is missing or the buffer is too short.
+    pub fn try_for_each_mut<const N: usize, C>(
+        &mut self,
+        mut f: impl FnMut(&mut C),
+    ) -> Result<(), MsgConversionError>
+    where
+        C: PointConvertible<N>,
+    {
+        let (offsets, datatypes) = self.element_offsets::<N, C>()?;
+        self.check_data_len()?;
+
+        let matches_message = |point: &RPCL2Point<N>| {
+            point
+                .fields
+                .iter()
+                .zip(datatypes.iter())
+                .all(|(pdata, datatype)| datatype.matches_code(u8::from(pdata.datatype)))
+        };
+        if !matches_message(&C::default().into()) {
+            return Err(MsgConversionError::InvalidFieldFormat);
+        }
+
+        for idx in 0..self.point_count() {
+            let point_offset = self.point_offset(idx);
+            let mut pdata = [PointData::default(); N];
+            for ((pdata, offset), datatype) in
+                pdata.iter_mut().zip(offsets.iter()).zip(datatypes.iter())
+            {
+                *pdata = PointData::from_buffer(
+                    &self.data,
+                    point_offset + offset,
+                    *datatype,
+                    self.endian,
+                );
+            }
+
+            let mut point = C::from(pdata.into());
+            f(&mut point);
+            let point: RPCL2Point<N> = point.into();
+            if !matches_message(&point) {
+                return Err(MsgConversionError::InvalidFieldFormat);
+            }
+
+            for (pdata, offset) in point.fields.iter().zip(offsets.iter()) {
+                let size = pdata.datatype.size();
+                let start = point_offset + offset;
+                let target = &mut self.data[start..start + size];
+                target.copy_from_slice(&pdata.bytes[..size]);
+                if pdata.endian != self.endian {
+                    target.reverse();
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Create a copy of the cloud where one numeric field is converted to another datatype, e.g. `f64` coordinates to `f32`.
+    ///
+    /// The fields after the converted one are moved by the size difference, while their bytes and all padding stay the same.
+    /// Integer targets saturate at their bounds and floats are rounded to the nearest representable value.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let msg = PointCloud2Msg::try_from_vec(vec![PointXYZI::new(1.0, 2.0, 3.0, 0.5)]).unwrap();
+    ///
+    /// let wide = msg.convert_field_type("intensity", FieldDatatype::F64).unwrap();
+    /// assert_eq!(wide.point_step, msg.point_step + 4);
+    /// let intensities: Vec<f64> = wide.field_iter("intensity").unwrap().collect();
+    /// assert_eq!(intensities, vec![0.5]);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::InvalidFieldFormat`] if another field shares bytes with the converted field.
+    /// Also returns an error if the field is missing, its datatype is unsupported or the buffer is too short.
+    pub fn convert_field_type(
+        &self,
+        name: &str,
+        to: FieldDatatype,
+    ) -> Result<PointCloud2Msg, MsgConversionError> {
+        let field = self.field_by_name(name)?;
+        let from = FieldDatatype::try_from(field)?;
+        let count = field.count as usize;
+        let start = field.offset as usize;
+        let old_end = start + from.size() * count;
+        let new_end = start + to.size() * count;
+        let point_step = self.point_step as usize;
+        if old_end > point_step {
+            return Err(MsgConversionError::DataLengthMismatch);
+        }
+
+        let mut fields = Vec::with_capacity(self.fields.len());
+        for other in self.fields.iter() {
+            let mut other = other.clone();
+            if other.name != field.name {
+                let other_start = other.offset as usize;
+                let other_end =
+                    other_start + FieldDatatype::try_from(&other)?.size() * other.count as usize;
+                if other_start < old_end && start < other_end {
+                    return Err(MsgConversionError::InvalidFieldFormat);
+                }
+                if other_start >= old_end {
+                    other.offset = u32::try_from(other_start - old_end + new_end)?;
+                }
+            } else {
+                other.datatype = to.into();
+            }
+            fields.push(other);
+        }
+
+        self.check_data_len()?;
+        let new_step = point_step - old_end + new_end;
+        let point_count = self.point_count();
+        let mut data = Vec::with_capacity(point_count * new_step);
+        for idx in 0..point_count {
+            let point = &self.data[self.point_offset(idx)..][..point_step];
+            data.extend_from_slice(&point[..start]);
+            for element in 0..count {
+                let value =
+                    PointData::from_buffer(point, start + element * from.size(), from, self.endian);
+                let converted = PointData::from_f64(value.to_f64(), to, self.endian);
+                data.extend_from_slice(&converted.bytes[..to.size()]);
+            }
+            data.extend_from_slice(&point[old_end..]);
+        }
+
+        Ok(PointCloud2Msg {
+            header: self.header.clone(),
+            dimensions: self.dimensions.clone(),
+            fields,
+            endian: self.endian,
+            point_step: u32::try_from(new_step)?,
+            row_step: u32::try_from(self.dimensions.width as usize * new_step)?,
+            data,
+            dense: self.dense,
+            field_transforms: self.field_transforms.clone(),
+        })
+    }
+
+    /// Bucket the `x`, `y` and `z` coordinates into a dense [`OccupancyGrid3D`] without creating a new cloud.
+    ///
+    /// The grid starts at the minimum corner of `bounds` and spans the box with cells of size `leaf`.
+    /// Points outside the box or with non-finite coordinates are ignored.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let cloud_points = vec![
+    ///     PointXYZ::new(0.1, 0.1, 0.1),
+    ///     PointXYZ::new(0.2, 0.3, 0.1),
+    ///     PointXYZ::new(1.5, 0.5, 0.5),
+    /// ];
+    /// let msg = PointCloud2Msg::try_from_vec(cloud_points).unwrap();
+    ///
+    /// let grid = msg.occupancy_grid([1.0; 3], ([0.0; 3], [2.0, 1.0, 1.0])).unwrap();
+    /// assert_eq!(grid.dimensions, [2, 1, 1]);
+    /// assert_eq!(grid.count([0, 0, 0]), 2);
+    /// assert!(grid.is_occupied([1, 0, 0]));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the `x`, `y` or `z` fields are missing or not `f32`, the buffer is too short,
+    /// the leaf size is not positive or the bounds are empty.
+    pub fn occupancy_grid(
+        &self,
+        leaf: [f32; 3],
+        bounds: ([f32; 3], [f32; 3]),
+    ) -> Result<OccupancyGrid3D, MsgConversionError> {
+        let (min, max) = bounds;
+        let mut dimensions = [0; 3];
+        for axis in 0..3 {
+            let extent = max[axis] - min[axis];
+            if !(leaf[axis] > 0.0 && extent > 0.0 && extent.is_finite()) {
+                return Err(MsgConversionError::InvalidArgument);
+            }
+
+            let cells = extent / leaf[axis];
+            if cells > u32::MAX as f32 {
+                return Err(MsgConversionError::NumberConversion);
+            }
+            // ceil(x) = -floor(-x)
+            dimensions[axis] = -floor_to_i64(-cells) as usize;
+        }
+
+        let cell_count = dimensions[0]
+            .checked_mul(dimensions[1])
+            .and_then(|c| c.checked_mul(dimensions[2]))
+            .ok_or(MsgConversionError::NumberConversion)?;
+
+        let mut grid = OccupancyGrid3D {
+            dimensions,
+            origin: min,
+            leaf,
+            counts: vec![0; cell_count],
+        };
+
+        let offsets = self.xyz_f32_offsets()?;
+        for idx in 0..self.point_count() {
+            let point = self.xyz_at(idx, &offsets);
+            if point.iter().any(|v| !v.is_finite())
+                || (0..3).any(|axis| point[axis] < min[axis] || point[axis] >= max[axis])
+            {
+                continue;
+            }
+
+            if let Some(cell) = grid.cell_of(point).and_then(|cell| grid.index(cell)) {
+                grid.counts[cell] = grid.counts[cell].saturating_add(1);
+            }
+        }
+
+        Ok(grid)
+    }
+
+    /// Compute the Morton (Z-order) code of every point, e.g. to sort the points for spatial locality.
+    ///
+    /// Each coordinate is quantized to `bits` bits within `bounds` and the bits of `x`, `y` and `z` are interleaved,
+    /// with `x` in the lowest bit. Points outside the bounds are clamped to the closest cell and NaN coordinates map to the minimum.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let cloud_points = vec![PointXYZ::new(0.0, 0.0, 0.0), PointXYZ::new(1.0, 0.0, 0.0), PointXYZ::new(0.0, 1.0, 1.0)];
+    /// let msg = PointCloud2Msg::try_from_vec(cloud_points).unwrap();
+    ///
+    /// let codes = msg.morton_codes(([0.0; 3], [2.0; 3]), 1).unwrap();
+    /// assert_eq!(codes, vec![0b000, 0b001, 0b110]);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::InvalidArgument`] if `bits` is not in `1..=21` or the bounds are empty.
+    /// Also returns an error if the `x`, `y` or `z` fields are missing or not `f32` or the buffer is too short.
+    pub fn morton_codes(
+        &self,
+        bounds: ([f32; 3], [f32; 3]),
+        bits: u32,
+    ) -> Result<Vec<u64>, MsgConversionError> {
+        if !(1..=21).contains(&bits) {
+            return Err(MsgConversionError::InvalidArgument);
+        }
+
+        let (min, max) = bounds;
+        let mut extent = [0.0; 3];
+        for axis in 0..3 {
+            extent[axis] = max[axis] - min[axis];
+            if !(extent[axis] > 0.0 && extent[axis].is_finite()) {
+                return Err(MsgConversionError::InvalidArgument);
+            }
+        }
+
+        let cells = 1u64 << bits;
+        let offsets = self.xyz_f32_offsets()?;
+        Ok((0..self.point_count())
+            .map(|idx| {
+                let point = self.xyz_at(idx, &offsets);
+                let mut code = 0;
+                for axis in 0..3 {
+                    let scaled = (point[axis] - min[axis]) / extent[axis] * cells as f32;
+                    // Casting saturates and maps NaN to 0.
+                    let cell = (scaled as u64).min(cells - 1);
+                    for bit in 0..bits {
+                        code |= ((cell >> bit) & 1) << (3 * bit + axis as u32);
+                    }
+                }
+                code
+            })
+            .collect())
+    }
+
+    /// Create a copy of the cloud with the points sorted by their [`morton_codes`](PointCloud2Msg::morton_codes),
+    /// so points that are close in space are close in the buffer. The result is an unorganized cloud.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let cloud_points = vec![PointXYZ::new(1.5, 1.5, 1.5), PointXYZ::new(0.5, 0.5, 0.5)];
+    /// let msg = PointCloud2Msg::try_from_vec(cloud_points).unwrap();
+    ///
+    /// let sorted: Vec<PointXYZ> = msg.sort_by_morton(([0.0; 3], [2.0; 3]), 10).unwrap().try_into_vec().unwrap();
+    /// assert_eq!(sorted[0], PointXYZ::new(0.5, 0.5, 0.5));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns the same errors as [`morton_codes`](PointCloud2Msg::morton_codes).
+    pub fn sort_by_morton(
+        &self,
+        bounds: ([f32; 3], [f32; 3]),
+        bits: u32,
+    ) -> Result<PointCloud2Msg, MsgConversionError> {
+        let codes = self.morton_codes(bounds, bits)?;
+
+        let mut indices: Vec<usize> = (0..codes.len()).collect();
+        indices.sort_by_key(|&idx| codes[idx]);
+
+        self.select_points(&indices)
+    }
+
+    /// Iterate over the coordinates of all points without naming a point type.
+    ///
+    /// The `x`, `y` and `z` fields are found by name, so their order in the message does not matter.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let msg = PointCloud2Msg::try_from_vec(vec![PointXYZI::new(1.0, 2.0, 3.0, 0.5)]).unwrap();
+    ///
+    /// for (x, y, z) in msg.iter_coords().unwrap() {
+    ///     assert_eq!((x, y, z), (1.0, 2.0, 3.0));
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the message has no f32 x, y and z fields or the buffer is too short.
+    pub fn iter_coords(
+        &self,
+    ) -> Result<impl Iterator<Item = (f32, f32, f32)> + '_, MsgConversionError> {
+        let offsets = self.xyz_f32_offsets()?;
+        Ok((0..self.point_count()).map(move |idx| {
+            let [x, y, z] = self.xyz_at(idx, &offsets);
+            (x, y, z)
+        }))
+    }
+
+    /// Collect the coordinates of all points as `[f32; 3]`, the layout that KD-tree crates like `kiddo` consume.
+    ///
+    /// The `x`, `y` and `z` fields are read in a single pass, independent of the other fields of the point.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let msg = PointCloud2Msg::try_from_vec(vec![PointXYZI::new(1.0, 2.0, 3.0, 0.5)]).unwrap();
+    /// assert_eq!(msg.to_xyz_vec().unwrap(), vec![[1.0, 2.0, 3.0]]);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the message has no f32 x, y and z fields or the buffer is too short.
+    pub fn to_xyz_vec(&self) -> Result<Vec<[f32; 3]>, MsgConversionError> {
+        let offsets = self.xyz_f32_offsets()?;
+        Ok((0..self.point_count())
+            .map(|idx| self.xyz_at(idx, &offsets))
+            .collect())
+    }
+
+    /// Borrow the coordinates of a pure XYZ cloud as `[f32; 3]` without copying.
+    ///
+    /// This needs a message with exactly the `f32` fields `x`, `y` and `z` at the offsets 0, 4 and 8, a point step of 12,
+    /// no padding between rows and the endian of the system. Use [`to_xyz_vec`](PointCloud2Msg::to_xyz_vec) for all other clouds.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let msg = PointCloud2Msg::try_from_xyz_iter([(1.0, 2.0, 3.0), (4.0, 5.0, 6.0)]).unwrap();
+    /// assert_eq!(msg.as_xyz_slice().unwrap(), &[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    ///
+    /// let padded = PointCloud2Msg::try_from_vec(vec![PointXYZ::new(1.0, 2.0, 3.0)]).unwrap();
+    /// assert!(padded.as_xyz_slice().is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::InvalidFieldFormat`] if the layout or endian differ, [`MsgConversionError::InvalidArgument`]
+    /// if the buffer is not aligned to 4 bytes, which can happen for buffers that were not allocated by this crate,
+    /// and an error if the buffer is too short.
+    pub fn as_xyz_slice(&self) -> Result<&[[f32; 3]], MsgConversionError> {
+        let is_xyz =
+            self.fields.len() == 3
+                && self.fields.iter().zip(["x", "y", "z"]).enumerate().all(
+                    |(idx, (field, name))| {
+                        field.name == name
+                            && field.offset == 4 * idx as u32
+                            && field.datatype == u8::from(FieldDatatype::F32)
+                            && field.count == 1
+                    },
+                );
+        if !is_xyz
+            || self.point_step != 12
+            || self.has_row_padding()
+            || self.endian != system_endian()
+        {
+            return Err(MsgConversionError::InvalidFieldFormat);
+        }
+        self.check_data_len()?;
+
+        let point_count = self.point_count();
+        if point_count == 0 {
+            return Ok(&[]);
+        }
+        if self
+            .data
+            .as_ptr()
+            .align_offset(core::mem::align_of::<[f32; 3]>())
+            != 0
+        {
+            return Err(MsgConversionError::InvalidArgument);
+        }
+
+        // The buffer holds `point_count` points of 12 bytes, `[f32; 3]` has a size of 12 and the pointer is aligned.
+        // Every bit pattern is a valid f32.
+        Ok(unsafe {
+            core::slice::from_raw_parts(self.data.as_ptr().cast::<[f32; 3]>(), point_count)
+        })
+    }
+
+    /// Check if any point lies inside the axis-aligned box between `min` and `max` (both inclusive).
+    ///
+    /// Only the xyz fields are read and the search stops at the first hit without allocating,
+    /// which makes it a cheap presence check for safety zones or proximity alarms.
+    /// Points with non-finite coordinates are never inside.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let msg = PointCloud2Msg::try_from_vec(vec![
+    ///     PointXYZ::new(5.0, 5.0, 5.0),
+    ///     PointXYZ::new(0.5, 0.2, 0.0),
+    /// ]).unwrap();
+    ///
+    /// assert!(msg.any_in_aabb([0.0, 0.0, 0.0], [1.0, 1.0, 1.0]).unwrap());
+    /// assert!(!msg.any_in_aabb([-1.0, -1.0, -1.0], [0.0, 0.0, -0.5]).unwrap());
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the message has no f32 x, y and z fields or the buffer is too short.
+    pub fn any_in_aabb(&self, min: [f32; 3], max: [f32; 3]) -> Result<bool, MsgConversionError> {
+        let offsets = self.xyz_f32_offsets()?;
+        Ok((0..self.point_count()).any(|idx| {
+            let point = self.xyz_at(idx, &offsets);
+            (0..3).all(|axis| point[axis] >= min[axis] && point[axis] <= max[axis])
+        }))
+    }
+
+    /// Best-effort guess of the byte order of the data by reading the `x`, `y` and `z` values of a few points in both orders.
+    ///
+    /// The order that yields more plausible coordinates (finite, zero or with a magnitude between `1e-20` and `1e6`) wins.
+    /// This helps to detect producers that set the `is_bigendian` flag wrong, but it is a heuristic and not a guarantee.
+    /// Returns `None` if the coordinates are not readable as `f32`, the cloud is empty or both orders are equally plausible.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    /// use ros_pointcloud2::Endian;
+    ///
+    /// let cloud_points = vec![PointXYZ::new(1.5, -2.25, 3.0), PointXYZ::new(10.0, 20.0, 30.0)];
+    /// let mut msg = PointCloud2Msg::try_from_vec_endian(cloud_points, Endian::Big).unwrap();
+    /// msg.endian = Endian::Little; // mislabeled by the producer
+    ///
+    /// assert_eq!(msg.guess_endian_from_xyz(), Some(Endian::Big));
+    /// ```
+    #[must_use]
+    pub fn guess_endian_from_xyz(&self) -> Option<Endian> {
+        const SAMPLES: usize = 32;
+
+        let offsets = self.xyz_f32_offsets().ok()?;
+        let point_count = self.point_count();
+        let stride = (point_count / SAMPLES).max(1);
+
+        let plausible = |value: f32| {
+            let magnitude = if value < 0.0 { -value } else { value };
+            value == 0.0 || (1e-20..1e6).contains(&magnitude)
+        };
+        let mut scores = [0usize; 2];
+        for idx in (0..point_count).step_by(stride).take(SAMPLES) {
+            let point_offset = self.point_offset(idx);
+            for (score, endian) in scores.iter_mut().zip([Endian::Little, Endian::Big]) {
+                *score += offsets
+                    .iter()
+                    .map(|offset| {
+                        PointData::from_buffer(
+                            &self.data,
+                            point_offset + offset,
+                            FieldDatatype::F32,
+                            endian,
+                        )
+                        .get::<f32>()
+                    })
+                    .filter(|value| plausible(*value))
+                    .count();
+            }
+        }
+
+        match scores[0].cmp(&scores[1]) {
+            core::cmp::Ordering::Greater => Some(Endian::Little),
+            core::cmp::Ordering::Less => Some(Endian::Big),
+            core::cmp::Ordering::Equal => None,
+        }
+    }
+
+    /// The smallest point step that fits the given fields, which is the end of the field with the largest offset.
+    ///
+    /// Use this when building messages by hand from a known field list.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let fields = vec![
+    ///     PointFieldMsg { name: "x".into(), offset: 0, datatype: 7, count: 1 },
+    ///     PointFieldMsg { name: "y".into(), offset: 4, datatype: 7, count: 1 },
+    ///     PointFieldMsg { name: "intensity".into(), offset: 16, datatype: 8, count: 1 },
+    /// ];
+    /// assert_eq!(PointCloud2Msg::point_step_for(&fields).unwrap(), 24);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if there are no fields, a datatype is unsupported or fields overlap.
+    pub fn point_step_for(fields: &[PointFieldMsg]) -> Result<u32, MsgConversionError> {
+        Self::fields_end(fields, false)
+    }
+
+    /// Byte ranges of the fields as `(start, end)`, sorted by offset.
+    fn field_ranges(fields: &[PointFieldMsg]) -> Result<Vec<(u32, u32)>, MsgConversionError> {
+        let mut ranges = Vec::with_capacity(fields.len());
+        for field in fields.iter() {
+            let end = (FieldDatatype::try_from(field)?.size() as u32)
+                .checked_mul(field.count)
+                .and_then(|size| size.checked_add(field.offset))
+                .ok_or(MsgConversionError::NumberConversion)?;
+            ranges.push((field.offset, end));
+        }
+
+        ranges.sort_unstable();
+        Ok(ranges)
+    }
+
+    /// End of the field with the largest offset, optionally checking that no fields share bytes.
+    fn fields_end(
+        fields: &[PointFieldMsg],
+        allow_overlap: bool,
+    ) -> Result<u32, MsgConversionError> {
+        if fields.is_empty() {
+            return Err(MsgConversionError::FieldsNotFound(Vec::new()));
+        }
+
+        let ranges = Self::field_ranges(fields)?;
+        if !allow_overlap && ranges.windows(2).any(|pair| pair[1].0 < pair[0].1) {
+            return Err(MsgConversionError::InvalidFieldFormat);
+        }
+
+        Ok(ranges.iter().map(|&(_, end)| end).max().unwrap_or(0))
+    }
+
+    /// Create an organized [`PointCloud2Msg`] from raw bytes whose rows may be padded to a stride, e.g. GPU aligned depth buffers.
+    ///
+    /// Each of the `height` rows holds `width` points of `point_step` bytes followed by padding up to `row_step` bytes.
+    /// The conversions and iterators skip the padding between the rows.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let fields = PointCloud2Msg::try_from_vec(vec![PointXYZ::default()]).unwrap().fields;
+    /// // 2x2 points of 16 bytes, rows padded to 64 bytes
+    /// let data = vec![0; 2 * 64];
+    /// let msg = PointCloud2Msg::try_from_organized_with_row_step(
+    ///     data,
+    ///     2,
+    ///     2,
+    ///     16,
+    ///     64,
+    ///     fields,
+    ///     ros_pointcloud2::Endian::default(),
+    /// )
+    /// .unwrap();
+    /// let points: Vec<PointXYZ> = msg.try_into_iter().unwrap().collect();
+    /// assert_eq!(points.len(), 4);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the fields are empty or do not fit into the point step, the row step is smaller than `width * point_step`
+    /// or the data length is not `height * row_step`.
+    pub fn try_from_organized_with_row_step(
+        data: Vec<u8>,
+        width: u32,
+        height: u32,
+        point_step: u32,
+        row_step: u32,
+        fields: Vec<PointFieldMsg>,
+        endian: Endian,
+    ) -> Result<Self, MsgConversionError> {
+        if fields.is_empty() {
+            return Err(MsgConversionError::FieldsNotFound(Vec::new()));
+        }
+
+        for field in fields.iter() {
+            let size = FieldDatatype::try_from(field)?.size() as u32;
+            let end = size
+                .checked_mul(field.count)
+                .and_then(|size| size.checked_add(field.offset))
+                .ok_or(MsgConversionError::NumberConversion)?;
+            if end > point_step {
+                return Err(MsgConversionError::InvalidFieldFormat);
+            }
+        }
+
+        let min_row_step = width
+            .checked_mul(point_step)
+            .ok_or(MsgConversionError::NumberConversion)?;
+        if row_step < min_row_step {
+            return Err(MsgConversionError::InvalidArgument);
+        }
+
+        if data.len() != height as usize * row_step as usize {
+            return Err(MsgConversionError::DataLengthMismatch);
+        }
+
+        Ok(Self {
+            header: HeaderMsg::default(),
+            dimensions: CloudDimensions { width, height },
+            fields,
+            endian,
+            point_step,
+            row_step,
+            data,
+            dense: Denseness::default(),
+            field_transforms: Vec::new(),
+        })
+    }
+
+    /// Borrow the points `start..start + len` as a [`PointCloud2Ref`] without copying the data.
+    ///
+    /// The view is unorganized with a height of 1 and keeps the fields and point step of the message.
+    /// This is useful for windowed processing of a large buffer, e.g. per scan line.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let cloud_points = vec![
+    ///     PointXYZ::new(1.0, 2.0, 3.0),
+    ///     PointXYZ::new(4.0, 5.0, 6.0),
+    ///     PointXYZ::new(7.0, 8.0, 9.0),
+    /// ];
+    /// let msg = PointCloud2Msg::try_from_vec(cloud_points).unwrap();
+    ///
+    /// let view = msg.sub_view(1, 2).unwrap();
+    /// assert_eq!(view.len(), 2);
+    /// let points: Vec<PointXYZ> = view.to_msg().try_into_vec().unwrap();
+    /// assert_eq!(points[0], PointXYZ::new(4.0, 5.0, 6.0));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the range exceeds the number of points or the buffer is too short.
+    /// Organized clouds with padding between the rows can not be viewed as a contiguous range and return [`MsgConversionError::InvalidArgument`].
+    pub fn sub_view(
+        &self,
+        start: usize,
+        len: usize,
+    ) -> Result<PointCloud2Ref<'_>, MsgConversionError> {
+        if self.has_row_padding() {
+            return Err(MsgConversionError::InvalidArgument);
+        }
+
+        sub_view_of(
+            &self.header,
+            &self.fields,
+            self.endian,
+            self.point_step,
+            &self.data,
+            self.dense,
+            &self.field_transforms,
+            self.point_count(),
+            start,
+            len,
+        )
+    }
+
+    /// Create a [`PointCloud2Msg`] from any iterable type that implements [`PointConvertible`].
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let cloud_points: Vec<PointXYZ> = vec![
+    ///     PointXYZ::new(1.0, 2.0, 3.0),
+    ///     PointXYZ::new(4.0, 5.0, 6.0),
+    /// ];
+    ///
+    // let msg_out = PointCloud2Msg::try_from_iter(cloud_points).unwrap();
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::InvalidFieldFormat`] if a point yields other field datatypes than the default point,
+    /// which hints at a faulty manual [`PointConvertible`] implementation.
+    pub fn try_from_iter<const N: usize, C>(
+        iterable: impl IntoIterator<Item = C>,
+    ) -> Result<Self, MsgConversionError>
+    where
+        C: PointConvertible<N>,
+    {
+        let mut writer = PointCloud2Writer::<N, C>::new()?;
+        for point in iterable {
+            writer.push(point)?;
+        }
+
+        writer.finish()
+    }
+
+    /// Create an organized [`PointCloud2Msg`] from points in row-major order, e.g. a depth grid with holes.
+    ///
+    /// Points with NaN values are kept in place to preserve the grid. If any `f32` or `f64` value of a point is not finite,
+    /// the cloud is marked as [`Denseness::Sparse`].
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let grid = vec![
+    ///     PointXYZ::new(0.0, 0.0, 1.0),
+    ///     PointXYZ::new(f32::NAN, f32::NAN, f32::NAN),
+    ///     PointXYZ::new(0.0, 1.0, 1.0),
+    ///     PointXYZ::new(1.0, 1.0, 1.0),
+    /// ];
+    /// let msg = PointCloud2Msg::try_from_iter_organized(grid, 2, 2).unwrap();
+    ///
+    /// assert_eq!((msg.dimensions.width, msg.dimensions.height), (2, 2));
+    /// assert_eq!(msg.dense, ros_pointcloud2::Denseness::Sparse);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::DataLengthMismatch`] if the number of points is not `width * height`.
+    /// Also returns the errors of [`try_from_iter`](PointCloud2Msg::try_from_iter).
+    pub fn try_from_iter_organized<const N: usize, C>(
+        iterable: impl IntoIterator<Item = C>,
+        width: u32,
+        height: u32,
+    ) -> Result<Self, MsgConversionError>
+    where
+        C: PointConvertible<N>,
+    {
+        let mut writer = PointCloud2Writer::<N, C>::new()?;
+        let mut dense = Denseness::Dense;
+        for point in iterable {
+            let point: RPCL2Point<N> = point.into();
+            let finite = point.fields.iter().all(|pdata| match pdata.datatype {
+                FieldDatatype::F32 => pdata.get::<f32>().is_finite(),
+                FieldDatatype::F64 => pdata.get::<f64>().is_finite(),
+                _ => true,
+            });
+            if !finite {
+                dense = Denseness::Sparse;
+            }
+            writer.push_point(point)?;
+        }
+
+        let expected = (width as usize)
+            .checked_mul(height as usize)
+            .ok_or(MsgConversionError::NumberConversion)?;
+        if writer.len() != expected {
+            return Err(MsgConversionError::DataLengthMismatch);
+        }
+
+        let mut cloud = writer.finish()?;
+        cloud.row_step = width
+            .checked_mul(cloud.point_step)
+            .ok_or(MsgConversionError::NumberConversion)?;
+        cloud.dimensions = CloudDimensions { width, height };
+        cloud.dense = dense;
+        Ok(cloud)
+    }
+
+    /// Create a [`PointCloud2Msg`] from a JSON array of points, e.g. golden clouds in test fixtures
+    /// or clouds received from a JSON endpoint. Requires the `serde` feature.
+    ///
+    /// The points are parsed with `serde_json` and written like in [`try_from_iter`](PointCloud2Msg::try_from_iter).
+    ///
+    /// # Example
+    /// ```ignore
+    /// #[derive(Clone, Debug, PartialEq, Copy, Default, PointConvertible, serde::Deserialize)]
+    /// #[repr(C, align(4))]
+    /// struct MyPointXYZI {
+    ///     x: f32,
+    ///     y: f32,
+    ///     z: f32,
+    ///     intensity: f32,
+    /// }
+    ///
+    /// let json = r#"[{ "x": 1.0, "y": 2.0, "z": 3.0, "intensity": 0.5 }]"#;
+    /// let msg = PointCloud2Msg::try_from_json_points::<4, MyPointXYZI>(json).unwrap();
+    /// assert_eq!(msg.len(), 1);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::InvalidJson`] if the input is not a JSON array of `C`.
+    /// Otherwise, the same errors as [`try_from_iter`](PointCloud2Msg::try_from_iter) are returned.
+    #[cfg(feature = "serde")]
+    pub fn try_from_json_points<const N: usize, C>(json: &str) -> Result<Self, MsgConversionError>
+    where
+        C: PointConvertible<N> + serde::de::DeserializeOwned,
+    {
+        let points: Vec<C> = serde_json::from_str(json)
+            .map_err(|err| MsgConversionError::InvalidJson(err.to_string()))?;
+        Self::try_from_iter(points)
+    }
+
+    /// Create a message without points that only describes the fields of `C`, e.g. for a latched message
+    /// that announces the format of a topic before data flows or for tests that assert the schema.
+    ///
+    /// The fields and point step are the same as for [`try_from_iter`](PointCloud2Msg::try_from_iter).
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    /// use ros_pointcloud2::ros::HeaderMsg;
+    ///
+    /// let header = HeaderMsg { frame_id: "lidar".into(), ..Default::default() };
+    /// let msg = PointCloud2Msg::schema_only::<4, PointXYZI>(header).unwrap();
+    ///
+    /// assert_eq!(msg.fields.len(), 4);
+    /// assert_eq!(msg.dimensions.width, 0);
+    /// assert!(msg.data.is_empty());
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the layout of `C` can not be described by the message.
+    pub fn schema_only<const N: usize, C>(header: HeaderMsg) -> Result<Self, MsgConversionError>
+    where
+        C: PointConvertible<N>,
+    {
+        let mut cloud = Self::try_from_iter(core::iter::empty::<C>())?;
+        cloud.header = header;
+        Ok(cloud)
+    }
+
+    /// Create an unorganized [`PointCloud2Msg`] from a flat buffer where every `fields` consecutive values form one point.
+    ///
+    /// The fields are named `f0`, `f1`, ... Use [`try_from_flat_f32_named`](PointCloud2Msg::try_from_flat_f32_named)
+    /// to choose the names, e.g. `x`, `y` and `z` for plain coordinates.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let msg = PointCloud2Msg::try_from_flat_f32(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], 2).unwrap();
+    /// assert_eq!(msg.dimensions.width, 3);
+    /// assert_eq!(msg.fields[1].name, "f1");
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::InvalidArgument`] if `fields` is 0
+    /// and [`MsgConversionError::DataLengthMismatch`] if the buffer length is not a multiple of `fields`.
+    pub fn try_from_flat_f32(data: Vec<f32>, fields: usize) -> Result<Self, MsgConversionError> {
+        let names: Vec<String> = (0..fields).map(|i| alloc::format!("f{i}")).collect();
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+        Self::try_from_flat_f32_named(data, &names)
+    }
+
+    /// Create an unorganized [`PointCloud2Msg`] from a flat buffer of f32 values with one field per name.
+    ///
+    /// The values are copied into the message with a single bulk copy, so no point type or per-value conversion is involved.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let flat = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+    /// let msg = PointCloud2Msg::try_from_flat_f32_named(flat, &["x", "y", "z"]).unwrap();
+    ///
+    /// let points: Vec<PointXYZ> = msg.try_into_iter().unwrap().collect();
+    /// assert_eq!(points[1], PointXYZ::new(4.0, 5.0, 6.0));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::InvalidArgument`] if `names` is empty
+    /// and [`MsgConversionError::DataLengthMismatch`] if the buffer length is not a multiple of the number of names.
+    pub fn try_from_flat_f32_named(
+        data: Vec<f32>,
+        names: &[&str],
+    ) -> Result<Self, MsgConversionError> {
+        if names.is_empty() {
+            return Err(MsgConversionError::InvalidArgument);
+        }
+        if data.len() % names.len() != 0 {
+            return Err(MsgConversionError::DataLengthMismatch);
+        }
+
+        let field_size = FieldDatatype::F32.size();
+        let fields = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                Ok(PointFieldMsg {
+                    name: String::from(*name),
+                    offset: u32::try_from(i * field_size)?,
+                    datatype: FieldDatatype::F32.into(),
+                    count: 1,
+                })
+            })
+            .collect::<Result<Vec<_>, MsgConversionError>>()?;
+        let point_step = u32::try_from(names.len() * field_size)?;
+        let width = u32::try_from(data.len() / names.len())?;
+
+        // The message is in system endian, so the memory of the values is already the wire format.
+        let bytes_total = data.len() * field_size;
+        let mut bytes = vec![u8::default(); bytes_total];
+        unsafe {
+            core::ptr::copy_nonoverlapping(
+                data.as_ptr().cast::<u8>(),
+                bytes.as_mut_ptr(),
+                bytes_total,
+            );
+        }
+
+        PointCloud2MsgBuilder::new()
+            .fields(fields)
+            .endian(system_endian() == Endian::Big)
+            .point_step(point_step)
+            .width(width)
+            .row_step(width * point_step)
+            .data(bytes)
+            .build()
+    }
+
+    /// Create a [`PointXYZ`](points::PointXYZ) cloud from coordinates stored as separate arrays (structure of arrays).
+    ///
+    /// The values are interleaved directly into the message buffer, so no intermediate Vec of points is needed.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let x = [1.0, 4.0];
+    /// let y = [2.0, 5.0];
+    /// let z = [3.0, 6.0];
+    /// let msg = PointCloud2Msg::try_from_soa_xyz(&x, &y, &z).unwrap();
+    ///
+    /// let points: Vec<PointXYZ> = msg.try_into_vec().unwrap();
+    /// assert_eq!(points, vec![PointXYZ::new(1.0, 2.0, 3.0), PointXYZ::new(4.0, 5.0, 6.0)]);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::DataLengthMismatch`] if the arrays have different lengths.
+    pub fn try_from_soa_xyz(x: &[f32], y: &[f32], z: &[f32]) -> Result<Self, MsgConversionError> {
+        if x.len() != y.len() || x.len() != z.len() {
+            return Err(MsgConversionError::DataLengthMismatch);
+        }
+
+        let mut cloud = Self::try_from_vec(Vec::<points::PointXYZ>::new())?;
+        let offsets = cloud.xyz_f32_offsets()?;
+        let point_step = cloud.point_step as usize;
+
+        cloud.data.resize(x.len() * point_step, 0);
+        for (idx, point) in cloud.data.chunks_exact_mut(point_step).enumerate() {
+            for (offset, value) in offsets.iter().zip([x[idx], y[idx], z[idx]]) {
+                point[*offset..*offset + 4].copy_from_slice(&value.to_ne_bytes());
+            }
+        }
+
+        cloud.dimensions = CloudDimensionsBuilder::new_with_width(x.len()).build()?;
+        cloud.row_step = u32::try_from(cloud.data.len())?;
+        Ok(cloud)
+    }
+
+    /// Create a [`PointXYZ`](points::PointXYZ) cloud from `(x, y, z)` tuples, e.g. a synthetic grid or sphere in a demo.
+    ///
+    /// This is the counterpart of [`iter_coords`](PointCloud2Msg::iter_coords) and needs no point type.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let grid = (0..4).flat_map(|x| (0..4).map(move |y| (x as f32, y as f32, 0.0)));
+    /// let msg = PointCloud2Msg::try_from_xyz_iter(grid).unwrap();
+    ///
+    /// assert_eq!(msg.len(), 16);
+    /// assert_eq!(msg.iter_coords().unwrap().last(), Some((3.0, 3.0, 0.0)));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::NumberConversion`] if there are more points than the message dimensions can hold.
+    pub fn try_from_xyz_iter(
+        iterable: impl IntoIterator<Item = (f32, f32, f32)>,
+    ) -> Result<Self, MsgConversionError> {
+        Self::try_from_iter(
+            iterable
+                .into_iter()
+                .map(|(x, y, z)| points::PointXYZ::new(x, y, z)),
+        )
+    }
+
+    /// Create a PointCloud2Msg from a parallel iterator. Requires the `rayon` and `derive` feature to be enabled.
+    ///
+    /// The points are collected and written with the direct copy of [`try_from_vec`](PointCloud2Msg::try_from_vec), so they need to implement [`CopyPointConvertible`].
+    #[cfg(feature = "rayon")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+    pub fn try_from_par_iter<const N: usize, C>(
+        iterable: impl rayon::iter::ParallelIterator<Item = C>,
+    ) -> Result<Self, MsgConversionError>
+    where
+        C: CopyPointConvertible<N> + Send + Sync,
+    {
+        Self::try_from_vec(iterable.collect::<Vec<_>>())
+    }
+
+    /// Create a [`PointCloud2Msg`] from a Vec of points.
+    /// Since the point type is known at compile time, the conversion is done by direct copy.
+    /// This requires the point to be plain data, see [`CopyPointConvertible`]. Use [`try_from_iter`](PointCloud2Msg::try_from_iter) for points that are not `Copy`.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let cloud_points: Vec<PointXYZ> = vec![
+    ///     PointXYZ::new(1.0, 2.0, 3.0),
+    ///     PointXYZ::new(4.0, 5.0, 6.0),
+    /// ];
+    ///
+    /// let msg_out = PointCloud2Msg::try_from_vec(cloud_points).unwrap();
+    /// ```
+    ///
+    /// The padding bytes of `C` are set to zero, so no uninitialized memory ends up in a published message.
+    ///
+    /// # Errors
+    /// Returns an error if the byte buffer does not match the expected layout or the message contains other discrepancies.
+    pub fn try_from_vec<const N: usize, C>(vec: Vec<C>) -> Result<Self, MsgConversionError>
+    where
+        C: CopyPointConvertible<N>,
+    {
+        Self::try_from_slice_with_buffer(&vec, Vec::new())
+    }
+
+    /// Create a [`PointCloud2Msg`] from a slice of points and write the data into the provided buffer.
+    ///
+    /// The buffer is cleared and its capacity is reused, so a latency-critical loop can keep the
+    /// large data buffer out of the allocator by passing the `data` of the previous message back in.
+    /// Otherwise, this behaves like [`try_from_vec`](PointCloud2Msg::try_from_vec).
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let mut buffer = Vec::with_capacity(1024);
+    /// for frame in 0..3 {
+    ///     let cloud_points = vec![PointXYZ::new(frame as f32, 0.0, 0.0); 8];
+    ///     let msg = PointCloud2Msg::try_from_slice_with_buffer(&cloud_points, buffer).unwrap();
+    ///     // publish or process the message ...
+    ///     buffer = msg.data;
+    /// }
+    /// assert!(buffer.capacity() >= 1024);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the byte buffer does not match the expected layout or the message contains other discrepancies.
+    pub fn try_from_slice_with_buffer<const N: usize, C>(
+        points: &[C],
+        mut buffer: Vec<u8>,
+    ) -> Result<Self, MsgConversionError>
+    where
+        C: CopyPointConvertible<N>,
+    {
+        check_point_size::<N>()?;
+
+        buffer.clear();
+        match (system_endian(), Endian::default()) {
+            (Endian::Big, Endian::Big) | (Endian::Little, Endian::Little) => {
+                let (cloud, point_step, padding) = {
+                    let point: RPCL2Point<N> = C::default().into();
+                    debug_assert!(point.fields.len() == N);
+
+                    let field_names = crate::ordered_field_names::<N, C>();
+                    debug_assert!(field_names.len() <= N);
+
+                    let layout = KnownLayoutInfo::try_from(C::layout())?;
+                    debug_assert!(field_names.len() <= layout.fields.len());
+
+                    let mut offset = 0;
+                    let mut fields: Vec<PointFieldMsg> = Vec::with_capacity(field_names.len());
+                    let mut padding = Vec::new();
+                    for f in layout.fields.into_iter() {
+                        match f {
+                            PointField::Field {
+                                datatype,
+                                size,
+                                count,
+                            } => {
+                                fields.push(PointFieldMsg {
+                                    name: field_names[fields.len()].clone().into_owned(),
+                                    offset,
+                                    datatype: datatype.into(),
+                                    count,
+                                });
+                                offset += size * count;
+                            }
+                            PointField::Padding(size) => {
+                                padding.push(offset as usize..(offset + size) as usize);
+                                offset += size;
+                            }
+                        }
+                    }
+
+                    (
+                        PointCloud2MsgBuilder::new()
+                            .fields(fields)
+                            .point_step(offset),
+                        offset,
+                        padding,
+                    )
+                };
+
+                let bytes_total = points.len() * point_step as usize;
+                buffer.resize(bytes_total, u8::default());
+                unsafe {
+                    core::ptr::copy_nonoverlapping(
+                        points.as_ptr().cast::<u8>(),
+                        buffer.as_mut_ptr(),
+                        bytes_total,
+                    );
+                }
+
+                // The padding of `C` is never written by the producer, so it must not leak memory contents into the message.
+                if !padding.is_empty() {
+                    for point in buffer.chunks_exact_mut(point_step as usize) {
+                        for range in padding.iter() {
+                            point[range.clone()].fill(0);
+                        }
+                    }
+                }
+
+                Ok(cloud
+                    .data(buffer)
+                    .width(points.len() as u32)
+                    .row_step(points.len() as u32 * point_step)
+                    .build()?)
+            }
+            _ => {
+                let mut cloud = Self::try_from_iter(points.iter().copied())?;
+                buffer.extend_from_slice(&cloud.data);
+                cloud.data = buffer;
+                Ok(cloud)
+            }
+        }
+    }
+
+    /// Create a [`PointCloud2Msg`] from a Vec of points with the data stored in the given byte order.
+    ///
+    /// [`try_from_vec`](PointCloud2Msg::try_from_vec) always writes in the host byte order. Use this function
+    /// when the receiver expects a specific endianness, e.g. big-endian hardware or recorded datasets,
+    /// regardless of the machine the message is created on.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    /// use ros_pointcloud2::Endian;
+    ///
+    /// let cloud_points = vec![PointXYZ::new(1.0, 2.0, 3.0)];
+    ///
+    /// let msg = PointCloud2Msg::try_from_vec_endian(cloud_points.clone(), Endian::Big).unwrap();
+    /// assert_eq!(msg.endian, Endian::Big);
+    /// assert_eq!(&msg.data[0..4], &1.0f32.to_be_bytes());
+    ///
+    /// let back: Vec<PointXYZ> = msg.try_into_iter().unwrap().collect();
+    /// assert_eq!(back, cloud_points);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the byte buffer does not match the expected layout or the message contains other discrepancies.
+    pub fn try_from_vec_endian<const N: usize, C>(
+        vec: Vec<C>,
+        endian: Endian,
+    ) -> Result<Self, MsgConversionError>
+    where
+        C: CopyPointConvertible<N>,
+    {
+        let mut cloud = Self::try_from_vec(vec)?;
+        if cloud.endian == endian {
+            return Ok(cloud);
+        }
+
+        let mut elements = Vec::new();
+        for field in cloud.fields.iter() {
+            let size = FieldDatatype::try_from(field)?.size();
+            if size < 2 {
+                continue;
+            }
+            for element in 0..field.count as usize {
+                elements.push((field.offset as usize + element * size, size));
+            }
+        }
+
+        for idx in 0..cloud.point_count() {
+            let point_offset = cloud.point_offset(idx);
+            for &(offset, size) in elements.iter() {
+                let start = point_offset + offset;
+                cloud.data[start..start + size].reverse();
+            }
+        }
+
+        cloud.endian = endian;
+        Ok(cloud)
+    }
+
+    /// Convert the [`PointCloud2Msg`] to a Vec of points.
+    ///
+    /// Matching layouts are read by direct copy, which requires the point to implement [`CopyPointConvertible`].
+    /// Use [`try_into_iter`](PointCloud2Msg::try_into_iter) for points that are not `Copy`.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let cloud_points: Vec<PointXYZI> = vec![
+    ///     PointXYZI::new(1.0, 2.0, 3.0, 0.5),
+    ///     PointXYZI::new(4.0, 5.0, 6.0, 1.1),
+    /// ];
+    ///
+    /// let msg_out = PointCloud2Msg::try_from_vec(cloud_points).unwrap();
+    /// let cloud_points_out: Vec<PointXYZ> = msg_out.try_into_vec().unwrap();
+    /// assert_eq!(1.0, cloud_points_out.get(0).unwrap().x);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the byte buffer does not match the expected layout or the message contains other discrepancies.
+    pub fn try_into_vec<const N: usize, C>(self) -> Result<Vec<C>, MsgConversionError>
+    where
+        C: CopyPointConvertible<N>,
+    {
+        self.try_into_vec_profiled().map(|(vec, _)| vec)
+    }
+
+    /// Convert the [`PointCloud2Msg`] to a Vec of points like [`try_into_vec`](PointCloud2Msg::try_into_vec)
+    /// and report which strategy decoded the points.
+    ///
+    /// This makes it possible to assert in tests that a hot topic stays on the fast path when the schema evolves.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let cloud_points = vec![PointXYZ::new(1.0, 2.0, 3.0)];
+    /// let msg = PointCloud2Msg::try_from_vec(cloud_points).unwrap();
+    ///
+    /// let (_, path) = msg.clone().try_into_vec_profiled::<3, PointXYZ>().unwrap();
+    /// assert_eq!(path, DecodePath::ZeroCopy);
+    ///
+    /// // Without the padding of `PointXYZ`, every field is decoded.
+    /// let tight = msg.repack_tight().unwrap();
+    /// let (_, path) = tight.try_into_vec_profiled::<3, PointXYZ>().unwrap();
+    /// assert_eq!(path, DecodePath::PerField);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns the same errors as [`try_into_vec`](PointCloud2Msg::try_into_vec).
+    pub fn try_into_vec_profiled<const N: usize, C>(
+        self,
+    ) -> Result<(Vec<C>, DecodePath), MsgConversionError>
+    where
+        C: CopyPointConvertible<N>,
+    {
+        let path = match self.layout_matches::<N, C>()? {
+            MatchKind::Exact => DecodePath::ZeroCopy,
+            MatchKind::Strided => DecodePath::Strided,
+            // Endianess does not match, read point by point since Endian is read at conversion time.
+            MatchKind::RequiresConversion if system_endian() != self.endian => {
+                debug_log!(
+                    "message endian {:?} differs from the system, decoding every field",
+                    self.endian
+                );
+                DecodePath::EndianFallback
+            }
+            MatchKind::RequiresConversion => DecodePath::PerField,
+        };
+
+        Ok((self.to_owned_vec()?, path))
+    }
+
+    /// Borrow the points as a slice of `C` without copying or allocating.
+    ///
+    /// Unlike [`try_into_vec`](PointCloud2Msg::try_into_vec), there is no fallback: the layout of `C` must match the message
+    /// exactly, see [`MatchKind::Exact`], and the buffer must be aligned for `C`.
+    /// Use [`to_owned_vec`](PointCloud2Msg::to_owned_vec) for an owned copy that works with every layout.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let cloud_points = vec![PointXYZI::new(1.0, 2.0, 3.0, 0.5), PointXYZI::new(4.0, 5.0, 6.0, 1.1)];
+    /// let msg = PointCloud2Msg::try_from_vec(cloud_points.clone()).unwrap();
+    ///
+    /// let points: &[PointXYZI] = msg.try_borrow_slice().unwrap();
+    /// assert_eq!(points, &cloud_points[..]);
+    ///
+    /// // Without the padding of `PointXYZ`, the points can not be borrowed.
+    /// let tight = PointCloud2Msg::try_from_xyz_iter([(1.0, 2.0, 3.0)]).unwrap();
+    /// assert!(tight.try_borrow_slice::<3, PointXYZ>().is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::InvalidFieldFormat`] if the layout or endian differ, [`MsgConversionError::InvalidArgument`]
+    /// if the buffer is not aligned for `C`, which can happen for buffers that were not allocated by this crate,
+    /// and an error if the buffer is too short.
+    pub fn try_borrow_slice<const N: usize, C>(&self) -> Result<&[C], MsgConversionError>
+    where
+        C: CopyPointConvertible<N>,
+    {
+        if self.layout_matches::<N, C>()? != MatchKind::Exact
+            || core::mem::size_of::<C>() != self.point_step as usize
+        {
+            return Err(MsgConversionError::InvalidFieldFormat);
+        }
+        self.check_data_len()?;
+
+        let point_count = self.point_count();
+        if point_count == 0 {
+            return Ok(&[]);
+        }
+        if self.data.as_ptr().align_offset(core::mem::align_of::<C>()) != 0 {
+            return Err(MsgConversionError::InvalidArgument);
+        }
+
+        // The layout of `C` matches the message byte by byte, the buffer holds `point_count` points of
+        // `size_of::<C>()` bytes and the pointer is aligned.
+        Ok(unsafe { core::slice::from_raw_parts(self.data.as_ptr().cast::<C>(), point_count) })
+    }
+
+    /// Decode the points into a new Vec without consuming the message.
+    ///
+    /// This always allocates, also when the layout matches, so the result can be modified freely.
+    /// Every layout is supported like in [`try_into_vec`](PointCloud2Msg::try_into_vec).
+    /// Use [`try_borrow_slice`](PointCloud2Msg::try_borrow_slice) to guarantee that no memory is allocated.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let msg = PointCloud2Msg::try_from_vec(vec![PointXYZI::new(1.0, 2.0, 3.0, 0.5)]).unwrap();
+    ///
+    /// let mut points: Vec<PointXYZ> = msg.to_owned_vec().unwrap();
+    /// points[0].x = 7.0;
+    /// assert_eq!(msg.try_borrow_slice::<4, PointXYZI>().unwrap()[0].x, 1.0);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns the same errors as [`try_into_vec`](PointCloud2Msg::try_into_vec).
+    pub fn to_owned_vec<const N: usize, C>(&self) -> Result<Vec<C>, MsgConversionError>
+    where
+        C: CopyPointConvertible<N>,
+    {
+        let point_count = self
+            .dimensions
+            .checked_len()
+            .ok_or(MsgConversionError::NumberConversion)?;
+        // The dimensions are untrusted, so they are checked against the buffer before allocating.
+        self.check_data_len()?;
+        let mut points = vec![C::default(); point_count];
+        let written = self.decode_into(&mut points)?;
+        points.truncate(written);
+        Ok(points)
+    }
+
+    /// Decode the points into a preallocated slice and return the number of written points.
+    ///
+    /// The slice needs room for at least all points of the cloud. No memory is allocated for the points,
+    /// which makes this suitable for loops with a latency budget that reuse one buffer.
+    /// Matching layouts are copied directly like in [`try_into_vec`](PointCloud2Msg::try_into_vec), other layouts are decoded point by point.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let cloud_points = vec![PointXYZI::new(1.0, 2.0, 3.0, 0.5), PointXYZI::new(4.0, 5.0, 6.0, 1.1)];
+    /// let msg = PointCloud2Msg::try_from_vec(cloud_points.clone()).unwrap();
+    ///
+    /// let mut buffer = vec![PointXYZI::default(); 16];
+    /// let written = msg.decode_into(&mut buffer).unwrap();
+    /// assert_eq!(&buffer[..written], &cloud_points[..]);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::InvalidArgument`] if the slice is too short for the cloud.
+    /// Otherwise, the same errors as [`try_into_vec`](PointCloud2Msg::try_into_vec) are returned.
+    pub fn decode_into<const N: usize, C>(&self, out: &mut [C]) -> Result<usize, MsgConversionError>
+    where
+        C: CopyPointConvertible<N>,
+    {
+        let point_count = self
+            .dimensions
+            .checked_len()
+            .ok_or(MsgConversionError::NumberConversion)?;
+        let out = out
+            .get_mut(..point_count)
+            .ok_or(MsgConversionError::InvalidArgument)?;
+
+        match self.layout_matches::<N, C>()? {
+            MatchKind::Exact => {
+                self.check_data_len()?;
+                unsafe {
+                    core::ptr::copy_nonoverlapping(
+                        self.data.as_ptr(),
+                        out.as_mut_ptr().cast::<u8>(),
+                        point_count * self.point_step as usize,
+                    );
+                }
+            }
+            MatchKind::Strided => {
+                self.check_data_len()?;
+                for (idx, point) in out.iter_mut().enumerate() {
+                    *point = unsafe {
+                        self.data
+                            .as_ptr()
+                            .add(self.point_offset(idx))
+                            .cast::<C>()
+                            .read_unaligned()
+                    };
+                }
+            }
+            MatchKind::RequiresConversion => {
+                let (offsets, datatypes) = self.element_offsets::<N, C>()?;
+                self.check_data_len()?;
+
+                for (idx, point) in out.iter_mut().enumerate() {
+                    let point_offset = self.point_offset(idx);
+                    let mut pdata = [PointData::default(); N];
+                    for ((pdata, offset), datatype) in
+                        pdata.iter_mut().zip(offsets.iter()).zip(datatypes.iter())
+                    {
+                        *pdata = PointData::from_buffer(
+                            &self.data,
+                            point_offset + offset,
+                            *datatype,
+                            self.endian,
+                        );
+                    }
+                    *point = C::from(pdata.into());
+                }
+            }
+        }
+
+        Ok(point_count)
+    }
+
+    /// Offset in the point and datatype in the message of every value of `C`, found by field name.
+    fn element_offsets<const N: usize, C>(
+        &self,
+    ) -> Result<([usize; N], [FieldDatatype; N]), MsgConversionError>
+    where
+        C: PointConvertible<N>,
+    {
+        let mut offsets = [0; N];
+        let mut datatypes = [FieldDatatype::default(); N];
+        for (((name, element), offset), datatype) in ordered_field_elements::<N, C>()
+            .iter()
+            .zip(offsets.iter_mut())
+            .zip(datatypes.iter_mut())
+        {
+            let field = self.field_by_name(name)?;
+            if *element >= field.count as usize {
+                return Err(MsgConversionError::UnsupportedFieldCount);
+            }
+            *datatype = FieldDatatype::try_from(field)?;
+            *offset = field.offset as usize + element * datatype.size();
+            if *offset + datatype.size() > self.point_step as usize {
+                return Err(MsgConversionError::DataLengthMismatch);
+            }
+        }
+
+        Ok((offsets, datatypes))
+    }
+
+    /// Decode the points as `C` without consuming or copying the message, like [`try_into_iter`](PointCloud2Msg::try_into_iter).
+    ///
+    /// With `lossy`, fields of `C` that are missing in the message are read as zero, except for `x`, `y` and `z`,
+    /// like [`try_into_iter_lossy`](PointCloud2Msg::try_into_iter_lossy).
+    fn borrowed_iter<const N: usize, C>(
+        &self,
+        lossy: bool,
+    ) -> Result<impl Iterator<Item = C> + '_, MsgConversionError>
+    where
+        C: PointConvertible<N>,
+    {
+        check_point_size::<N>()?;
+
+        let mut offsets = [None; N];
+        let mut datatypes = [FieldDatatype::default(); N];
+        let mut names_not_found: Vec<Cow<'static, str>> = Vec::new();
+        for (((name, element), offset), datatype) in ordered_field_elements::<N, C>()
+            .into_iter()
+            .zip(offsets.iter_mut())
+            .zip(datatypes.iter_mut())
+        {
+            let Some(field) = self.fields.iter().find(|field| field.name == name) else {
+                let required = !lossy || matches!(name.as_ref(), "x" | "y" | "z");
+                if required && !names_not_found.contains(&name) {
+                    names_not_found.push(name);
+                }
+                continue;
+            };
+            if element >= field.count as usize {
+                return Err(MsgConversionError::UnsupportedFieldCount);
+            }
+            *datatype = FieldDatatype::try_from(field)?;
+            let field_offset = field.offset as usize + element * datatype.size();
+            if field_offset + datatype.size() > self.point_step as usize {
+                return Err(MsgConversionError::DataLengthMismatch);
+            }
+            *offset = Some(field_offset);
+        }
+
+        if !names_not_found.is_empty() {
+            return Err(MsgConversionError::FieldsNotFound(names_not_found));
+        }
+        self.check_data_len()?;
+
+        Ok((0..self.point_count()).map(move |idx| {
+            let point_offset = self.point_offset(idx);
+            let mut pdata = [PointData::default(); N];
+            for ((pdata, offset), datatype) in
+                pdata.iter_mut().zip(offsets.iter()).zip(datatypes.iter())
+            {
+                if let Some(offset) = offset {
+                    *pdata = PointData::from_buffer(
+                        &self.data,
+                        point_offset + offset,
+                        *datatype,
+                        self.endian,
+                    );
+                }
+            }
+            C::from(pdata.into())
+        }))
+    }
+
+    /// Convert the [`PointCloud2Msg`] to a Vec of points like [`try_into_vec`](PointCloud2Msg::try_into_vec)
+    /// but require that the point type models every field of the message.
+    ///
+    /// This is useful when fields should not be dropped silently by decoding into a narrower type.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let cloud_points = vec![PointXYZI::new(1.0, 2.0, 3.0, 0.5)];
+    /// let msg = PointCloud2Msg::try_from_vec(cloud_points).unwrap();
+    ///
+    /// assert!(msg.clone().try_into_vec_exact::<4, PointXYZI>().is_ok());
+    /// assert!(msg.try_into_vec_exact::<3, PointXYZ>().is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::SchemaMismatch`] with the unused fields if the message has fields that are not part of the point type.
+    /// Otherwise, the same errors as [`try_into_vec`](PointCloud2Msg::try_into_vec) are returned.
+    pub fn try_into_vec_exact<const N: usize, C>(self) -> Result<Vec<C>, MsgConversionError>
+    where
+        C: CopyPointConvertible<N>,
+    {
+        let field_names = ordered_field_names::<N, C>();
+        let extra: Vec<String> = self
+            .fields
+            .iter()
+            .filter(|field| !field_names.iter().any(|name| *name == field.name))
+            .map(|field| field.name.clone())
+            .collect();
+
+        if !extra.is_empty() {
+            return Err(MsgConversionError::SchemaMismatch {
+                missing: Vec::new(),
+                extra,
+                mismatched: Vec::new(),
+            });
+        }
+
+        self.try_into_vec()
+    }
+
+    /// Convert the [`PointCloud2Msg`] to a Vec of points like [`try_into_vec`](PointCloud2Msg::try_into_vec)
+    /// and also return the fields of the message that are not part of the point type.
+    ///
+    /// The returned fields keep their offsets, so their values can be read from the same buffer later,
+    /// e.g. with [`field_iter`](PointCloud2Msg::field_iter) on a clone of the message.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let cloud_points = vec![PointXYZI::new(1.0, 2.0, 3.0, 0.5)];
+    /// let msg = PointCloud2Msg::try_from_vec(cloud_points).unwrap();
+    ///
+    /// let (points, extras) = msg.try_into_vec_with_extras::<3, PointXYZ>().unwrap();
+    /// assert_eq!(points, vec![PointXYZ::new(1.0, 2.0, 3.0)]);
+    /// assert_eq!(extras[0].name, "intensity");
+    /// ```
+    ///
+    /// # Errors
+    /// Returns the same errors as [`try_into_vec`](PointCloud2Msg::try_into_vec).
+    pub fn try_into_vec_with_extras<const N: usize, C>(
+        self,
+    ) -> Result<(Vec<C>, Vec<PointFieldMsg>), MsgConversionError>
+    where
+        C: CopyPointConvertible<N>,
+    {
+        let field_names = ordered_field_names::<N, C>();
+        let extras = self
+            .fields
+            .iter()
+            .filter(|field| !field_names.iter().any(|name| *name == field.name))
+            .cloned()
+            .collect();
+
+        Ok((self.try_into_vec()?, extras))
+    }
+
+    /// Convert the [`PointCloud2Msg`] to a Vec of points like [`try_into_vec`](PointCloud2Msg::try_into_vec)
+    /// but refuse clouds that declare more than `max_points` points.
+    ///
+    /// The check happens before any memory is reserved, so a crafted width and height from an untrusted source
+    /// cannot trigger a huge allocation.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let cloud_points = vec![PointXYZ::new(1.0, 2.0, 3.0); 10];
+    /// let msg = PointCloud2Msg::try_from_vec(cloud_points).unwrap();
+    ///
+    /// assert!(msg.clone().try_into_vec_limited::<3, PointXYZ>(10).is_ok());
+    /// assert!(matches!(
+    ///     msg.try_into_vec_limited::<3, PointXYZ>(5),
+    ///     Err(MsgConversionError::ExceedsLimit { points: 10, limit: 5 })
+    /// ));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::ExceedsLimit`] if the declared point count is larger than `max_points`.
+    /// Otherwise, the same errors as [`try_into_vec`](PointCloud2Msg::try_into_vec) are returned.
+    pub fn try_into_vec_limited<const N: usize, C>(
+        self,
+        max_points: usize,
+    ) -> Result<Vec<C>, MsgConversionError>
+    where
+        C: CopyPointConvertible<N>,
+    {
+        let points = self.point_count();
+        if points > max_points {
+            return Err(MsgConversionError::ExceedsLimit {
+                points,
+                limit: max_points,
+            });
+        }
+
+        self.try_into_vec()
+    }
+
+    /// Decode only the points `start..start + count` into a Vec, e.g. to page through a huge cloud.
+    ///
+    /// Only the bytes of the window are copied before decoding, so the rest of the cloud is never converted.
+    /// Organized clouds are indexed row by row and padding between the rows is skipped.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let cloud_points = (0..100).map(|i| PointXYZ::new(i as f32, 0.0, 0.0)).collect::<Vec<_>>();
+    /// let msg = PointCloud2Msg::try_from_vec(cloud_points.clone()).unwrap();
+    ///
+    /// let page: Vec<PointXYZ> = msg.try_into_vec_range(10, 5).unwrap();
+    /// assert_eq!(page, cloud_points[10..15]);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::InvalidArgument`] if the window exceeds the number of points.
+    /// Otherwise, the same errors as [`try_into_vec`](PointCloud2Msg::try_into_vec) are returned.
+    pub fn try_into_vec_range<const N: usize, C>(
+        &self,
+        start: usize,
+        count: usize,
+    ) -> Result<Vec<C>, MsgConversionError>
+    where
+        C: CopyPointConvertible<N>,
+    {
+        let end = start
+            .checked_add(count)
+            .filter(|&end| end <= self.point_count())
+            .ok_or(MsgConversionError::InvalidArgument)?;
+
+        let indices: Vec<usize> = (start..end).collect();
+        self.select_points(&indices)?.try_into_vec()
+    }
+
+    /// Convert the [`PointCloud2Msg`] to an iterator.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let cloud_points: Vec<PointXYZI> = vec![
+    ///     PointXYZI::new(1.0, 2.0, 3.0, 0.5),
+    ///     PointXYZI::new(4.0, 5.0, 6.0, 1.1),
+    /// ];
+    ///
+    /// let msg_out = PointCloud2Msg::try_from_iter(cloud_points).unwrap();
+    /// let cloud_points_out = msg_out.try_into_iter().unwrap().collect::<Vec<PointXYZ>>();
+    /// ```
+    /// # Errors
+    /// Returns an error if the byte buffer does not match the expected layout or the message contains other discrepancies.
+    pub fn try_into_iter<const N: usize, C>(
+        self,
+    ) -> Result<impl Iterator<Item = C>, MsgConversionError>
     where
-        C: PointConvertible<N> + Send + Sync,
+        C: PointConvertible<N>,
     {
-        Self::try_from_vec(iterable.collect::<Vec<_>>())
+        iterator::PointCloudIterator::try_from(self)
     }
 
-    /// Create a [`PointCloud2Msg`] from a Vec of points.
-    /// Since the point type is known at compile time, the conversion is done by direct copy.
+    /// Convert the [`PointCloud2Msg`] to an iterator and read fields of `C` that are missing in the message as zero.
+    ///
+    /// This allows reading related topics with one superset point type, e.g. [`PointXYZI`](points::PointXYZI) from a cloud without intensities.
+    /// The coordinates `x`, `y` and `z` are still required.
     ///
     /// # Example
     /// ```
     /// use ros_pointcloud2::prelude::*;
     ///
-    /// let cloud_points: Vec<PointXYZ> = vec![
-    ///     PointXYZ::new(1.0, 2.0, 3.0),
-    ///     PointXYZ::new(4.0, 5.0, 6.0),
-    /// ];
-    ///
-    /// let msg_out = PointCloud2Msg::try_from_vec(cloud_points).unwrap();
+    /// let msg = PointCloud2Msg::try_from_vec(vec![PointXYZ::new(1.0, 2.0, 3.0)]).unwrap();
+    /// let points: Vec<PointXYZI> = msg.try_into_iter_lossy().unwrap().collect();
+    /// assert_eq!(points, vec![PointXYZI::new(1.0, 2.0, 3.0, 0.0)]);
     /// ```
     ///
     /// # Errors
-    /// Returns an error if the byte buffer does not match the expected layout or the message contains other discrepancies.
-    pub fn try_from_vec<const N: usize, C>(vec: Vec<C>) -> Result<Self, MsgConversionError>
+    /// Returns an error if a coordinate field is missing, the byte buffer does not match the expected layout or the message contains other discrepancies.
+    pub fn try_into_iter_lossy<const N: usize, C>(
+        self,
+    ) -> Result<impl Iterator<Item = C>, MsgConversionError>
     where
         C: PointConvertible<N>,
     {
-        match (system_endian(), Endian::default()) {
-            (Endian::Big, Endian::Big) | (Endian::Little, Endian::Little) => {
-                let (mut cloud, point_step) = {
-                    let point: RPCL2Point<N> = C::default().into();
-                    debug_assert!(point.fields.len() == N);
-
-                    let field_names = crate::ordered_field_names::<N, C>();
-                    debug_assert!(field_names.len() == N);
-
-                    let layout = KnownLayoutInfo::try_from(C::layout())?;
-                    debug_assert!(field_names.len() <= layout.fields.len());
-
-                    let mut offset = 0;
-                    let mut fields: Vec<PointFieldMsg> = Vec::with_capacity(field_names.len());
-                    for f in layout.fields.into_iter() {
-                        match f {
-                            PointField::Field {
-                                datatype,
-                                size,
-                                count,
-                            } => {
-                                fields.push(PointFieldMsg {
-                                    name: field_names[fields.len()].clone(),
-                                    offset,
-                                    datatype,
-                                    ..Default::default()
-                                });
-                                offset += size * count;
-                            }
-                            PointField::Padding(size) => {
-                                offset += size;
-                            }
-                        }
-                    }
-
-                    (
-                        PointCloud2MsgBuilder::new()
-                            .fields(fields)
-                            .point_step(offset),
-                        offset,
-                    )
-                };
-
-                let bytes_total = vec.len() * point_step as usize;
-                cloud.data.resize(bytes_total, u8::default());
-                let raw_data: *mut C = cloud.data.as_mut_ptr() as *mut C;
-                unsafe {
-                    core::ptr::copy_nonoverlapping(
-                        vec.as_ptr().cast::<u8>(),
-                        raw_data.cast::<u8>(),
-                        bytes_total,
-                    );
-                }
-
-                Ok(cloud
-                    .width(vec.len() as u32)
-                    .row_step(vec.len() as u32 * point_step)
-                    .build()?)
-            }
-            _ => Self::try_from_iter(vec),
-        }
+        iterator::PointCloudIterator::from_msg(self, true, ColorDecode::Packed)
     }
 
-    /// Convert the [`PointCloud2Msg`] to a Vec of points.
+    /// Convert the [`PointCloud2Msg`] to an iterator and choose how a packed `rgb` field is decoded.
+    ///
+    /// With [`ColorDecode::Channels`], a point type with separate `u8` fields `r`, `g` and `b` can be read from a message
+    /// that only stores the packed `rgb` field. The channels are taken from the bytes of the packed value, so no separate pass is needed.
+    /// With [`ColorDecode::Packed`], this is the same as [`try_into_iter`](PointCloud2Msg::try_into_iter).
     ///
     /// # Example
     /// ```
     /// use ros_pointcloud2::prelude::*;
     ///
-    /// let cloud_points: Vec<PointXYZI> = vec![
-    ///     PointXYZI::new(1.0, 2.0, 3.0, 0.5),
-    ///     PointXYZI::new(4.0, 5.0, 6.0, 1.1),
-    /// ];
+    /// #[derive(Clone, Copy, Debug, Default, PartialEq)]
+    /// struct PointXYZColor {
+    ///     x: f32, y: f32, z: f32,
+    ///     r: u8, g: u8, b: u8,
+    /// }
+    /// # impl From<RPCL2Point<6>> for PointXYZColor {
+    /// #     fn from(p: RPCL2Point<6>) -> Self {
+    /// #         Self { x: p[0].get(), y: p[1].get(), z: p[2].get(), r: p[3].get(), g: p[4].get(), b: p[5].get() }
+    /// #     }
+    /// # }
+    /// # impl From<PointXYZColor> for RPCL2Point<6> {
+    /// #     fn from(p: PointXYZColor) -> Self {
+    /// #         [p.x.into(), p.y.into(), p.z.into(), p.r.into(), p.g.into(), p.b.into()].into()
+    /// #     }
+    /// # }
+    /// # unsafe impl PointConvertible<6> for PointXYZColor {
+    /// #     fn layout() -> LayoutDescription {
+    /// #         LayoutDescription::new(&[
+    /// #             LayoutField::new("x", "f32", 4),
+    /// #             LayoutField::new("y", "f32", 4),
+    /// #             LayoutField::new("z", "f32", 4),
+    /// #             LayoutField::new("r", "u8", 1),
+    /// #             LayoutField::new("g", "u8", 1),
+    /// #             LayoutField::new("b", "u8", 1),
+    /// #             LayoutField::padding(1),
+    /// #         ])
+    /// #     }
+    /// # }
     ///
-    /// let msg_out = PointCloud2Msg::try_from_vec(cloud_points).unwrap();
-    /// let cloud_points_out: Vec<PointXYZ> = msg_out.try_into_vec().unwrap();
-    /// assert_eq!(1.0, cloud_points_out.get(0).unwrap().x);
+    /// let msg = PointCloud2Msg::try_from_vec(vec![PointXYZRGB::new(1.0, 2.0, 3.0, 10, 20, 30)]).unwrap();
+    ///
+    /// let points: Vec<PointXYZColor> = msg
+    ///     .try_into_iter_with_color(ColorDecode::Channels)
+    ///     .unwrap()
+    ///     .collect();
+    /// assert_eq!((points[0].r, points[0].g, points[0].b), (10, 20, 30));
     /// ```
     ///
     /// # Errors
-    /// Returns an error if the byte buffer does not match the expected layout or the message contains other discrepancies.
-    pub fn try_into_vec<const N: usize, C>(self) -> Result<Vec<C>, MsgConversionError>
+    /// Returns [`MsgConversionError::InvalidFieldFormat`] if a channel is unpacked into a field of `C` that is not `u8`
+    /// or the packed field is not 4 bytes wide. Also returns the errors of [`try_into_iter`](PointCloud2Msg::try_into_iter).
+    pub fn try_into_iter_with_color<const N: usize, C>(
+        self,
+        color: ColorDecode,
+    ) -> Result<impl Iterator<Item = C>, MsgConversionError>
     where
         C: PointConvertible<N>,
     {
-        match (system_endian(), self.endian) {
-            (Endian::Big, Endian::Big) | (Endian::Little, Endian::Little) => {
-                let bytematch = match self.byte_similarity::<N, C>()? {
-                    ByteSimilarity::Equal => true,
-                    ByteSimilarity::Overlapping => false,
-                    ByteSimilarity::Different => return Ok(self.try_into_iter()?.collect()),
-                };
-
-                let cloud_width = self.dimensions.width as usize;
-                let point_step = self.point_step as usize;
-                let mut vec: Vec<C> = Vec::with_capacity(cloud_width);
-                if bytematch {
-                    unsafe {
-                        core::ptr::copy_nonoverlapping(
-                            self.data.as_ptr(),
-                            vec.as_mut_ptr().cast::<u8>(),
-                            self.data.len(),
-                        );
-                        vec.set_len(cloud_width);
-                    }
-                } else {
-                    unsafe {
-                        for i in 0..cloud_width {
-                            let point_ptr = self.data.as_ptr().add(i * point_step).cast::<C>();
-                            let point = point_ptr.read();
-                            vec.push(point);
-                        }
-                    }
-                }
-
-                Ok(vec)
-            }
-            _ => Ok(self.try_into_iter()?.collect()), // Endianess does not match, read point by point since Endian is read at conversion time.
-        }
+        iterator::PointCloudIterator::from_msg(self, false, color)
     }
 
-    /// Convert the [`PointCloud2Msg`] to an iterator.
+    /// Convert the [`PointCloud2Msg`] to an iterator that also yields whether the `x`, `y` and `z` coordinates of each point are finite.
+    ///
+    /// Sparse organized clouds mark missing returns with NaN coordinates, so this tells the valid pixels apart from the holes
+    /// without reading the coordinates again after the conversion.
     ///
     /// # Example
     /// ```
     /// use ros_pointcloud2::prelude::*;
     ///
-    /// let cloud_points: Vec<PointXYZI> = vec![
-    ///     PointXYZI::new(1.0, 2.0, 3.0, 0.5),
-    ///     PointXYZI::new(4.0, 5.0, 6.0, 1.1),
-    /// ];
+    /// let cloud_points = vec![PointXYZ::new(1.0, 2.0, 3.0), PointXYZ::new(f32::NAN, f32::NAN, f32::NAN)];
+    /// let msg = PointCloud2Msg::try_from_vec(cloud_points).unwrap();
     ///
-    /// let msg_out = PointCloud2Msg::try_from_iter(cloud_points).unwrap();
-    /// let cloud_points_out = msg_out.try_into_iter().unwrap().collect::<Vec<PointXYZ>>();
+    /// let validity: Vec<bool> = msg
+    ///     .try_into_iter_with_validity::<3, PointXYZ>()
+    ///     .unwrap()
+    ///     .map(|(_, valid)| valid)
+    ///     .collect();
+    /// assert_eq!(validity, vec![true, false]);
     /// ```
+    ///
     /// # Errors
-    /// Returns an error if the byte buffer does not match the expected layout or the message contains other discrepancies.
-    pub fn try_into_iter<const N: usize, C>(
+    /// Returns an error if `C` or the message have no `f32` fields `x`, `y` and `z`, the byte buffer does not match the expected layout
+    /// or the message contains other discrepancies.
+    pub fn try_into_iter_with_validity<const N: usize, C>(
         self,
-    ) -> Result<impl Iterator<Item = C>, MsgConversionError>
+    ) -> Result<impl Iterator<Item = (C, bool)>, MsgConversionError>
     where
         C: PointConvertible<N>,
     {
-        iterator::PointCloudIterator::try_from(self)
+        self.xyz_f32_offsets()?;
+
+        let field_elements = ordered_field_elements::<N, C>();
+        let mut xyz_idx = [0; 3];
+        for (idx, name) in xyz_idx.iter_mut().zip(["x", "y", "z"]) {
+            *idx = field_elements
+                .iter()
+                .position(|(field_name, _)| field_name == name)
+                .ok_or_else(|| MsgConversionError::FieldsNotFound(vec![name.into()]))?;
+        }
+
+        let mut iter = iterator::PointCloudIterator::<N, C>::try_from(self)?;
+        Ok(core::iter::from_fn(move || {
+            iter.next_raw().map(|point| {
+                let valid = xyz_idx
+                    .iter()
+                    .all(|&idx| point[idx].get::<f32>().is_finite());
+                (C::from(point), valid)
+            })
+        }))
     }
 
     /// Convert the PointCloud2Msg to a parallel iterator. Requires the `rayon` feature to be enabled.
@@ -822,6 +5481,38 @@ impl PointCloud2Msg {
     }
 }
 
+/// Convert every point of a cloud with `f` into a new cloud of another point type.
+///
+/// The predefined points implement `From` for the common lossy conversions, so `Into::into` can be passed for them.
+///
+/// # Example
+/// ```
+/// use ros_pointcloud2::prelude::*;
+///
+/// let cloud_points = vec![PointXYZI::new(1.0, 2.0, 3.0, 0.5)];
+/// let msg = PointCloud2Msg::try_from_vec(cloud_points).unwrap();
+///
+/// let xyz_msg = convert_cloud::<4, PointXYZI, 3, PointXYZ>(&msg, Into::into).unwrap();
+/// let points: Vec<PointXYZ> = xyz_msg.try_into_vec().unwrap();
+/// assert_eq!(points, vec![PointXYZ::new(1.0, 2.0, 3.0)]);
+/// ```
+///
+/// # Errors
+/// Returns an error if the message can not be read as `CI` or the result can not be written as `CO`.
+pub fn convert_cloud<const NI: usize, CI, const NO: usize, CO>(
+    msg: &PointCloud2Msg,
+    f: impl Fn(CI) -> CO,
+) -> Result<PointCloud2Msg, MsgConversionError>
+where
+    CI: PointConvertible<NI>,
+    CO: PointConvertible<NO>,
+{
+    let header = msg.header.clone();
+    let mut converted = PointCloud2Msg::try_from_iter(msg.borrowed_iter(false)?.map(f))?;
+    converted.header = header;
+    Ok(converted)
+}
+
 /// Internal point representation. It is used to store the point data entries.
 ///
 /// In each iteration, an internal point representation is converted to the desired point type.
@@ -846,6 +5537,26 @@ impl<const N: usize> From<[PointData; N]> for RPCL2Point<N> {
     }
 }
 
+impl<const N: usize> RPCL2Point<N> {
+    /// Read `K` consecutive values starting at `start`, e.g. the values of a field declared with [`LayoutField::array`].
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// let point: RPCL2Point<4> = [1.0f32.into(), 0.0f32.into(), 0.0f32.into(), 1.0f32.into()].into();
+    /// let normal: [f32; 3] = point.get_array(1);
+    /// assert_eq!(normal, [0.0, 0.0, 1.0]);
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if `start + K` is larger than `N`.
+    #[must_use]
+    pub fn get_array<T: FromBytes, const K: usize>(&self, start: usize) -> [T; K] {
+        core::array::from_fn(|i| self.fields[start + i].get())
+    }
+}
+
 /// Trait to enable point conversions on the fly.
 ///
 /// Implement this trait for your custom point you want to read or write in the message.
@@ -857,6 +5568,7 @@ impl<const N: usize> From<[PointData; N]> for RPCL2Point<N> {
 /// An example for diverging point layouts with padding can be seen in the source code of [this](points::PointXYZRGBA::layout) implementation.
 ///
 /// The generic parameter `N` is the number of fields in the point type. There can be more (hidden) fields that pad the layout but they do not count for the N.
+/// Fields declared with [`LayoutField::array`] count once per value.
 /// For
 ///
 /// # Derive
@@ -925,10 +5637,25 @@ pub unsafe trait PointConvertible<const N: usize>:
     fn layout() -> LayoutDescription;
 }
 
+/// Marker trait for points that can be copied byte by byte from and into the message buffer.
+///
+/// The `_vec` conversions use this for their direct copy fast path, so they need plain data without any owned resources.
+/// The iterator conversions only need [`PointConvertible`], which allows points to carry non-`Copy` fields for application logic.
+///
+/// It is implemented automatically for every [`PointConvertible`] type that is also `Copy`.
+/// Points with [`FromFieldValue`] fields must not be `Copy`, since the copied bytes are not checked for valid enum values.
+pub trait CopyPointConvertible<const N: usize>: PointConvertible<N> + Copy {}
+
+impl<const N: usize, C> CopyPointConvertible<N> for C where C: PointConvertible<N> + Copy {}
+
 #[derive(Debug, Clone)]
 enum PointField {
     Padding(u32),
-    Field { size: u32, datatype: u8, count: u32 },
+    Field {
+        size: u32,
+        datatype: FieldDatatype,
+        count: u32,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -946,10 +5673,24 @@ impl TryFrom<LayoutField> for PointField {
                 let datatype = FieldDatatype::from_str(typename.as_str())?;
                 Ok(Self::Field {
                     size: size.try_into()?,
-                    datatype: datatype.into(),
+                    datatype,
                     count: 1,
                 })
             }
+            LayoutField::Array {
+                name: _,
+                ty,
+                size,
+                count,
+            } => {
+                let typename: String = ty.into_owned().to_lowercase();
+                let datatype = FieldDatatype::from_str(typename.as_str())?;
+                Ok(Self::Field {
+                    size: size.try_into()?,
+                    datatype,
+                    count: count.try_into()?,
+                })
+            }
             LayoutField::Padding { size } => Ok(Self::Padding(size.try_into()?)),
         }
     }
@@ -979,6 +5720,10 @@ impl TryFrom<LayoutDescription> for KnownLayoutInfo {
 /// let original_data: f64 = 1.0;
 /// let pdata = PointData::new(original_data);
 /// let my_data: f64 = pdata.get();
+///
+/// // The standard conversion traits check the stored datatype.
+/// let checked: f64 = pdata.try_into().unwrap();
+/// assert!(f32::try_from(pdata).is_err());
 /// ```
 #[derive(Debug, Clone, Copy)]
 pub struct PointData {
@@ -987,6 +5732,11 @@ pub struct PointData {
     datatype: FieldDatatype,
 }
 
+/// The values of a point decoded by [`PointCloud2Msg::dynamic_iter`], stored inline for up to 8 values.
+#[cfg(feature = "smallvec")]
+#[cfg_attr(docsrs, doc(cfg(feature = "smallvec")))]
+pub type DynamicPoint = smallvec::SmallVec<[PointData; 8]>;
+
 impl Default for PointData {
     fn default() -> Self {
         Self {
@@ -1029,6 +5779,12 @@ impl PointData {
         }
     }
 
+    /// The datatype the value was read as.
+    #[must_use]
+    pub fn datatype(&self) -> FieldDatatype {
+        self.datatype
+    }
+
     /// Get the numeric value from the [`PointData`] description.
     ///
     /// # Example
@@ -1044,6 +5800,93 @@ impl PointData {
             Endian::Little => T::from_le_bytes(PointDataBuffer::new(self.bytes)),
         }
     }
+
+    /// Get a [`FromFieldValue`] type like a classification enum from its stored primitive value.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::prelude::*;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// enum Class {
+    ///     Ground,
+    ///     Unknown,
+    /// }
+    ///
+    /// impl FromFieldValue for Class {
+    ///     type Repr = u8;
+    ///
+    ///     fn from_value(value: u8) -> Self {
+    ///         match value {
+    ///             2 => Class::Ground,
+    ///             _ => Class::Unknown,
+    ///         }
+    ///     }
+    ///
+    ///     fn to_value(&self) -> u8 {
+    ///         match self {
+    ///             Class::Ground => 2,
+    ///             Class::Unknown => 0,
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let pdata = PointData::from_field_value(&Class::Ground);
+    /// assert_eq!(pdata.datatype(), FieldDatatype::U8);
+    /// assert_eq!(pdata.get_field_value::<Class>(), Class::Ground);
+    /// ```
+    #[must_use]
+    pub fn get_field_value<T: FromFieldValue>(&self) -> T {
+        T::from_value(self.get())
+    }
+
+    /// Create a [`PointData`] from the primitive value of a [`FromFieldValue`] type.
+    pub fn from_field_value<T: FromFieldValue>(value: &T) -> Self {
+        Self::new(value.to_value())
+    }
+
+    /// Like [`get`](PointData::get) but checks that `T` matches the stored datatype.
+    #[inline]
+    fn try_get<T: FromBytes>(&self) -> Result<T, MsgConversionError> {
+        if !T::field_datatype().matches_code(u8::from(self.datatype)) {
+            return Err(MsgConversionError::InvalidFieldFormat);
+        }
+
+        Ok(self.get())
+    }
+
+    /// The value widened to `f64`, which represents every supported datatype exactly.
+    fn to_f64(self) -> f64 {
+        match self.datatype {
+            FieldDatatype::F32 | FieldDatatype::RGB => self.get::<f32>() as f64,
+            FieldDatatype::F64 => self.get::<f64>(),
+            FieldDatatype::I32 => self.get::<i32>() as f64,
+            FieldDatatype::U8 => self.get::<u8>() as f64,
+            FieldDatatype::U16 => self.get::<u16>() as f64,
+            FieldDatatype::U32 => self.get::<u32>() as f64,
+            FieldDatatype::I8 => self.get::<i8>() as f64,
+            FieldDatatype::I16 => self.get::<i16>() as f64,
+        }
+    }
+
+    /// Convert `value` to `datatype`, saturating at the bounds of integer types, and store it with the given endian.
+    fn from_f64(value: f64, datatype: FieldDatatype, endian: Endian) -> Self {
+        let mut pdata = match datatype {
+            FieldDatatype::F32 | FieldDatatype::RGB => Self::new(value as f32),
+            FieldDatatype::F64 => Self::new(value),
+            FieldDatatype::I32 => Self::new(value as i32),
+            FieldDatatype::U8 => Self::new(value as u8),
+            FieldDatatype::U16 => Self::new(value as u16),
+            FieldDatatype::U32 => Self::new(value as u32),
+            FieldDatatype::I8 => Self::new(value as i8),
+            FieldDatatype::I16 => Self::new(value as i16),
+        };
+        if endian != system_endian() {
+            pdata.bytes[..datatype.size()].reverse();
+        }
+        pdata.endian = endian;
+        pdata
+    }
 }
 
 impl From<f32> for PointData {
@@ -1094,6 +5937,78 @@ impl From<i16> for PointData {
     }
 }
 
+impl TryFrom<PointData> for f32 {
+    type Error = MsgConversionError;
+
+    fn try_from(value: PointData) -> Result<Self, Self::Error> {
+        value.try_get()
+    }
+}
+
+impl TryFrom<PointData> for f64 {
+    type Error = MsgConversionError;
+
+    fn try_from(value: PointData) -> Result<Self, Self::Error> {
+        value.try_get()
+    }
+}
+
+impl TryFrom<PointData> for i32 {
+    type Error = MsgConversionError;
+
+    fn try_from(value: PointData) -> Result<Self, Self::Error> {
+        value.try_get()
+    }
+}
+
+impl TryFrom<PointData> for u8 {
+    type Error = MsgConversionError;
+
+    fn try_from(value: PointData) -> Result<Self, Self::Error> {
+        value.try_get()
+    }
+}
+
+impl TryFrom<PointData> for u16 {
+    type Error = MsgConversionError;
+
+    fn try_from(value: PointData) -> Result<Self, Self::Error> {
+        value.try_get()
+    }
+}
+
+impl TryFrom<PointData> for u32 {
+    type Error = MsgConversionError;
+
+    fn try_from(value: PointData) -> Result<Self, Self::Error> {
+        value.try_get()
+    }
+}
+
+impl TryFrom<PointData> for i8 {
+    type Error = MsgConversionError;
+
+    fn try_from(value: PointData) -> Result<Self, Self::Error> {
+        value.try_get()
+    }
+}
+
+impl TryFrom<PointData> for i16 {
+    type Error = MsgConversionError;
+
+    fn try_from(value: PointData) -> Result<Self, Self::Error> {
+        value.try_get()
+    }
+}
+
+impl TryFrom<PointData> for points::RGB {
+    type Error = MsgConversionError;
+
+    fn try_from(value: PointData) -> Result<Self, Self::Error> {
+        value.try_get()
+    }
+}
+
 /// Datatypes from the [`PointFieldMsg`].
 #[derive(Default, Clone, Debug, PartialEq, Copy)]
 pub enum FieldDatatype {
@@ -1109,6 +6024,7 @@ pub enum FieldDatatype {
 
     /// While RGB is not officially supported by ROS, it is used in the tooling as a packed f32.
     /// To make it easy to work with and avoid packing code, the [`RGB`](points::RGB) union is supported here and handled like a f32.
+    /// Some producers store the packed color as a u32 instead, which is accepted when reading as well.
     RGB,
 }
 
@@ -1128,6 +6044,36 @@ impl FieldDatatype {
     }
 }
 
+impl FieldDatatype {
+    /// Type name as used in [`LayoutField::new`].
+    fn type_name(self) -> &'static str {
+        match self {
+            FieldDatatype::F32 => "f32",
+            FieldDatatype::F64 => "f64",
+            FieldDatatype::I32 => "i32",
+            FieldDatatype::U8 => "u8",
+            FieldDatatype::U16 => "u16",
+            FieldDatatype::U32 => "u32",
+            FieldDatatype::I8 => "i8",
+            FieldDatatype::I16 => "i16",
+            FieldDatatype::RGB => "rgb",
+        }
+    }
+
+    /// Check if a field stored with the datatype code of a [`PointFieldMsg`] can be read as this datatype.
+    ///
+    /// Packed RGB colors are accepted as `f32` (PCL convention) and as `u32`, since both store the same 4 bytes.
+    #[inline]
+    fn matches_code(self, code: u8) -> bool {
+        match self {
+            FieldDatatype::RGB => {
+                code == u8::from(FieldDatatype::F32) || code == u8::from(FieldDatatype::U32)
+            }
+            _ => code == u8::from(self),
+        }
+    }
+}
+
 impl core::str::FromStr for FieldDatatype {
     type Err = MsgConversionError;
 
@@ -1283,12 +6229,41 @@ impl PointDataBuffer {
         self.0
     }
 
+    /// Copy up to 8 bytes into the buffer and fill the rest with zeros.
+    ///
+    /// The data is expected to have the width of a field datatype. Longer slices panic.
+    /// Use [`try_from_slice`](PointDataBuffer::try_from_slice) for buffers with a length that is not known in advance.
     #[must_use]
     pub fn from_slice(data: &[u8]) -> Self {
+        debug_assert!(
+            data.len() <= 8,
+            "PointDataBuffer holds at most 8 bytes but got {}.",
+            data.len()
+        );
         let mut buffer = [0; 8];
         data.iter().enumerate().for_each(|(i, &v)| buffer[i] = v);
         Self(buffer)
     }
+
+    /// Like [`from_slice`](PointDataBuffer::from_slice) but returns an error instead of panicking for more than 8 bytes.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::PointDataBuffer;
+    ///
+    /// assert!(PointDataBuffer::try_from_slice(&[1, 2, 3, 4]).is_ok());
+    /// assert!(PointDataBuffer::try_from_slice(&[0; 9]).is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`MsgConversionError::InvalidArgument`] if the slice is longer than 8 bytes.
+    pub fn try_from_slice(data: &[u8]) -> Result<Self, MsgConversionError> {
+        if data.len() > 8 {
+            return Err(MsgConversionError::InvalidArgument);
+        }
+
+        Ok(Self::from_slice(data))
+    }
 }
 
 impl From<&[u8]> for PointDataBuffer {
@@ -1364,6 +6339,27 @@ pub trait FromBytes: Default + Sized + Copy + GetFieldDatatype + Into<PointDataB
     fn from_le_bytes(bytes: PointDataBuffer) -> Self;
 }
 
+/// A type that is stored in a field as one of the supported primitives, e.g. a classification enum backed by `u8`.
+///
+/// Declare the field with [`LayoutField::new`] and the type of `Repr`, e.g. `LayoutField::new("class", "u8", 1)`,
+/// and convert the values with [`PointData::get_field_value`] and [`PointData::from_field_value`] in the conversions of the point.
+///
+/// Do not derive `Copy` for a point with such a field and only use the iterator conversions.
+/// Every `Copy` point implements [`CopyPointConvertible`], so [`PointCloud2Msg::try_from_vec`], [`PointCloud2Msg::try_into_vec`]
+/// and [`PointCloud2Msg::try_borrow_slice`] would copy the in-memory enum directly without calling [`FromFieldValue::from_value`].
+/// A stored value without a variant then becomes an invalid enum, which is undefined behavior.
+pub trait FromFieldValue: Sized {
+    /// The primitive that is stored in the message.
+    type Repr: FromBytes;
+
+    /// Decode the stored value. Values without a variant should map to a fallback instead of panicking,
+    /// since received messages may contain any value.
+    fn from_value(value: Self::Repr) -> Self;
+
+    /// The value that is stored in the message.
+    fn to_value(&self) -> Self::Repr;
+}
+
 impl FromBytes for i8 {
     fn from_be_bytes(bytes: PointDataBuffer) -> Self {
         Self::from_be_bytes([bytes[0]])
@@ -1535,7 +6531,7 @@ mod test {
         }
     }
 
-    #[derive(Debug, Clone, Default, PartialEq)]
+    #[derive(Debug, Clone, Copy, Default, PartialEq)]
     #[repr(C)]
     struct PointB {
         pub x: f32,
@@ -1652,6 +6648,25 @@ mod test {
         }
     }
 
+    #[test]
+    fn ros_bool_flags() {
+        assert_eq!(crate::Endian::from_ros_bool(true), crate::Endian::Big);
+        assert_eq!(crate::Endian::from_ros_bool(false), crate::Endian::Little);
+        assert!(crate::Endian::Big.to_ros_bool());
+        assert!(!crate::Endian::Little.to_ros_bool());
+
+        assert_eq!(
+            crate::Denseness::from_ros_bool(true),
+            crate::Denseness::Dense
+        );
+        assert_eq!(
+            crate::Denseness::from_ros_bool(false),
+            crate::Denseness::Sparse
+        );
+        assert!(crate::Denseness::Dense.to_ros_bool());
+        assert!(!crate::Denseness::Sparse.to_ros_bool());
+    }
+
     #[test]
     fn subtype_iterator_fallback() {
         let cloud_a = PointCloud2Msg::try_from_iter(vec![
@@ -1,4 +1,6 @@
 //! Predefined point types commonly used in ROS.
+//!
+//! Tuples of up to 8 primitives, e.g. `(f32, f32, f32)`, can also be used as points. Their fields are named after the tuple index.
 use crate::{LayoutDescription, LayoutField, PointConvertible, RPCL2Point};
 
 /// Packed RGB color encoding as used in ROS tools.
@@ -245,6 +247,39 @@ unsafe impl PointConvertible<3> for PointXYZ {
     }
 }
 
+#[cfg(feature = "nalgebra")]
+#[cfg_attr(docsrs, doc(cfg(feature = "nalgebra")))]
+impl From<RPCL2Point<3>> for nalgebra::Point3<f32> {
+    fn from(point: RPCL2Point<3>) -> Self {
+        nalgebra::Point3::new(point[0].get(), point[1].get(), point[2].get())
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+#[cfg_attr(docsrs, doc(cfg(feature = "nalgebra")))]
+impl From<nalgebra::Point3<f32>> for RPCL2Point<3> {
+    fn from(point: nalgebra::Point3<f32>) -> Self {
+        [point.x.into(), point.y.into(), point.z.into()].into()
+    }
+}
+
+/// nalgebra points can be converted directly with the fields `x`, `y` and `z`.
+///
+/// [`nalgebra::Point3`] is `#[repr(C)]` around its coordinate array, so `Point3<f32>` is laid out
+/// as three consecutive f32 values without padding. This allows the `_vec` conversions to copy the
+/// buffer directly without mapping into [`PointXYZ`] first.
+#[cfg(feature = "nalgebra")]
+#[cfg_attr(docsrs, doc(cfg(feature = "nalgebra")))]
+unsafe impl PointConvertible<3> for nalgebra::Point3<f32> {
+    fn layout() -> LayoutDescription {
+        LayoutDescription::new(&[
+            LayoutField::new("x", "f32", 4),
+            LayoutField::new("y", "f32", 4),
+            LayoutField::new("z", "f32", 4),
+        ])
+    }
+}
+
 /// 3D point with x, y, z coordinates and an intensity value, commonly used in ROS with PCL.
 #[derive(Clone, Debug, PartialEq, Copy, Default)]
 #[repr(C, align(16))]
@@ -988,3 +1023,724 @@ unsafe impl PointConvertible<6> for PointXYZNormal {
         ])
     }
 }
+
+/// 3D point with x, y, z coordinates, a label, a normal vector and the surface curvature.
+///
+/// The fields are packed without the padding of `pcl::PointXYZLNormal`, so clouds from PCL are decoded field by field
+/// instead of being copied directly.
+#[derive(Clone, Debug, PartialEq, Copy, Default)]
+#[repr(C, align(16))]
+pub struct PointXYZLNormal {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub label: u32,
+    pub normal_x: f32,
+    pub normal_y: f32,
+    pub normal_z: f32,
+    pub curvature: f32,
+}
+
+impl PointXYZLNormal {
+    #[allow(clippy::too_many_arguments)]
+    #[must_use]
+    pub fn new(
+        x: f32,
+        y: f32,
+        z: f32,
+        label: u32,
+        normal_x: f32,
+        normal_y: f32,
+        normal_z: f32,
+        curvature: f32,
+    ) -> Self {
+        Self {
+            x,
+            y,
+            z,
+            label,
+            normal_x,
+            normal_y,
+            normal_z,
+            curvature,
+        }
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "nalgebra")))]
+    pub fn xyz_f32(&self) -> nalgebra::Point3<f32> {
+        nalgebra::Point3::new(self.x, self.y, self.z)
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "nalgebra")))]
+    pub fn xyz_f64(&self) -> nalgebra::Point3<f64> {
+        nalgebra::Point3::new(self.x as f64, self.y as f64, self.z as f64)
+    }
+}
+
+unsafe impl Send for PointXYZLNormal {}
+unsafe impl Sync for PointXYZLNormal {}
+
+impl From<RPCL2Point<8>> for PointXYZLNormal {
+    fn from(point: RPCL2Point<8>) -> Self {
+        Self::new(
+            point[0].get(),
+            point[1].get(),
+            point[2].get(),
+            point[3].get(),
+            point[4].get(),
+            point[5].get(),
+            point[6].get(),
+            point[7].get(),
+        )
+    }
+}
+
+impl From<PointXYZLNormal> for RPCL2Point<8> {
+    fn from(point: PointXYZLNormal) -> Self {
+        [
+            point.x.into(),
+            point.y.into(),
+            point.z.into(),
+            point.label.into(),
+            point.normal_x.into(),
+            point.normal_y.into(),
+            point.normal_z.into(),
+            point.curvature.into(),
+        ]
+        .into()
+    }
+}
+
+unsafe impl PointConvertible<8> for PointXYZLNormal {
+    fn layout() -> LayoutDescription {
+        LayoutDescription::new(&[
+            LayoutField::new("x", "f32", 4),
+            LayoutField::new("y", "f32", 4),
+            LayoutField::new("z", "f32", 4),
+            LayoutField::new("label", "u32", 4),
+            LayoutField::new("normal_x", "f32", 4),
+            LayoutField::new("normal_y", "f32", 4),
+            LayoutField::new("normal_z", "f32", 4),
+            LayoutField::new("curvature", "f32", 4),
+        ])
+    }
+}
+
+/// A normal vector with the surface curvature and without coordinates, commonly used in PCL as the output of normal estimation.
+///
+/// Since the point has no coordinates, functions that need `x`, `y` and `z` do not work with clouds of this type.
+#[derive(Clone, Debug, PartialEq, Copy, Default)]
+#[repr(C, align(16))]
+pub struct PointNormal {
+    pub normal_x: f32,
+    pub normal_y: f32,
+    pub normal_z: f32,
+    pub curvature: f32,
+}
+
+impl PointNormal {
+    #[must_use]
+    pub fn new(normal_x: f32, normal_y: f32, normal_z: f32, curvature: f32) -> Self {
+        Self {
+            normal_x,
+            normal_y,
+            normal_z,
+            curvature,
+        }
+    }
+}
+
+unsafe impl Send for PointNormal {}
+unsafe impl Sync for PointNormal {}
+
+impl From<RPCL2Point<4>> for PointNormal {
+    fn from(point: RPCL2Point<4>) -> Self {
+        Self::new(
+            point[0].get(),
+            point[1].get(),
+            point[2].get(),
+            point[3].get(),
+        )
+    }
+}
+
+impl From<PointNormal> for RPCL2Point<4> {
+    fn from(point: PointNormal) -> Self {
+        [
+            point.normal_x.into(),
+            point.normal_y.into(),
+            point.normal_z.into(),
+            point.curvature.into(),
+        ]
+        .into()
+    }
+}
+
+unsafe impl PointConvertible<4> for PointNormal {
+    fn layout() -> LayoutDescription {
+        LayoutDescription::new(&[
+            LayoutField::new("normal_x", "f32", 4),
+            LayoutField::new("normal_y", "f32", 4),
+            LayoutField::new("normal_z", "f32", 4),
+            LayoutField::new("curvature", "f32", 4),
+        ])
+    }
+}
+
+/// Convert a f32 into the bits of a IEEE 754 half precision float with round to nearest even.
+///
+/// The bits are what is stored in a u16 field, since PointCloud2 has no datatype for half precision floats.
+/// With the `half` feature, `half::f16::from_bits` turns them into a typed value.
+#[must_use]
+pub fn f32_to_f16_bits(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exp = ((bits >> 23) & 0xff) as i32;
+    let mant = bits & 0x007f_ffff;
+
+    if exp == 0xff {
+        // inf stays inf, NaN stays a quiet NaN
+        return sign | 0x7c00 | if mant != 0 { 0x0200 } else { 0 };
+    }
+
+    let half_exp = exp - 127 + 15;
+    if half_exp >= 0x1f {
+        return sign | 0x7c00;
+    }
+
+    if half_exp <= 0 {
+        if half_exp < -10 {
+            return sign;
+        }
+        let mant = mant | 0x0080_0000;
+        let shift = (14 - half_exp) as u32;
+        let mut half_mant = (mant >> shift) as u16;
+        let rest = mant & ((1 << shift) - 1);
+        let halfway = 1 << (shift - 1);
+        if rest > halfway || (rest == halfway && half_mant & 1 == 1) {
+            half_mant += 1;
+        }
+        return sign | half_mant;
+    }
+
+    // A carry from rounding correctly moves into the exponent.
+    let mut half = sign | ((half_exp as u16) << 10) | (mant >> 13) as u16;
+    let rest = mant & 0x1fff;
+    if rest > 0x1000 || (rest == 0x1000 && half & 1 == 1) {
+        half += 1;
+    }
+    half
+}
+
+/// Convert the bits of a IEEE 754 half precision float, e.g. from a u16 field, into a f32.
+#[must_use]
+pub fn f16_bits_to_f32(half: u16) -> f32 {
+    let sign = u32::from(half & 0x8000) << 16;
+    let exp = u32::from((half >> 10) & 0x1f);
+    let mant = u32::from(half & 0x03ff);
+
+    let bits = match exp {
+        0 => {
+            let value = mant as f32 / (1 << 24) as f32;
+            return f32::from_bits(value.to_bits() | sign);
+        }
+        0x1f => sign | 0x7f80_0000 | (mant << 13),
+        _ => sign | ((exp + 127 - 15) << 23) | (mant << 13),
+    };
+    f32::from_bits(bits)
+}
+
+#[inline]
+fn sign_not_zero(value: f32) -> f32 {
+    if value >= 0.0 {
+        1.0
+    } else {
+        -1.0
+    }
+}
+
+/// Pack a unit normal vector into two half precision floats using octahedral encoding.
+///
+/// The values are returned as the bits of the half precision floats, since PointCloud2 has no datatype for them,
+/// so they are sent as u16 fields. Use [`unpack_normal_oct`] for the inverse.
+/// A zero vector is encoded like `(0, 0, 1)`.
+///
+/// # Example
+/// ```
+/// use ros_pointcloud2::points::{pack_normal_oct, unpack_normal_oct};
+///
+/// let packed = pack_normal_oct(0.0, 0.0, -1.0);
+/// assert_eq!(unpack_normal_oct(packed), [0.0, 0.0, -1.0]);
+/// ```
+#[must_use]
+pub fn pack_normal_oct(normal_x: f32, normal_y: f32, normal_z: f32) -> [u16; 2] {
+    let l1 = normal_x.abs() + normal_y.abs() + normal_z.abs();
+    if l1 == 0.0 || l1.is_nan() {
+        return [0, 0];
+    }
+
+    let mut u = normal_x / l1;
+    let mut v = normal_y / l1;
+    if normal_z < 0.0 {
+        // fold the lower hemisphere over the diagonals
+        let folded_u = (1.0 - v.abs()) * sign_not_zero(u);
+        v = (1.0 - u.abs()) * sign_not_zero(v);
+        u = folded_u;
+    }
+
+    [f32_to_f16_bits(u), f32_to_f16_bits(v)]
+}
+
+/// Unpack a normal vector from two half precision floats in octahedral encoding created by [`pack_normal_oct`].
+///
+/// The result is normalized to unit length.
+#[must_use]
+pub fn unpack_normal_oct(packed: [u16; 2]) -> [f32; 3] {
+    let u = f16_bits_to_f32(packed[0]);
+    let v = f16_bits_to_f32(packed[1]);
+
+    let z = 1.0 - u.abs() - v.abs();
+    let (x, y) = if z < 0.0 {
+        (
+            (1.0 - v.abs()) * sign_not_zero(u),
+            (1.0 - u.abs()) * sign_not_zero(v),
+        )
+    } else {
+        (u, v)
+    };
+
+    let inv_len = inv_sqrt_unit_range(x * x + y * y + z * z);
+    [x * inv_len, y * inv_len, z * inv_len]
+}
+
+/// Pack a unit normal vector into two typed half precision floats using octahedral encoding.
+///
+/// The same encoding as [`pack_normal_oct`], which returns the bits that are stored in the message.
+///
+/// # Example
+/// ```
+/// use ros_pointcloud2::points::{pack_normal_oct_f16, unpack_normal_oct_f16};
+///
+/// let packed = pack_normal_oct_f16(0.0, 1.0, 0.0);
+/// assert_eq!(packed[1], half::f16::ONE);
+/// assert_eq!(unpack_normal_oct_f16(packed), [0.0, 1.0, 0.0]);
+/// ```
+#[cfg(feature = "half")]
+#[cfg_attr(docsrs, doc(cfg(feature = "half")))]
+#[must_use]
+pub fn pack_normal_oct_f16(normal_x: f32, normal_y: f32, normal_z: f32) -> [half::f16; 2] {
+    pack_normal_oct(normal_x, normal_y, normal_z).map(half::f16::from_bits)
+}
+
+/// Unpack a normal vector from two typed half precision floats created by [`pack_normal_oct_f16`].
+#[cfg(feature = "half")]
+#[cfg_attr(docsrs, doc(cfg(feature = "half")))]
+#[must_use]
+pub fn unpack_normal_oct_f16(packed: [half::f16; 2]) -> [f32; 3] {
+    unpack_normal_oct(packed.map(half::f16::to_bits))
+}
+
+/// Inverse square root for values in `[1/3, 1]`, which is the range of the squared length of a decoded octahedral vector.
+/// Newton iterations are used since `f32::sqrt` is not available in `core`.
+#[inline]
+fn inv_sqrt_unit_range(value: f32) -> f32 {
+    let mut estimate = 1.0;
+    for _ in 0..6 {
+        estimate *= 1.5 - 0.5 * value * estimate * estimate;
+    }
+    estimate
+}
+
+/// 3D point with x, y, z coordinates and a compact normal vector in octahedral encoding.
+///
+/// The normal is stored as two half precision floats in u16 fields, see [`pack_normal_oct`].
+/// This needs half of the memory of [`PointXYZNormal`].
+#[derive(Clone, Debug, PartialEq, Copy, Default)]
+#[repr(C, align(16))]
+pub struct PointXYZOctNormal {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub normal_oct_u: u16,
+    pub normal_oct_v: u16,
+}
+
+impl PointXYZOctNormal {
+    #[must_use]
+    pub fn new(x: f32, y: f32, z: f32, normal_x: f32, normal_y: f32, normal_z: f32) -> Self {
+        let [normal_oct_u, normal_oct_v] = pack_normal_oct(normal_x, normal_y, normal_z);
+        Self {
+            x,
+            y,
+            z,
+            normal_oct_u,
+            normal_oct_v,
+        }
+    }
+
+    /// Get the decoded unit normal vector.
+    #[must_use]
+    pub fn normal(&self) -> [f32; 3] {
+        unpack_normal_oct([self.normal_oct_u, self.normal_oct_v])
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "nalgebra")))]
+    pub fn xyz_f32(&self) -> nalgebra::Point3<f32> {
+        nalgebra::Point3::new(self.x, self.y, self.z)
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "nalgebra")))]
+    pub fn xyz_f64(&self) -> nalgebra::Point3<f64> {
+        nalgebra::Point3::new(self.x as f64, self.y as f64, self.z as f64)
+    }
+}
+
+unsafe impl Send for PointXYZOctNormal {}
+unsafe impl Sync for PointXYZOctNormal {}
+
+impl From<RPCL2Point<5>> for PointXYZOctNormal {
+    fn from(point: RPCL2Point<5>) -> Self {
+        Self {
+            x: point[0].get(),
+            y: point[1].get(),
+            z: point[2].get(),
+            normal_oct_u: point[3].get(),
+            normal_oct_v: point[4].get(),
+        }
+    }
+}
+
+impl From<PointXYZOctNormal> for RPCL2Point<5> {
+    fn from(point: PointXYZOctNormal) -> Self {
+        [
+            point.x.into(),
+            point.y.into(),
+            point.z.into(),
+            point.normal_oct_u.into(),
+            point.normal_oct_v.into(),
+        ]
+        .into()
+    }
+}
+
+unsafe impl PointConvertible<5> for PointXYZOctNormal {
+    fn layout() -> LayoutDescription {
+        LayoutDescription::new(&[
+            LayoutField::new("x", "f32", 4),
+            LayoutField::new("y", "f32", 4),
+            LayoutField::new("z", "f32", 4),
+            LayoutField::new("normal_oct_u", "u16", 2),
+            LayoutField::new("normal_oct_v", "u16", 2),
+        ])
+    }
+}
+
+impl From<PointXYZNormal> for PointXYZOctNormal {
+    fn from(point: PointXYZNormal) -> Self {
+        Self::new(
+            point.x,
+            point.y,
+            point.z,
+            point.normal_x,
+            point.normal_y,
+            point.normal_z,
+        )
+    }
+}
+
+impl From<PointXYZOctNormal> for PointXYZNormal {
+    fn from(point: PointXYZOctNormal) -> Self {
+        let [normal_x, normal_y, normal_z] = point.normal();
+        Self::new(point.x, point.y, point.z, normal_x, normal_y, normal_z)
+    }
+}
+
+// Lossy conversions between the predefined points that drop the fields missing in the target.
+
+impl From<PointXYZI> for PointXYZ {
+    fn from(point: PointXYZI) -> Self {
+        Self {
+            x: point.x,
+            y: point.y,
+            z: point.z,
+        }
+    }
+}
+
+impl From<PointXYZL> for PointXYZ {
+    fn from(point: PointXYZL) -> Self {
+        Self {
+            x: point.x,
+            y: point.y,
+            z: point.z,
+        }
+    }
+}
+
+impl From<PointXYZRGB> for PointXYZ {
+    fn from(point: PointXYZRGB) -> Self {
+        Self {
+            x: point.x,
+            y: point.y,
+            z: point.z,
+        }
+    }
+}
+
+impl From<PointXYZRGBA> for PointXYZ {
+    fn from(point: PointXYZRGBA) -> Self {
+        Self {
+            x: point.x,
+            y: point.y,
+            z: point.z,
+        }
+    }
+}
+
+impl From<PointXYZRGBA> for PointXYZRGB {
+    fn from(point: PointXYZRGBA) -> Self {
+        Self {
+            x: point.x,
+            y: point.y,
+            z: point.z,
+            rgb: point.rgb,
+        }
+    }
+}
+
+impl From<PointXYZRGBNormal> for PointXYZ {
+    fn from(point: PointXYZRGBNormal) -> Self {
+        Self {
+            x: point.x,
+            y: point.y,
+            z: point.z,
+        }
+    }
+}
+
+impl From<PointXYZRGBNormal> for PointXYZRGB {
+    fn from(point: PointXYZRGBNormal) -> Self {
+        Self {
+            x: point.x,
+            y: point.y,
+            z: point.z,
+            rgb: point.rgb,
+        }
+    }
+}
+
+impl From<PointXYZRGBNormal> for PointXYZNormal {
+    fn from(point: PointXYZRGBNormal) -> Self {
+        Self {
+            x: point.x,
+            y: point.y,
+            z: point.z,
+            normal_x: point.normal_x,
+            normal_y: point.normal_y,
+            normal_z: point.normal_z,
+        }
+    }
+}
+
+impl From<PointXYZINormal> for PointXYZ {
+    fn from(point: PointXYZINormal) -> Self {
+        Self {
+            x: point.x,
+            y: point.y,
+            z: point.z,
+        }
+    }
+}
+
+impl From<PointXYZINormal> for PointXYZI {
+    fn from(point: PointXYZINormal) -> Self {
+        Self {
+            x: point.x,
+            y: point.y,
+            z: point.z,
+            intensity: point.intensity,
+        }
+    }
+}
+
+impl From<PointXYZINormal> for PointXYZNormal {
+    fn from(point: PointXYZINormal) -> Self {
+        Self {
+            x: point.x,
+            y: point.y,
+            z: point.z,
+            normal_x: point.normal_x,
+            normal_y: point.normal_y,
+            normal_z: point.normal_z,
+        }
+    }
+}
+
+impl From<PointXYZRGBL> for PointXYZ {
+    fn from(point: PointXYZRGBL) -> Self {
+        Self {
+            x: point.x,
+            y: point.y,
+            z: point.z,
+        }
+    }
+}
+
+impl From<PointXYZRGBL> for PointXYZRGB {
+    fn from(point: PointXYZRGBL) -> Self {
+        Self {
+            x: point.x,
+            y: point.y,
+            z: point.z,
+            rgb: point.rgb,
+        }
+    }
+}
+
+impl From<PointXYZRGBL> for PointXYZL {
+    fn from(point: PointXYZRGBL) -> Self {
+        Self {
+            x: point.x,
+            y: point.y,
+            z: point.z,
+            label: point.label,
+        }
+    }
+}
+
+impl From<PointXYZNormal> for PointXYZ {
+    fn from(point: PointXYZNormal) -> Self {
+        Self {
+            x: point.x,
+            y: point.y,
+            z: point.z,
+        }
+    }
+}
+
+impl From<PointXYZLNormal> for PointXYZ {
+    fn from(point: PointXYZLNormal) -> Self {
+        Self {
+            x: point.x,
+            y: point.y,
+            z: point.z,
+        }
+    }
+}
+
+impl From<PointXYZLNormal> for PointXYZL {
+    fn from(point: PointXYZLNormal) -> Self {
+        Self::new(point.x, point.y, point.z, point.label)
+    }
+}
+
+impl From<PointXYZLNormal> for PointXYZNormal {
+    fn from(point: PointXYZLNormal) -> Self {
+        Self::new(
+            point.x,
+            point.y,
+            point.z,
+            point.normal_x,
+            point.normal_y,
+            point.normal_z,
+        )
+    }
+}
+
+impl From<PointXYZLNormal> for PointNormal {
+    fn from(point: PointXYZLNormal) -> Self {
+        Self::new(
+            point.normal_x,
+            point.normal_y,
+            point.normal_z,
+            point.curvature,
+        )
+    }
+}
+
+// Widening conversions that fill the fields missing in the source with their defaults.
+
+impl From<PointXYZ> for PointXYZI {
+    fn from(point: PointXYZ) -> Self {
+        Self::new(point.x, point.y, point.z, 0.0)
+    }
+}
+
+impl From<PointXYZ> for PointXYZL {
+    fn from(point: PointXYZ) -> Self {
+        Self::new(point.x, point.y, point.z, 0)
+    }
+}
+
+/// Byte offsets of the fields of a tuple, indexed by tuple position.
+trait TupleOffsets<const N: usize> {
+    fn offsets() -> [usize; N];
+
+    /// Tuple positions in memory order, since the compiler may reorder the fields of `#[repr(Rust)]` types.
+    fn memory_order() -> [usize; N] {
+        let offsets = Self::offsets();
+        let mut order: [usize; N] = core::array::from_fn(|i| i);
+        order.sort_unstable_by_key(|&i| offsets[i]);
+        order
+    }
+}
+
+/// Implements [`PointConvertible`] for a tuple of primitives. The fields are named after their tuple index
+/// and described in memory order, so the message layout matches the tuple for direct copies.
+macro_rules! impl_point_convertible_for_tuple {
+    ($n:literal; $($ty:ident $idx:tt),+) => {
+        impl<$($ty),+> TupleOffsets<$n> for ($($ty,)+) {
+            fn offsets() -> [usize; $n] {
+                [$(core::mem::offset_of!(Self, $idx)),+]
+            }
+        }
+
+        impl<$($ty: crate::FromBytes),+> From<RPCL2Point<$n>> for ($($ty,)+) {
+            fn from(point: RPCL2Point<$n>) -> Self {
+                let order = <Self as TupleOffsets<$n>>::memory_order();
+                let mut position = [0; $n];
+                for (pos, idx) in order.iter().enumerate() {
+                    position[*idx] = pos;
+                }
+
+                ($(point[position[$idx]].get::<$ty>(),)+)
+            }
+        }
+
+        impl<$($ty: crate::FromBytes),+> From<($($ty,)+)> for RPCL2Point<$n> {
+            fn from(point: ($($ty,)+)) -> Self {
+                let order = <($($ty,)+) as TupleOffsets<$n>>::memory_order();
+                let values = [$(crate::PointData::new(point.$idx)),+];
+
+                core::array::from_fn::<_, $n, _>(|pos| values[order[pos]]).into()
+            }
+        }
+
+        unsafe impl<$($ty: crate::FromBytes),+> PointConvertible<$n> for ($($ty,)+) {
+            fn layout() -> LayoutDescription {
+                let offsets = <Self as TupleOffsets<$n>>::offsets();
+                let names = [$(stringify!($idx)),+];
+                let datatypes = [$(<$ty as crate::GetFieldDatatype>::field_datatype()),+];
+                let order = <Self as TupleOffsets<$n>>::memory_order();
+
+                LayoutDescription::from_offsets(
+                    core::mem::size_of::<Self>(),
+                    &order.map(|idx| (names[idx], datatypes[idx], offsets[idx])),
+                )
+            }
+        }
+    };
+}
+
+impl_point_convertible_for_tuple!(1; A 0);
+impl_point_convertible_for_tuple!(2; A 0, B 1);
+impl_point_convertible_for_tuple!(3; A 0, B 1, C 2);
+impl_point_convertible_for_tuple!(4; A 0, B 1, C 2, D 3);
+impl_point_convertible_for_tuple!(5; A 0, B 1, C 2, D 3, E 4);
+impl_point_convertible_for_tuple!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+impl_point_convertible_for_tuple!(7; A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_point_convertible_for_tuple!(8; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
@@ -1,12 +1,17 @@
 //! Commonly used types and traits for predefined and custom point conversions.
 pub use crate::{
-    FieldDatatype, FromBytes, GetFieldDatatype, LayoutDescription, LayoutField, MsgConversionError,
-    PointCloud2Msg, PointConvertible, PointDataBuffer, RPCL2Point,
+    convert_cloud, Axis, ColorDecode, CopyPointConvertible, DecodePath, FieldDatatype,
+    FieldTransform, FromBytes, FromFieldValue, GetFieldDatatype, LayoutDescription, LayoutField,
+    MatchKind, MsgConversionError, PointCloud2Msg, PointCloud2Ref, PointCloud2Writer,
+    PointConvertible, PointData, PointDataBuffer, RPCL2Point,
 };
 
 pub use crate::points::*;
 pub use crate::ros::*;
 
+#[cfg(feature = "smallvec")]
+pub use crate::DynamicPoint;
+
 #[cfg(feature = "rayon")]
 pub use rayon::prelude::*;
 
@@ -22,7 +22,21 @@
 //!     }
 //! }
 //! ```
+//!
+//! The integrations for r2r (`r2r_msg`) and rosrust (`rosrust_msg`) are built into this crate with these `From` implementations,
+//! so a message from these crates can be converted with `.into()` directly when the feature is enabled.
+//! ```ignore
+//! // ros_pointcloud2 = { version = "*", features = ["r2r_msg"] }
+//! let msg: r2r::sensor_msgs::msg::PointCloud2 = ...; // inside the callback
+//! let converted: ros_pointcloud2::PointCloud2Msg = msg.into();
+//! let back: r2r::sensor_msgs::msg::PointCloud2 = converted.into();
+//! ```
+//!
+//! ROS1 messages generated by roslibrust live in the using crate, so their conversions are generated with
+//! [`impl_pointcloud2_for_roslibrust_ros1!`](crate::impl_pointcloud2_for_roslibrust_ros1) for the module path of the generated messages.
+//! The same applies to the ROS2 structs used with ros2-client, see [`impl_pointcloud2_for_ros2_client_native!`](crate::impl_pointcloud2_for_ros2_client_native).
 
+use alloc::borrow::Cow;
 use alloc::string::String;
 
 /// [Time](https://docs.ros2.org/latest/api/builtin_interfaces/msg/Time.html) representation for ROS messages.
@@ -42,12 +56,83 @@ impl From<rosrust::Time> for TimeMsg {
     }
 }
 
+#[cfg(feature = "r2r_msg")]
+impl From<r2r::builtin_interfaces::msg::Time> for TimeMsg {
+    fn from(time: r2r::builtin_interfaces::msg::Time) -> Self {
+        Self {
+            sec: time.sec,
+            nanosec: time.nanosec,
+        }
+    }
+}
+
+#[cfg(feature = "r2r_msg")]
+impl From<TimeMsg> for r2r::builtin_interfaces::msg::Time {
+    fn from(time: TimeMsg) -> Self {
+        Self {
+            sec: time.sec,
+            nanosec: time.nanosec,
+        }
+    }
+}
+
 /// Represents the [header of a ROS message](https://docs.ros2.org/latest/api/std_msgs/msg/Header.html).
+///
+/// The `frame_id` is a copy-on-write string, so forwarding a static or reused frame id does not allocate.
+///
+/// The `seq` field only exists in ROS1 headers. ROS2 integrations set it to `0` when converting into
+/// a [`PointCloud2Msg`](crate::PointCloud2Msg) and drop it when converting back, so it only carries meaning for ROS1 messages.
 #[derive(Clone, Debug, Default)]
 pub struct HeaderMsg {
     pub seq: u32,
     pub stamp: TimeMsg,
-    pub frame_id: String,
+    pub frame_id: Cow<'static, str>,
+}
+
+#[cfg(feature = "r2r_msg")]
+impl From<r2r::std_msgs::msg::Header> for HeaderMsg {
+    fn from(header: r2r::std_msgs::msg::Header) -> Self {
+        Self {
+            seq: 0, // ROS2 headers have no sequence number.
+            stamp: header.stamp.into(),
+            frame_id: header.frame_id.into(),
+        }
+    }
+}
+
+#[cfg(feature = "r2r_msg")]
+impl From<HeaderMsg> for r2r::std_msgs::msg::Header {
+    fn from(header: HeaderMsg) -> Self {
+        // The ROS1 sequence number is dropped since ROS2 headers do not have it.
+        Self {
+            stamp: header.stamp.into(),
+            frame_id: header.frame_id.into_owned(),
+        }
+    }
+}
+
+impl HeaderMsg {
+    /// Reset the ROS1 sequence number to `0`, which is the value ROS2 integrations use.
+    ///
+    /// This is useful when comparing headers that went through different ROS versions.
+    ///
+    /// # Example
+    /// ```
+    /// use ros_pointcloud2::ros::HeaderMsg;
+    ///
+    /// let header = HeaderMsg {
+    ///     seq: 42,
+    ///     frame_id: "map".into(),
+    ///     ..Default::default()
+    /// };
+    /// let header = header.without_seq();
+    /// assert_eq!(header.seq, 0);
+    /// assert_eq!(header.frame_id, "map");
+    /// ```
+    #[must_use]
+    pub fn without_seq(self) -> Self {
+        Self { seq: 0, ..self }
+    }
 }
 
 /// Describing a point encoded in the byte buffer of a PointCloud2 message. See the [official message description](https://docs.ros2.org/latest/api/sensor_msgs/msg/PointField.html) for more information.
@@ -74,14 +159,7 @@ impl Default for PointFieldMsg {
 impl From<r2r::sensor_msgs::msg::PointCloud2> for crate::PointCloud2Msg {
     fn from(msg: r2r::sensor_msgs::msg::PointCloud2) -> Self {
         Self {
-            header: HeaderMsg {
-                seq: 0,
-                stamp: TimeMsg {
-                    sec: msg.header.stamp.sec,
-                    nanosec: msg.header.stamp.nanosec,
-                },
-                frame_id: msg.header.frame_id,
-            },
+            header: msg.header.into(),
             dimensions: crate::CloudDimensions {
                 width: msg.width,
                 height: msg.height,
@@ -96,19 +174,12 @@ impl From<r2r::sensor_msgs::msg::PointCloud2> for crate::PointCloud2Msg {
                     count: field.count,
                 })
                 .collect(),
-            endian: if msg.is_bigendian {
-                crate::Endian::Big
-            } else {
-                crate::Endian::Little
-            },
+            endian: crate::Endian::from_ros_bool(msg.is_bigendian),
             point_step: msg.point_step,
             row_step: msg.row_step,
             data: msg.data,
-            dense: if msg.is_dense {
-                crate::Denseness::Dense
-            } else {
-                crate::Denseness::Sparse
-            },
+            dense: crate::Denseness::from_ros_bool(msg.is_dense),
+            field_transforms: Vec::new(),
         }
     }
 }
@@ -117,13 +188,7 @@ impl From<r2r::sensor_msgs::msg::PointCloud2> for crate::PointCloud2Msg {
 impl From<crate::PointCloud2Msg> for r2r::sensor_msgs::msg::PointCloud2 {
     fn from(msg: crate::PointCloud2Msg) -> Self {
         r2r::sensor_msgs::msg::PointCloud2 {
-            header: r2r::std_msgs::msg::Header {
-                stamp: r2r::builtin_interfaces::msg::Time {
-                    sec: msg.header.stamp.sec,
-                    nanosec: msg.header.stamp.nanosec,
-                },
-                frame_id: msg.header.frame_id,
-            },
+            header: msg.header.into(),
             height: msg.dimensions.height,
             width: msg.dimensions.width,
             fields: msg
@@ -136,17 +201,11 @@ impl From<crate::PointCloud2Msg> for r2r::sensor_msgs::msg::PointCloud2 {
                     count: field.count,
                 })
                 .collect(),
-            is_bigendian: match msg.endian {
-                crate::Endian::Big => true,
-                crate::Endian::Little => false,
-            },
+            is_bigendian: msg.endian.to_ros_bool(),
             point_step: msg.point_step,
             row_step: msg.row_step,
             data: msg.data,
-            is_dense: match msg.dense {
-                crate::Denseness::Dense => true,
-                crate::Denseness::Sparse => false,
-            },
+            is_dense: msg.dense.to_ros_bool(),
         }
     }
 }
@@ -161,7 +220,7 @@ impl From<rosrust_msg::sensor_msgs::PointCloud2> for crate::PointCloud2Msg {
                     sec: msg.header.stamp.sec as i32,
                     nanosec: msg.header.stamp.nsec,
                 },
-                frame_id: msg.header.frame_id,
+                frame_id: msg.header.frame_id.into(),
             },
             dimensions: crate::CloudDimensions {
                 width: msg.width,
@@ -177,19 +236,12 @@ impl From<rosrust_msg::sensor_msgs::PointCloud2> for crate::PointCloud2Msg {
                     count: field.count,
                 })
                 .collect(),
-            endian: if msg.is_bigendian {
-                crate::Endian::Big
-            } else {
-                crate::Endian::Little
-            },
+            endian: crate::Endian::from_ros_bool(msg.is_bigendian),
             point_step: msg.point_step,
             row_step: msg.row_step,
             data: msg.data,
-            dense: if msg.is_dense {
-                crate::Denseness::Dense
-            } else {
-                crate::Denseness::Sparse
-            },
+            dense: crate::Denseness::from_ros_bool(msg.is_dense),
+            field_transforms: Vec::new(),
         }
     }
 }
@@ -204,7 +256,7 @@ impl From<crate::PointCloud2Msg> for rosrust_msg::sensor_msgs::PointCloud2 {
                     sec: msg.header.stamp.sec as u32,
                     nsec: msg.header.stamp.nanosec,
                 },
-                frame_id: msg.header.frame_id,
+                frame_id: msg.header.frame_id.into_owned(),
             },
             height: msg.dimensions.height,
             width: msg.dimensions.width,
@@ -218,19 +270,187 @@ impl From<crate::PointCloud2Msg> for rosrust_msg::sensor_msgs::PointCloud2 {
                     count: field.count,
                 })
                 .collect(),
-            is_bigendian: if msg.endian == crate::Endian::Big {
-                true
-            } else {
-                false
-            },
+            is_bigendian: msg.endian.to_ros_bool(),
             point_step: msg.point_step,
             row_step: msg.row_step,
             data: msg.data,
-            is_dense: if msg.dense == crate::Denseness::Dense {
-                true
-            } else {
-                false
-            },
+            is_dense: msg.dense.to_ros_bool(),
         }
     }
 }
+
+/// Implement the conversions between [`PointCloud2Msg`](crate::PointCloud2Msg) and a ROS1 `PointCloud2` generated by roslibrust.
+///
+/// roslibrust generates the messages into a module of the using crate, so the conversions can not be provided by this crate directly.
+/// Pass the module path that contains the generated `sensor_msgs` and `std_msgs` modules.
+/// The header stamp uses the `secs` and `nsecs` fields of the roslibrust ROS1 time type.
+///
+/// # Example
+/// ```ignore
+/// roslibrust_codegen_macro::find_and_generate_ros_messages!("assets/ros1_common_interfaces");
+/// ros_pointcloud2::impl_pointcloud2_for_roslibrust_ros1!(crate);
+///
+/// let msg: sensor_msgs::PointCloud2 = ...; // inside the callback
+/// let converted: ros_pointcloud2::PointCloud2Msg = msg.into();
+/// let back: sensor_msgs::PointCloud2 = converted.into();
+/// ```
+#[macro_export]
+macro_rules! impl_pointcloud2_for_roslibrust_ros1 {
+    ($($msgs:ident)::+) => {
+        impl From<$($msgs)::+::sensor_msgs::PointCloud2> for $crate::PointCloud2Msg {
+            fn from(msg: $($msgs)::+::sensor_msgs::PointCloud2) -> Self {
+                Self {
+                    header: $crate::ros::HeaderMsg {
+                        seq: msg.header.seq,
+                        stamp: $crate::ros::TimeMsg {
+                            sec: msg.header.stamp.secs as i32,
+                            nanosec: msg.header.stamp.nsecs,
+                        },
+                        frame_id: msg.header.frame_id.into(),
+                    },
+                    dimensions: $crate::CloudDimensions {
+                        width: msg.width,
+                        height: msg.height,
+                    },
+                    fields: msg
+                        .fields
+                        .into_iter()
+                        .map(|field| $crate::ros::PointFieldMsg {
+                            name: field.name,
+                            offset: field.offset,
+                            datatype: field.datatype,
+                            count: field.count,
+                        })
+                        .collect(),
+                    endian: $crate::Endian::from_ros_bool(msg.is_bigendian),
+                    point_step: msg.point_step,
+                    row_step: msg.row_step,
+                    data: msg.data,
+                    dense: $crate::Denseness::from_ros_bool(msg.is_dense),
+                    field_transforms: ::core::default::Default::default(),
+                }
+            }
+        }
+
+        impl From<$crate::PointCloud2Msg> for $($msgs)::+::sensor_msgs::PointCloud2 {
+            fn from(msg: $crate::PointCloud2Msg) -> Self {
+                // The time type lives in the roslibrust crates, so it is filled in place.
+                let mut header: $($msgs)::+::std_msgs::Header = Default::default();
+                header.seq = msg.header.seq;
+                header.stamp.secs = msg.header.stamp.sec as u32;
+                header.stamp.nsecs = msg.header.stamp.nanosec;
+                header.frame_id = msg.header.frame_id.into_owned();
+
+                Self {
+                    header,
+                    height: msg.dimensions.height,
+                    width: msg.dimensions.width,
+                    fields: msg
+                        .fields
+                        .into_iter()
+                        .map(|field| $($msgs)::+::sensor_msgs::PointField {
+                            name: field.name,
+                            offset: field.offset,
+                            datatype: field.datatype,
+                            count: field.count,
+                        })
+                        .collect(),
+                    is_bigendian: msg.endian.to_ros_bool(),
+                    point_step: msg.point_step,
+                    row_step: msg.row_step,
+                    data: msg.data,
+                    is_dense: msg.dense.to_ros_bool(),
+                }
+            }
+        }
+    };
+}
+
+/// Implement the conversions between [`PointCloud2Msg`](crate::PointCloud2Msg) and a ROS2 `PointCloud2` struct used with ros2-client.
+///
+/// ros2-client does not ship message types, so the CDR-deserialized structs are defined in the using crate.
+/// Pass the module path that contains the `sensor_msgs` and `std_msgs` modules with the ROS2 message layout.
+/// The header stamp uses the `sec` and `nanosec` fields of the ROS2 time type and the ROS1-only `seq` is dropped.
+///
+/// # Example
+/// ```ignore
+/// mod msgs {
+///     pub mod std_msgs { /* Header { stamp: Time { sec, nanosec }, frame_id } */ }
+///     pub mod sensor_msgs { /* PointField, PointCloud2 */ }
+/// }
+/// ros_pointcloud2::impl_pointcloud2_for_ros2_client_native!(crate::msgs);
+///
+/// let msg: msgs::sensor_msgs::PointCloud2 = ...; // from a ros2-client subscription
+/// let converted: ros_pointcloud2::PointCloud2Msg = msg.into();
+/// let back: msgs::sensor_msgs::PointCloud2 = converted.into();
+/// ```
+#[macro_export]
+macro_rules! impl_pointcloud2_for_ros2_client_native {
+    ($($msgs:ident)::+) => {
+        impl From<$($msgs)::+::sensor_msgs::PointCloud2> for $crate::PointCloud2Msg {
+            fn from(msg: $($msgs)::+::sensor_msgs::PointCloud2) -> Self {
+                Self {
+                    header: $crate::ros::HeaderMsg {
+                        seq: 0,
+                        stamp: $crate::ros::TimeMsg {
+                            sec: msg.header.stamp.sec,
+                            nanosec: msg.header.stamp.nanosec,
+                        },
+                        frame_id: msg.header.frame_id.into(),
+                    },
+                    dimensions: $crate::CloudDimensions {
+                        width: msg.width,
+                        height: msg.height,
+                    },
+                    fields: msg
+                        .fields
+                        .into_iter()
+                        .map(|field| $crate::ros::PointFieldMsg {
+                            name: field.name,
+                            offset: field.offset,
+                            datatype: field.datatype,
+                            count: field.count,
+                        })
+                        .collect(),
+                    endian: $crate::Endian::from_ros_bool(msg.is_bigendian),
+                    point_step: msg.point_step,
+                    row_step: msg.row_step,
+                    data: msg.data,
+                    dense: $crate::Denseness::from_ros_bool(msg.is_dense),
+                    field_transforms: ::core::default::Default::default(),
+                }
+            }
+        }
+
+        impl From<$crate::PointCloud2Msg> for $($msgs)::+::sensor_msgs::PointCloud2 {
+            fn from(msg: $crate::PointCloud2Msg) -> Self {
+                // The time type is defined by the using crate, so it is filled in place.
+                let mut header: $($msgs)::+::std_msgs::Header = Default::default();
+                header.stamp.sec = msg.header.stamp.sec;
+                header.stamp.nanosec = msg.header.stamp.nanosec;
+                header.frame_id = msg.header.frame_id.into_owned();
+
+                Self {
+                    header,
+                    height: msg.dimensions.height,
+                    width: msg.dimensions.width,
+                    fields: msg
+                        .fields
+                        .into_iter()
+                        .map(|field| $($msgs)::+::sensor_msgs::PointField {
+                            name: field.name,
+                            offset: field.offset,
+                            datatype: field.datatype,
+                            count: field.count,
+                        })
+                        .collect(),
+                    is_bigendian: msg.endian.to_ros_bool(),
+                    point_step: msg.point_step,
+                    row_step: msg.row_step,
+                    data: msg.data,
+                    is_dense: msg.dense.to_ros_bool(),
+                }
+            }
+        }
+    };
+}
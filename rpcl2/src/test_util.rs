@@ -0,0 +1,110 @@
+//! Helpers for testing [`PointConvertible`] implementations. Requires the `test-util` feature.
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{
+    ordered_layout_fields, CopyPointConvertible, MsgConversionError, PointCloud2Msg,
+    PointConvertible, RPCL2Point,
+};
+
+/// Write the points into a [`PointCloud2Msg`], decode them again and check that every point is unchanged.
+///
+/// This catches wrong layouts, offsets or field names of manual [`PointConvertible`] implementations,
+/// which otherwise only show as corrupted values in a running system.
+/// For `Copy` points, [`assert_roundtrip_copy`] also checks the direct copy conversions.
+///
+/// # Example
+/// ```
+/// use ros_pointcloud2::prelude::*;
+/// use ros_pointcloud2::test_util::assert_roundtrip;
+///
+/// let points = vec![PointXYZI::new(1.0, 2.0, 3.0, 0.5), PointXYZI::new(4.0, 5.0, 6.0, 1.5)];
+/// assert_roundtrip(&points).unwrap();
+/// ```
+///
+/// # Errors
+/// Returns [`MsgConversionError::RoundtripMismatch`] with the index of the first point that differs after decoding
+/// and the names of its differing values, where array elements are named like `normal[2]`. The names are empty if
+/// the values are bitwise equal but the points are not, e.g. for NaN.
+/// Returns [`MsgConversionError::DataLengthMismatch`] if the number of decoded points differs and the errors of
+/// [`try_from_iter`](PointCloud2Msg::try_from_iter) and [`try_into_iter`](PointCloud2Msg::try_into_iter) otherwise.
+pub fn assert_roundtrip<const N: usize, C>(points: &[C]) -> Result<(), MsgConversionError>
+where
+    C: PointConvertible<N> + PartialEq + Clone,
+{
+    let msg = PointCloud2Msg::try_from_iter(points.iter().cloned())?;
+    let decoded: Vec<C> = msg.try_into_iter()?.collect();
+    compare(points, &decoded)
+}
+
+/// Like [`assert_roundtrip`], but also crosses the direct copy conversions of `Copy` points with the iterator conversions.
+///
+/// [`try_from_vec`](PointCloud2Msg::try_from_vec) and [`try_into_vec`](PointCloud2Msg::try_into_vec) copy the memory of
+/// the points, while the iterators use the offsets from the layout. Both only agree if the layout, including its padding,
+/// describes the actual memory representation of the type.
+///
+/// # Example
+/// ```
+/// use ros_pointcloud2::prelude::*;
+/// use ros_pointcloud2::test_util::assert_roundtrip_copy;
+///
+/// assert_roundtrip_copy(&[PointXYZI::new(1.0, 2.0, 3.0, 0.5)]).unwrap();
+/// ```
+///
+/// # Errors
+/// Returns the errors of [`assert_roundtrip`] for each of the conversion paths.
+pub fn assert_roundtrip_copy<const N: usize, C>(points: &[C]) -> Result<(), MsgConversionError>
+where
+    C: CopyPointConvertible<N> + PartialEq,
+{
+    assert_roundtrip(points)?;
+
+    let msg = PointCloud2Msg::try_from_vec(points.to_vec())?;
+    let decoded: Vec<C> = msg.try_into_iter()?.collect();
+    compare(points, &decoded)?;
+
+    let msg = PointCloud2Msg::try_from_iter(points.iter().copied())?;
+    let decoded: Vec<C> = msg.try_into_vec()?;
+    compare(points, &decoded)
+}
+
+fn compare<const N: usize, C>(points: &[C], decoded: &[C]) -> Result<(), MsgConversionError>
+where
+    C: PointConvertible<N> + PartialEq + Clone,
+{
+    if decoded.len() != points.len() {
+        return Err(MsgConversionError::DataLengthMismatch);
+    }
+
+    let Some(index) = points
+        .iter()
+        .zip(decoded.iter())
+        .position(|(original, decoded)| original != decoded)
+    else {
+        return Ok(());
+    };
+
+    let original: RPCL2Point<N> = points[index].clone().into();
+    let decoded: RPCL2Point<N> = decoded[index].clone().into();
+    let names = ordered_layout_fields::<N, C>()
+        .into_iter()
+        .flat_map(|(name, count)| {
+            (0..count).map(move |element| {
+                if count > 1 {
+                    format!("{name}[{element}]")
+                } else {
+                    String::from(name.as_ref())
+                }
+            })
+        });
+    let fields = names
+        .zip(original.fields.iter().zip(decoded.fields.iter()))
+        .filter(|(_, (original, decoded))| {
+            let size = original.datatype.size();
+            original.datatype != decoded.datatype || original.bytes[..size] != decoded.bytes[..size]
+        })
+        .map(|(name, _)| name)
+        .collect();
+
+    Err(MsgConversionError::RoundtripMismatch { index, fields })
+}
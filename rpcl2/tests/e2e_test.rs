@@ -423,6 +423,40 @@ fn converterxyzinormal() {
     );
 }
 
+#[test]
+fn converterxyzlnormal() {
+    let cloud = vec![
+        PointXYZLNormal::new(0.0, 1.0, 5.0, 0, 0.0, 0.0, 1.0, 0.0),
+        PointXYZLNormal::new(1.0, 1.5, 5.0, 1, 1.0, 0.0, 0.0, 0.1),
+        PointXYZLNormal::new(1.3, 1.6, 5.7, u32::MAX, 0.0, 1.0, 0.0, 0.2),
+    ];
+    convert_from_into!(PointXYZLNormal, cloud.clone());
+
+    let msg = PointCloud2Msg::try_from_vec(cloud.clone()).unwrap();
+    assert_eq!(msg.point_step, 32);
+    assert_eq!(
+        msg.layout_matches::<8, PointXYZLNormal>().unwrap(),
+        MatchKind::Exact
+    );
+
+    let normals = msg.cast::<8, PointXYZLNormal, 4, PointNormal>().unwrap();
+    assert_eq!(normals.point_step, 16);
+    let normals: Vec<PointNormal> = normals.try_into_vec().unwrap();
+    assert_eq!(normals[2], PointNormal::new(0.0, 1.0, 0.0, 0.2));
+}
+
+#[test]
+fn converternormal() {
+    convert_from_into!(
+        PointNormal,
+        vec![
+            PointNormal::new(0.0, 0.0, 1.0, 0.0),
+            PointNormal::new(1.0, 0.0, 0.0, 0.5),
+            PointNormal::new(f32::MAX, f32::MIN, 0.0, f32::MAX),
+        ]
+    );
+}
+
 #[test]
 fn converterxyzrgbnormal() {
     convert_from_into!(
@@ -649,3 +683,3985 @@ fn write_less_than_available() {
 
     convert_from_into_in_out_cloud!(write_cloud, CustomPoint, read_cloud, CustomPoint);
 }
+
+#[test]
+fn non_copy_point_iter() {
+    #[derive(Debug, PartialEq, Clone, Default)]
+    #[repr(C, align(4))]
+    struct TaggedPoint {
+        x: f32,
+        y: f32,
+        z: f32,
+        tag: String,
+    }
+
+    impl From<RPCL2Point<3>> for TaggedPoint {
+        fn from(point: RPCL2Point<3>) -> Self {
+            Self {
+                x: point[0].get(),
+                y: point[1].get(),
+                z: point[2].get(),
+                tag: String::new(),
+            }
+        }
+    }
+
+    impl From<TaggedPoint> for RPCL2Point<3> {
+        fn from(point: TaggedPoint) -> Self {
+            [point.x.into(), point.y.into(), point.z.into()].into()
+        }
+    }
+
+    unsafe impl PointConvertible<3> for TaggedPoint {
+        fn layout() -> LayoutDescription {
+            LayoutDescription::new(&[
+                LayoutField::new("x", "f32", 4),
+                LayoutField::new("y", "f32", 4),
+                LayoutField::new("z", "f32", 4),
+            ])
+        }
+    }
+
+    let write_cloud = vec![
+        TaggedPoint {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+            tag: "first".into(),
+        },
+        TaggedPoint {
+            x: 4.0,
+            y: 5.0,
+            z: 6.0,
+            tag: "second".into(),
+        },
+    ];
+
+    let read_cloud = [
+        TaggedPoint {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+            tag: String::new(),
+        },
+        TaggedPoint {
+            x: 4.0,
+            y: 5.0,
+            z: 6.0,
+            tag: String::new(),
+        },
+    ];
+
+    convert_from_into_in_out_cloud!(write_cloud, TaggedPoint, read_cloud, TaggedPoint);
+}
+
+#[test]
+fn occupancy_grid() {
+    let cloud = vec![
+        PointXYZ::new(0.1, 0.1, 0.1),
+        PointXYZ::new(0.4, 0.2, 0.3),
+        PointXYZ::new(1.2, 1.7, 0.9),
+        PointXYZ::new(2.0, 0.0, 0.0), // max bound is exclusive
+        PointXYZ::new(-0.1, 0.0, 0.0),
+        PointXYZ::new(f32::NAN, 0.5, 0.5),
+    ];
+    let msg = PointCloud2Msg::try_from_vec(cloud).unwrap();
+
+    let grid = msg
+        .occupancy_grid([0.5, 0.5, 1.0], ([0.0; 3], [2.0, 2.0, 1.0]))
+        .unwrap();
+    assert_eq!(grid.dimensions, [4, 4, 1]);
+    assert_eq!(grid.counts.len(), 16);
+    assert_eq!(grid.count([0, 0, 0]), 2);
+    assert_eq!(grid.count([2, 3, 0]), 1);
+    assert_eq!(grid.counts.iter().map(|&c| c as usize).sum::<usize>(), 3);
+    assert_eq!(grid.occupancy().iter().filter(|&&o| o).count(), 2);
+    assert!(!grid.is_occupied([3, 3, 0]));
+    assert_eq!(grid.count([4, 0, 0]), 0);
+
+    assert!(matches!(
+        msg.occupancy_grid([0.0, 1.0, 1.0], ([0.0; 3], [1.0; 3])),
+        Err(MsgConversionError::InvalidArgument)
+    ));
+    assert!(matches!(
+        msg.occupancy_grid([1.0; 3], ([1.0; 3], [1.0; 3])),
+        Err(MsgConversionError::InvalidArgument)
+    ));
+
+    let msg = PointCloud2Msg::try_from_iter(vec![PointXYZL::new(0.0, 0.0, 0.0, 1)]).unwrap();
+    assert!(msg.occupancy_grid([1.0; 3], ([0.0; 3], [1.0; 3])).is_ok());
+}
+
+#[test]
+fn sub_view() {
+    let cloud = vec![
+        PointXYZI::new(0.0, 1.0, 5.0, 0.0),
+        PointXYZI::new(1.0, 1.5, 5.0, 1.0),
+        PointXYZI::new(1.3, 1.6, 5.7, 2.0),
+        PointXYZI::new(2.0, 2.5, 6.0, 3.0),
+    ];
+    let msg = PointCloud2Msg::try_from_vec(cloud.clone()).unwrap();
+
+    let view = msg.sub_view(1, 3).unwrap();
+    assert_eq!(view.len(), 3);
+    assert_eq!(view.data.len(), 3 * msg.point_step as usize);
+    assert_eq!(view.row_step, 3 * msg.point_step);
+    assert_eq!(
+        view.point_bytes(0).unwrap(),
+        &msg.data[msg.point_step as usize..2 * msg.point_step as usize]
+    );
+    assert!(view.point_bytes(3).is_none());
+
+    let inner = view.sub_view(1, 2).unwrap();
+    let points: Vec<PointXYZI> = inner.to_msg().try_into_vec().unwrap();
+    assert_eq!(points, cloud[2..4]);
+
+    assert!(msg.sub_view(4, 0).unwrap().is_empty());
+    assert!(matches!(
+        msg.sub_view(3, 2),
+        Err(MsgConversionError::ExhaustedSource)
+    ));
+    assert!(matches!(
+        view.sub_view(0, 4),
+        Err(MsgConversionError::ExhaustedSource)
+    ));
+}
+
+#[test]
+fn repack_tight() {
+    let cloud = vec![
+        PointXYZRGBA::new(0.0, 1.0, 5.0, 1, 2, 3, 4),
+        PointXYZRGBA::new(1.0, 1.5, 5.0, 5, 6, 7, 8),
+    ];
+    let msg = PointCloud2Msg::try_from_vec(cloud.clone()).unwrap();
+    assert!(msg.padding_bytes_per_point().unwrap() > 0);
+
+    let tight = msg.repack_tight().unwrap();
+    assert_eq!(tight.padding_bytes_per_point().unwrap(), 0);
+    assert_eq!(tight.point_step, 17);
+    assert_eq!(tight.row_step, 34);
+    assert_eq!(tight.data.len(), 34);
+
+    let back: Vec<PointXYZRGBA> = tight.try_into_iter().unwrap().collect();
+    assert_eq!(back, cloud);
+
+    // Field descriptions of received messages must not overflow.
+    let mut huge_count = msg.clone();
+    huge_count.fields[0].count = u32::MAX;
+    assert!(matches!(
+        huge_count.repack_tight(),
+        Err(MsgConversionError::NumberConversion)
+    ));
+    let mut huge_offset = msg.clone();
+    huge_offset.fields[0].offset = u32::MAX - 1;
+    assert!(matches!(
+        huge_offset.repack_tight(),
+        Err(MsgConversionError::NumberConversion)
+    ));
+}
+
+#[test]
+fn random_subsample() {
+    let cloud = (0..50)
+        .map(|i| PointXYZI::new(i as f32, 0.0, 0.0, i as f32 * 2.0))
+        .collect::<Vec<_>>();
+    let msg = PointCloud2Msg::try_from_vec(cloud).unwrap();
+
+    let sampled = msg.random_subsample(8, 7).unwrap();
+    assert_eq!(sampled.dimensions.width, 8);
+    assert_eq!(sampled.dimensions.height, 1);
+    assert_eq!(sampled.data.len(), 8 * msg.point_step as usize);
+
+    let points: Vec<PointXYZI> = sampled.try_into_vec().unwrap();
+    assert!(points.windows(2).all(|w| w[0].x < w[1].x));
+    assert!(points.iter().all(|p| p.intensity == p.x * 2.0));
+
+    let again: Vec<PointXYZI> = msg.random_subsample(8, 7).unwrap().try_into_vec().unwrap();
+    assert_eq!(points, again);
+
+    let other: Vec<PointXYZI> = msg.random_subsample(8, 8).unwrap().try_into_vec().unwrap();
+    assert_ne!(points, other);
+
+    assert_eq!(msg.random_subsample(100, 7).unwrap().data, msg.data);
+    assert!(msg.random_subsample(0, 7).unwrap().data.is_empty());
+}
+
+#[test]
+fn expect_schema() {
+    let msg = PointCloud2Msg::try_from_vec(vec![PointXYZL::new(1.0, 2.0, 3.0, 4)]).unwrap();
+
+    assert!(msg
+        .expect_schema(&[
+            ("label", FieldDatatype::U32),
+            ("z", FieldDatatype::F32),
+            ("y", FieldDatatype::F32),
+            ("x", FieldDatatype::F32),
+        ])
+        .is_ok());
+
+    let err = msg
+        .expect_schema(&[
+            ("x", FieldDatatype::F32),
+            ("y", FieldDatatype::F64),
+            ("z", FieldDatatype::F32),
+            ("intensity", FieldDatatype::F32),
+        ])
+        .unwrap_err();
+    match err {
+        MsgConversionError::SchemaMismatch {
+            missing,
+            extra,
+            mismatched,
+        } => {
+            assert_eq!(missing, vec!["intensity".to_string()]);
+            assert_eq!(extra, vec!["label".to_string()]);
+            assert_eq!(mismatched, vec!["y".to_string()]);
+        }
+        _ => panic!("unexpected error {err:?}"),
+    }
+}
+
+#[test]
+fn field_iter() {
+    let cloud = vec![
+        PointXYZL::new(0.0, 1.0, 5.0, 3),
+        PointXYZL::new(1.0, 1.5, 5.0, 4),
+        PointXYZL::new(1.3, 1.6, 5.7, 5),
+    ];
+    let msg = PointCloud2Msg::try_from_vec(cloud).unwrap();
+
+    let labels: Vec<u32> = msg.field_iter("label").unwrap().collect();
+    assert_eq!(labels, vec![3, 4, 5]);
+    let zs: Vec<f32> = msg.field_iter("z").unwrap().collect();
+    assert_eq!(zs, vec![5.0, 5.0, 5.7]);
+
+    assert!(matches!(
+        msg.field_iter::<f32>("label"),
+        Err(MsgConversionError::InvalidFieldFormat)
+    ));
+    assert!(matches!(
+        msg.field_iter::<f32>("intensity"),
+        Err(MsgConversionError::FieldsNotFound(_))
+    ));
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn par_field_iter() {
+    let cloud = (0..1000)
+        .map(|i| PointXYZL::new(i as f32, 0.0, 0.0, i))
+        .collect::<Vec<_>>();
+    let msg = PointCloud2Msg::try_from_vec(cloud).unwrap();
+
+    let labels: Vec<u32> = msg.par_field_iter("label").unwrap().collect();
+    assert_eq!(labels, (0..1000).collect::<Vec<_>>());
+    let sum: u64 = msg
+        .par_field_iter::<u32>("label")
+        .unwrap()
+        .map(|l| l as u64)
+        .sum();
+    assert_eq!(sum, 499500);
+}
+
+#[test]
+fn iter_rejects_malformed_length() {
+    let cloud = vec![PointXYZ::new(0.0, 1.0, 5.0), PointXYZ::new(1.0, 1.5, 5.0)];
+    let msg = PointCloud2Msg::try_from_vec(cloud).unwrap();
+
+    let mut truncated = msg.clone();
+    truncated.data.truncate(msg.point_step as usize);
+    assert!(matches!(
+        truncated.try_into_iter::<3, PointXYZ>().map(|_| ()),
+        Err(MsgConversionError::DataLengthMismatch)
+    ));
+
+    let mut too_many_points = msg.clone();
+    too_many_points.dimensions.height = 2;
+    too_many_points.row_step = msg.point_step * 2;
+    assert!(matches!(
+        too_many_points.try_into_iter::<3, PointXYZ>().map(|_| ()),
+        Err(MsgConversionError::DataLengthMismatch)
+    ));
+
+    let mut organized = msg.clone();
+    organized.dimensions.width = 1;
+    organized.dimensions.height = 2;
+    organized.row_step = msg.point_step;
+    assert_eq!(
+        organized
+            .clone()
+            .try_into_iter::<3, PointXYZ>()
+            .unwrap()
+            .count(),
+        2
+    );
+    organized.row_step = msg.point_step * 2;
+    assert!(matches!(
+        organized.try_into_iter::<3, PointXYZ>().map(|_| ()),
+        Err(MsgConversionError::DataLengthMismatch)
+    ));
+}
+
+#[test]
+fn iter_empty_cloud() {
+    let msg = PointCloud2Msg::try_from_vec(Vec::<PointXYZ>::new()).unwrap();
+    let mut iter = msg.try_into_iter::<3, PointXYZ>().unwrap();
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn builder_point_alignment() {
+    let cloud = vec![
+        PointXYZRGBA::new(0.0, 1.0, 5.0, 1, 2, 3, 4),
+        PointXYZRGBA::new(1.0, 1.5, 5.0, 5, 6, 7, 8),
+    ];
+    let tight = PointCloud2Msg::try_from_vec(cloud.clone())
+        .unwrap()
+        .repack_tight()
+        .unwrap();
+    assert_eq!(tight.point_step, 17);
+
+    let aligned = ros_pointcloud2::PointCloud2MsgBuilder::new()
+        .fields(tight.fields.clone())
+        .point_step(tight.point_step)
+        .width(2)
+        .row_step(tight.row_step)
+        .data(tight.data.clone())
+        .with_point_alignment(16)
+        .build()
+        .unwrap();
+    assert_eq!(aligned.point_step, 32);
+    assert_eq!(aligned.row_step, 64);
+    assert_eq!(aligned.data.len(), 64);
+    assert!(aligned.data[17..32].iter().all(|&b| b == 0));
+
+    let back: Vec<PointXYZRGBA> = aligned.try_into_iter().unwrap().collect();
+    assert_eq!(back, cloud);
+
+    let invalid = ros_pointcloud2::PointCloud2MsgBuilder::new()
+        .fields(tight.fields)
+        .point_step(tight.point_step)
+        .width(2)
+        .data(tight.data)
+        .with_point_alignment(12)
+        .build();
+    assert!(matches!(invalid, Err(MsgConversionError::InvalidArgument)));
+}
+
+#[test]
+fn try_into_vec_exact() {
+    let cloud = vec![
+        PointXYZL::new(0.0, 1.0, 5.0, 3),
+        PointXYZL::new(1.0, 1.5, 5.0, 4),
+    ];
+    let msg = PointCloud2Msg::try_from_vec(cloud.clone()).unwrap();
+
+    let exact: Vec<PointXYZL> = msg.clone().try_into_vec_exact().unwrap();
+    assert_eq!(exact, cloud);
+
+    match msg.try_into_vec_exact::<3, PointXYZ>() {
+        Err(MsgConversionError::SchemaMismatch { extra, .. }) => {
+            assert_eq!(extra, vec!["label".to_string()]);
+        }
+        other => panic!("unexpected result {other:?}"),
+    }
+}
+
+#[test]
+fn convert_cloud_predefined() {
+    let cloud = vec![
+        PointXYZRGBL::new(0.0, 1.0, 5.0, 1, 2, 3, 7),
+        PointXYZRGBL::new(1.0, 1.5, 5.0, 4, 5, 6, 8),
+    ];
+    let mut msg = PointCloud2Msg::try_from_vec(cloud.clone()).unwrap();
+    msg.header.frame_id = "lidar".into();
+
+    let rgb_msg = convert_cloud::<5, PointXYZRGBL, 4, PointXYZRGB>(&msg, Into::into).unwrap();
+    assert_eq!(rgb_msg.header.frame_id, "lidar");
+    let rgb: Vec<PointXYZRGB> = rgb_msg.try_into_vec().unwrap();
+    assert_eq!(
+        rgb,
+        cloud
+            .iter()
+            .map(|&p| p.into())
+            .collect::<Vec<PointXYZRGB>>()
+    );
+    assert_eq!(rgb[1].r(), 4);
+
+    let shifted = convert_cloud::<5, PointXYZRGBL, 4, PointXYZL>(&msg, |p| {
+        let mut p: PointXYZL = p.into();
+        p.label += 1;
+        p
+    })
+    .unwrap();
+    let labels: Vec<u32> = shifted.field_iter("label").unwrap().collect();
+    assert_eq!(labels, vec![8, 9]);
+}
+
+#[test]
+fn read_rgb_stored_as_u32() {
+    let cloud = vec![
+        PointXYZRGB::new(0.0, 1.0, 5.0, 10, 20, 30),
+        PointXYZRGB::new(1.0, 1.5, 5.0, 40, 50, 60),
+    ];
+    let mut msg = PointCloud2Msg::try_from_vec(cloud.clone()).unwrap();
+    let rgb_field = msg.fields.iter_mut().find(|f| f.name == "rgb").unwrap();
+    rgb_field.datatype = 6; // u32
+
+    let colors: Vec<RGB> = msg.field_iter("rgb").unwrap().collect();
+    assert_eq!(colors[1], RGB::new(40, 50, 60));
+    assert!(msg
+        .expect_schema(&[
+            ("x", FieldDatatype::F32),
+            ("y", FieldDatatype::F32),
+            ("z", FieldDatatype::F32),
+            ("rgb", FieldDatatype::RGB),
+        ])
+        .is_ok());
+
+    let from_iter: Vec<PointXYZRGB> = msg.clone().try_into_iter().unwrap().collect();
+    assert_eq!(from_iter, cloud);
+    let from_vec: Vec<PointXYZRGB> = msg.try_into_vec().unwrap();
+    assert_eq!(from_vec, cloud);
+}
+
+#[test]
+fn named_iter() {
+    let cloud = vec![
+        PointXYZL::new(0.0, 1.0, 5.0, 7),
+        PointXYZL::new(1.0, 1.5, 5.0, 8),
+    ];
+    let msg = PointCloud2Msg::try_from_vec(cloud).unwrap();
+
+    let points: Vec<Vec<(&str, PointData)>> = msg.try_into_named_iter().unwrap().collect();
+    assert_eq!(points.len(), 2);
+
+    let names: Vec<&str> = points[1].iter().map(|(name, _)| *name).collect();
+    assert_eq!(names, vec!["x", "y", "z", "label"]);
+    assert_eq!(points[1][1].1.get::<f32>(), 1.5);
+    assert_eq!(points[1][3].1.datatype(), FieldDatatype::U32);
+    assert_eq!(points[1][3].1.get::<u32>(), 8);
+}
+
+#[test]
+#[cfg(feature = "nalgebra")]
+fn nalgebra_point3_vec() {
+    let cloud = vec![
+        nalgebra::Point3::new(0.0f32, 1.0, 5.0),
+        nalgebra::Point3::new(1.0, 1.5, 5.0),
+        nalgebra::Point3::new(1.3, 1.6, 5.7),
+    ];
+    let msg = PointCloud2Msg::try_from_vec(cloud.clone()).unwrap();
+    assert_eq!(msg.point_step, 12);
+
+    let as_xyz: Vec<PointXYZ> = msg.clone().try_into_vec().unwrap();
+    assert_eq!(as_xyz[2], PointXYZ::new(1.3, 1.6, 5.7));
+
+    let back: Vec<nalgebra::Point3<f32>> = msg.clone().try_into_vec().unwrap();
+    assert_eq!(back, cloud);
+    let back_iter: Vec<nalgebra::Point3<f32>> = msg.try_into_iter().unwrap().collect();
+    assert_eq!(back_iter, cloud);
+}
+
+#[test]
+fn fields_not_found_names() {
+    let empty = ros_pointcloud2::PointCloud2MsgBuilder::new().build();
+    assert!(matches!(
+        empty,
+        Err(MsgConversionError::FieldsNotFound(names)) if names.is_empty()
+    ));
+
+    let msg = PointCloud2Msg::try_from_vec(vec![PointXYZ::new(0.0, 1.0, 5.0)]).unwrap();
+    match msg.try_into_iter::<4, PointXYZI>() {
+        Err(MsgConversionError::FieldsNotFound(names)) => {
+            assert_eq!(names, vec!["intensity"]);
+            assert!(matches!(names[0], std::borrow::Cow::Borrowed(_)));
+        }
+        _ => panic!("expected missing intensity field"),
+    }
+}
+
+#[test]
+fn sort_by_field() {
+    let cloud = vec![
+        PointXYZL::new(0.0, 1.0, 5.0, 1),
+        PointXYZL::new(1.0, 1.5, 2.0, 2),
+        PointXYZL::new(1.3, 1.6, 5.0, 3),
+        PointXYZL::new(2.0, 0.0, f32::NAN, 4),
+        PointXYZL::new(2.0, 0.0, -1.0, 5),
+    ];
+    let msg = PointCloud2Msg::try_from_vec(cloud).unwrap();
+
+    let ascending = msg.sort_by_field_f32("z", true).unwrap();
+    assert_eq!(ascending.dimensions.width, 5);
+    let labels: Vec<u32> = ascending.field_iter("label").unwrap().collect();
+    assert_eq!(labels, vec![5, 2, 1, 3, 4]);
+
+    let descending = msg.sort_by_field_f32("z", false).unwrap();
+    let labels: Vec<u32> = descending.field_iter("label").unwrap().collect();
+    assert_eq!(labels, vec![4, 1, 3, 2, 5]);
+
+    assert!(msg.sort_by_field_f32("label", true).is_err());
+    assert!(msg.sort_by_field_f32("intensity", true).is_err());
+}
+
+#[test]
+fn layout_matches() {
+    let cloud = vec![PointXYZINormal::new(1.0, 2.0, 3.0, 0.5, 0.0, 0.0, 1.0)];
+    let msg = PointCloud2Msg::try_from_vec(cloud).unwrap();
+
+    assert_eq!(
+        msg.layout_matches::<7, PointXYZINormal>().unwrap(),
+        MatchKind::Exact
+    );
+    assert_eq!(
+        msg.layout_matches::<3, PointXYZ>().unwrap(),
+        MatchKind::Strided
+    );
+    assert_eq!(
+        msg.layout_matches::<4, PointXYZL>().unwrap(),
+        MatchKind::RequiresConversion
+    );
+
+    let mut swapped = msg.clone();
+    swapped.endian = match swapped.endian {
+        ros_pointcloud2::Endian::Big => ros_pointcloud2::Endian::Little,
+        ros_pointcloud2::Endian::Little => ros_pointcloud2::Endian::Big,
+    };
+    assert_eq!(
+        swapped.layout_matches::<7, PointXYZINormal>().unwrap(),
+        MatchKind::RequiresConversion
+    );
+}
+
+#[test]
+fn f16_bits() {
+    use ros_pointcloud2::points::{f16_bits_to_f32, f32_to_f16_bits};
+
+    assert_eq!(f32_to_f16_bits(0.0), 0x0000);
+    assert_eq!(f32_to_f16_bits(-0.0), 0x8000);
+    assert_eq!(f32_to_f16_bits(1.0), 0x3c00);
+    assert_eq!(f32_to_f16_bits(-2.0), 0xc000);
+    assert_eq!(f32_to_f16_bits(65504.0), 0x7bff);
+    assert_eq!(f32_to_f16_bits(1.0e6), 0x7c00);
+    assert_eq!(f32_to_f16_bits(f32::NEG_INFINITY), 0xfc00);
+    assert!(f16_bits_to_f32(f32_to_f16_bits(f32::NAN)).is_nan());
+    // smallest subnormal and rounding to even
+    assert_eq!(f32_to_f16_bits(5.960_464_5e-8), 0x0001);
+    assert_eq!(f32_to_f16_bits(1.0 + 1.0 / 4096.0), 0x3c00);
+    assert_eq!(f32_to_f16_bits(1.0 + 2.0 / 4096.0), 0x3c00);
+    assert_eq!(f32_to_f16_bits(1.0 + 3.0 / 4096.0), 0x3c01);
+    assert_eq!(f32_to_f16_bits(1.0 + 6.0 / 4096.0), 0x3c02);
+
+    for bits in (0..0x7c00u16).step_by(7) {
+        assert_eq!(f32_to_f16_bits(f16_bits_to_f32(bits)), bits);
+        assert_eq!(f32_to_f16_bits(-f16_bits_to_f32(bits)), bits | 0x8000);
+    }
+}
+
+#[test]
+#[cfg(feature = "half")]
+fn f16_bits_match_half() {
+    use ros_pointcloud2::points::{
+        f16_bits_to_f32, f32_to_f16_bits, pack_normal_oct, pack_normal_oct_f16,
+        unpack_normal_oct_f16,
+    };
+
+    for value in [
+        0.0,
+        -0.0,
+        1.0,
+        -2.5,
+        1.0e-6,
+        5.960_464_5e-8,
+        65504.0,
+        1.0e6,
+        1.0 + 3.0 / 4096.0,
+    ] {
+        assert_eq!(f32_to_f16_bits(value), half::f16::from_f32(value).to_bits());
+    }
+    for bits in (0..0x7c00u16).step_by(7) {
+        assert_eq!(f16_bits_to_f32(bits), half::f16::from_bits(bits).to_f32());
+    }
+
+    let packed = pack_normal_oct_f16(0.6, -0.8, -0.0);
+    assert_eq!(
+        packed.map(half::f16::to_bits),
+        pack_normal_oct(0.6, -0.8, -0.0)
+    );
+    let [x, y, z] = unpack_normal_oct_f16(packed);
+    assert!((x - 0.6).abs() < 1e-3 && (y + 0.8).abs() < 1e-3 && z.abs() < 1e-3);
+}
+
+#[test]
+fn oct_normal_roundtrip() {
+    use ros_pointcloud2::points::{pack_normal_oct, unpack_normal_oct};
+
+    fn normalized(n: [f32; 3]) -> [f32; 3] {
+        let len = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+        [n[0] / len, n[1] / len, n[2] / len]
+    }
+
+    let normals = [
+        [0.0, 0.0, 1.0],
+        [0.0, 0.0, -1.0],
+        [1.0e-4, -2.0e-4, 1.0],
+        [1.0e-4, 2.0e-4, -1.0],
+        [-1.0e-3, 0.0, -1.0],
+        [1.0, 0.0, 0.0],
+        [0.0, -1.0, 0.0],
+        [0.3, -0.5, 0.8],
+        [-0.7, 0.1, -0.2],
+    ];
+    for normal in normals.iter() {
+        let expected = normalized(*normal);
+        let decoded = unpack_normal_oct(pack_normal_oct(expected[0], expected[1], expected[2]));
+        let dot = expected[0] * decoded[0] + expected[1] * decoded[1] + expected[2] * decoded[2];
+        let len =
+            (decoded[0] * decoded[0] + decoded[1] * decoded[1] + decoded[2] * decoded[2]).sqrt();
+        assert!(
+            (len - 1.0).abs() < 1e-6,
+            "{normal:?} decoded to length {len}"
+        );
+        assert!(dot > 0.999_99, "{normal:?} decoded to {decoded:?}");
+    }
+
+    assert_eq!(
+        unpack_normal_oct(pack_normal_oct(0.0, 0.0, 0.0)),
+        [0.0, 0.0, 1.0]
+    );
+
+    let cloud = vec![
+        PointXYZOctNormal::new(0.0, 1.0, 5.0, 0.0, 0.0, -1.0),
+        PointXYZOctNormal::new(1.0, 1.5, 5.0, 1.0, 0.0, 0.0),
+    ];
+    assert_eq!(std::mem::size_of::<PointXYZOctNormal>(), 16);
+    convert_from_into!(PointXYZOctNormal, cloud.clone());
+
+    let as_normals: Vec<PointXYZNormal> = cloud.iter().map(|p| (*p).into()).collect();
+    assert_eq!(as_normals[0].normal_z, -1.0);
+    assert_eq!(as_normals[1].normal_x, 1.0);
+}
+
+#[test]
+fn iter_lossy_missing_fields() {
+    let cloud = vec![PointXYZ::new(0.0, 1.0, 5.0), PointXYZ::new(1.0, 1.5, 5.0)];
+    let msg = PointCloud2Msg::try_from_vec(cloud).unwrap();
+
+    assert!(msg.clone().try_into_iter::<4, PointXYZI>().is_err());
+
+    let points: Vec<PointXYZRGBNormal> = msg.clone().try_into_iter_lossy().unwrap().collect();
+    assert_eq!(
+        points[1],
+        PointXYZRGBNormal::new(1.0, 1.5, 5.0, RGB::new(0, 0, 0), 0.0, 0.0, 0.0)
+    );
+
+    let labels = PointCloud2Msg::try_from_vec(vec![PointXYZL::new(0.0, 1.0, 5.0, 3)]).unwrap();
+    let mut without_y = labels.clone();
+    without_y.fields.retain(|f| f.name != "y");
+    match without_y.try_into_iter_lossy::<3, PointXYZ>() {
+        Err(MsgConversionError::FieldsNotFound(names)) => assert_eq!(names, vec!["y"]),
+        _ => panic!("expected missing y field"),
+    }
+
+    let points: Vec<PointXYZRGBL> = labels.try_into_iter_lossy().unwrap().collect();
+    assert_eq!(points[0].label, 3);
+    assert_eq!(points[0].z, 5.0);
+}
+
+#[test]
+fn iter_fields_in_other_order() {
+    let cloud = vec![PointXYZI::new(0.0, 1.0, 5.0, 0.5)];
+    let mut msg = PointCloud2Msg::try_from_vec(cloud.clone()).unwrap();
+    msg.fields.reverse();
+
+    let points: Vec<PointXYZI> = msg.try_into_iter().unwrap().collect();
+    assert_eq!(points, cloud);
+}
+
+#[test]
+fn organized_with_row_padding() {
+    let points = vec![
+        PointXYZI::new(0.0, 0.0, 1.0, 0.1),
+        PointXYZI::new(1.0, 0.0, 2.0, 0.2),
+        PointXYZI::new(0.0, 1.0, 3.0, 0.3),
+        PointXYZI::new(1.0, 1.0, 4.0, 0.4),
+    ];
+    let packed = PointCloud2Msg::try_from_vec(points.clone()).unwrap();
+    let point_step = packed.point_step;
+    let row_step = 2 * point_step + 8;
+
+    let mut data = Vec::new();
+    for row in packed.data.chunks_exact(2 * point_step as usize) {
+        data.extend_from_slice(row);
+        data.extend_from_slice(&[0xff; 8]);
+    }
+
+    let msg = PointCloud2Msg::try_from_organized_with_row_step(
+        data.clone(),
+        2,
+        2,
+        point_step,
+        row_step,
+        packed.fields.clone(),
+        packed.endian,
+    )
+    .unwrap();
+    assert_eq!(msg.dimensions.height, 2);
+    assert_eq!(msg.row_step, row_step);
+
+    let from_iter: Vec<PointXYZI> = msg.clone().try_into_iter().unwrap().collect();
+    assert_eq!(from_iter, points);
+    assert_eq!(
+        msg.layout_matches::<4, PointXYZI>().unwrap(),
+        MatchKind::Strided
+    );
+    let from_vec: Vec<PointXYZI> = msg.clone().try_into_vec().unwrap();
+    assert_eq!(from_vec, points);
+    let zs: Vec<f32> = msg.field_iter("z").unwrap().collect();
+    assert_eq!(zs, vec![1.0, 2.0, 3.0, 4.0]);
+
+    let tight = msg.repack_tight().unwrap();
+    assert_eq!(tight.row_step, 2 * tight.point_step);
+    let from_tight: Vec<PointXYZI> = tight.try_into_iter().unwrap().collect();
+    assert_eq!(from_tight, points);
+
+    assert!(matches!(
+        msg.sub_view(0, 1),
+        Err(MsgConversionError::InvalidArgument)
+    ));
+
+    assert!(matches!(
+        PointCloud2Msg::try_from_organized_with_row_step(
+            data.clone(),
+            2,
+            2,
+            point_step,
+            point_step,
+            packed.fields.clone(),
+            packed.endian,
+        ),
+        Err(MsgConversionError::InvalidArgument)
+    ));
+    assert!(matches!(
+        PointCloud2Msg::try_from_organized_with_row_step(
+            data[..data.len() - 1].to_vec(),
+            2,
+            2,
+            point_step,
+            row_step,
+            packed.fields,
+            packed.endian,
+        ),
+        Err(MsgConversionError::DataLengthMismatch)
+    ));
+}
+
+#[test]
+fn point_data_try_from() {
+    assert_eq!(f32::try_from(PointData::new(1.5f32)).unwrap(), 1.5);
+    assert_eq!(u8::try_from(PointData::new(7u8)).unwrap(), 7);
+    assert_eq!(i16::try_from(PointData::new(-3i16)).unwrap(), -3);
+    assert!(matches!(
+        u32::try_from(PointData::new(1.5f32)),
+        Err(MsgConversionError::InvalidFieldFormat)
+    ));
+    assert!(f64::try_from(PointData::new(1i32)).is_err());
+
+    let rgb = RGB::new(1, 2, 3);
+    assert_eq!(RGB::try_from(PointData::new(rgb)).unwrap(), rgb);
+    assert_eq!(RGB::try_from(PointData::new(rgb.raw())).unwrap(), rgb);
+
+    // values read from a message carry the datatype of their field
+    let msg = PointCloud2Msg::try_from_vec(vec![PointXYZL::new(0.0, 1.0, 5.0, 42)]).unwrap();
+    let point = &msg.try_into_named_iter().unwrap().next().unwrap();
+    assert_eq!(u32::try_from(point[3].1).unwrap(), 42);
+    assert_eq!(f32::try_from(point[1].1).unwrap(), 1.0);
+    assert!(u32::try_from(point[0].1).is_err());
+}
+
+#[test]
+fn decode_into_slice() {
+    let cloud = vec![
+        PointXYZINormal::new(0.0, 1.0, 5.0, 0.1, 0.0, 0.0, 1.0),
+        PointXYZINormal::new(1.0, 1.5, 5.0, 0.2, 0.0, 1.0, 0.0),
+        PointXYZINormal::new(1.3, 1.6, 5.7, 0.3, 1.0, 0.0, 0.0),
+    ];
+    let msg = PointCloud2Msg::try_from_vec(cloud.clone()).unwrap();
+
+    let mut exact = [PointXYZINormal::default(); 4];
+    assert_eq!(msg.decode_into(&mut exact).unwrap(), 3);
+    assert_eq!(&exact[..3], &cloud[..]);
+    assert_eq!(exact[3], PointXYZINormal::default());
+
+    let mut strided = [PointXYZI::default(); 3];
+    assert_eq!(msg.decode_into(&mut strided).unwrap(), 3);
+    assert_eq!(strided[2], PointXYZI::new(1.3, 1.6, 5.7, 0.3));
+
+    let mut reordered = msg.clone();
+    reordered.fields.reverse();
+    let mut converted = [PointXYZINormal::default(); 3];
+    assert_eq!(reordered.decode_into(&mut converted).unwrap(), 3);
+    assert_eq!(&converted[..], &cloud[..]);
+
+    let mut too_short = [PointXYZI::default(); 2];
+    assert!(matches!(
+        msg.decode_into(&mut too_short),
+        Err(MsgConversionError::InvalidArgument)
+    ));
+}
+
+#[test]
+fn array_iter() {
+    let cloud = vec![
+        PointXYZRGBNormal::new(0.0, 1.0, 5.0, RGB::new(1, 2, 3), 0.0, 0.0, 1.0),
+        PointXYZRGBNormal::new(1.0, 1.5, 5.0, RGB::new(4, 5, 6), 1.0, 0.0, 0.0),
+    ];
+    let msg = PointCloud2Msg::try_from_vec(cloud).unwrap();
+
+    let xyz: Vec<[f32; 3]> = msg.try_into_array_iter().unwrap().collect();
+    assert_eq!(xyz, vec![[0.0, 1.0, 5.0], [1.0, 1.5, 5.0]]);
+
+    // The packed color is stored as f32 as well.
+    let all: Vec<[f32; 7]> = msg.try_into_array_iter().unwrap().collect();
+    assert_eq!(all[1][4..], [1.0, 0.0, 0.0]);
+
+    assert!(matches!(
+        msg.try_into_array_iter::<8>().map(|_| ()),
+        Err(MsgConversionError::ExhaustedSource)
+    ));
+}
+
+#[test]
+fn iter_with_validity() {
+    let cloud = vec![
+        PointXYZI::new(0.0, 1.0, 5.0, 0.1),
+        PointXYZI::new(f32::NAN, f32::NAN, f32::NAN, 0.2),
+        PointXYZI::new(1.0, f32::INFINITY, 2.0, 0.3),
+        PointXYZI::new(1.3, 1.6, 5.7, f32::NAN),
+    ];
+    let mut msg = PointCloud2Msg::try_from_vec(cloud).unwrap();
+    msg.dimensions.width = 2;
+    msg.dimensions.height = 2;
+    msg.row_step = 2 * msg.point_step;
+
+    let points: Vec<(PointXYZI, bool)> =
+        msg.clone().try_into_iter_with_validity().unwrap().collect();
+    let validity: Vec<bool> = points.iter().map(|(_, valid)| *valid).collect();
+    assert_eq!(validity, vec![true, false, false, true]);
+    assert_eq!(points[0].0, PointXYZI::new(0.0, 1.0, 5.0, 0.1));
+    assert_eq!(points[3].0.x, 1.3);
+
+    let mut without_z = msg;
+    without_z.fields.retain(|f| f.name != "z");
+    assert!(without_z
+        .try_into_iter_with_validity::<4, PointXYZI>()
+        .is_err());
+}
+
+#[test]
+fn affine_field() {
+    let cloud = vec![
+        PointXYZL::new(0.0, 1.0, 5.0, 1),
+        PointXYZL::new(1.0, 1.5, 5.0, 2),
+    ];
+    let mut msg = PointCloud2Msg::try_from_vec(cloud).unwrap();
+
+    msg.affine_field_f32("z", 0.5, -1.0).unwrap();
+    let points: Vec<PointXYZL> = msg.clone().try_into_iter().unwrap().collect();
+    assert_eq!(
+        points,
+        vec![
+            PointXYZL::new(0.0, 1.0, 1.5, 1),
+            PointXYZL::new(1.0, 1.5, 1.5, 2)
+        ]
+    );
+
+    let mut big_endian = msg.clone();
+    big_endian.endian = ros_pointcloud2::Endian::Big;
+    for point in big_endian
+        .data
+        .chunks_exact_mut(big_endian.point_step as usize)
+    {
+        point[..12]
+            .chunks_exact_mut(4)
+            .for_each(|value| value.reverse());
+    }
+    big_endian.affine_field_f32("x", 2.0, 1.0).unwrap();
+    let xs: Vec<f32> = big_endian.field_iter("x").unwrap().collect();
+    assert_eq!(xs, vec![1.0, 3.0]);
+
+    assert!(matches!(
+        msg.affine_field_f32("label", 2.0, 0.0),
+        Err(MsgConversionError::InvalidFieldFormat)
+    ));
+}
+
+#[test]
+fn merge_dedup_clouds() {
+    let scan_a = PointCloud2Msg::try_from_vec(vec![
+        PointXYZI::new(0.0, 0.0, 0.0, 1.0),
+        PointXYZI::new(1.0, 0.0, 0.0, 2.0),
+        PointXYZI::new(-0.5, 0.25, 0.0, 3.0),
+    ])
+    .unwrap();
+    let scan_b = PointCloud2Msg::try_from_vec(vec![
+        PointXYZI::new(1.005, 0.0, 0.0, 4.0),
+        PointXYZI::new(-0.499, 0.25, 0.0, 5.0),
+        PointXYZI::new(f32::NAN, 0.0, 0.0, 6.0),
+        PointXYZI::new(2.0, 0.0, 0.0, 7.0),
+        PointXYZI::new(2.0, 0.0, 0.0, 8.0),
+    ])
+    .unwrap();
+
+    let merged = PointCloud2Msg::merge_dedup(&[&scan_a, &scan_b], 0.01).unwrap();
+    let intensities: Vec<f32> = merged.field_iter("intensity").unwrap().collect();
+    assert_eq!(intensities, vec![1.0, 2.0, 3.0, 6.0, 7.0]);
+    assert_eq!(merged.row_step, 5 * merged.point_step);
+
+    let xyz = PointCloud2Msg::try_from_vec(vec![PointXYZ::new(0.0, 0.0, 0.0)]).unwrap();
+    match PointCloud2Msg::merge_dedup(&[&scan_a, &xyz], 0.01) {
+        Err(MsgConversionError::SchemaMismatch { missing, .. }) => {
+            assert_eq!(missing, vec!["intensity".to_string()])
+        }
+        _ => panic!("expected a schema mismatch"),
+    }
+    assert!(matches!(
+        PointCloud2Msg::merge_dedup(&[&scan_a], 0.0),
+        Err(MsgConversionError::InvalidArgument)
+    ));
+    assert!(matches!(
+        PointCloud2Msg::merge_dedup(&[], 0.1),
+        Err(MsgConversionError::InvalidArgument)
+    ));
+}
+
+#[test]
+fn layout_from_repr_c_padding() {
+    #[derive(Clone, Debug, PartialEq, Copy, Default)]
+    #[repr(C)]
+    struct GappedPoint {
+        x: f32,
+        flag: u8,
+        y: f64,
+        color: RGB,
+    }
+
+    impl From<GappedPoint> for RPCL2Point<4> {
+        fn from(point: GappedPoint) -> Self {
+            [
+                point.x.into(),
+                point.flag.into(),
+                point.y.into(),
+                PointData::new(point.color),
+            ]
+            .into()
+        }
+    }
+
+    impl From<RPCL2Point<4>> for GappedPoint {
+        fn from(point: RPCL2Point<4>) -> Self {
+            Self {
+                x: point[0].get(),
+                flag: point[1].get(),
+                y: point[2].get(),
+                color: point[3].get(),
+            }
+        }
+    }
+
+    unsafe impl PointConvertible<4> for GappedPoint {
+        fn layout() -> LayoutDescription {
+            ros_pointcloud2::layout_from_repr_c!(GappedPoint {
+                x: f32,
+                flag: u8,
+                y: f64,
+                color as "rgb": RGB,
+            })
+        }
+    }
+
+    let cloud = vec![
+        GappedPoint {
+            x: 1.0,
+            flag: 3,
+            y: 2.5,
+            color: RGB::new(1, 2, 3),
+        },
+        GappedPoint {
+            x: -1.0,
+            flag: 4,
+            y: 0.5,
+            color: RGB::new(4, 5, 6),
+        },
+    ];
+    let msg = PointCloud2Msg::try_from_vec(cloud.clone()).unwrap();
+    assert_eq!(msg.point_step as usize, std::mem::size_of::<GappedPoint>());
+    let offsets: Vec<(String, u32)> = msg
+        .fields
+        .iter()
+        .map(|f| (f.name.clone(), f.offset))
+        .collect();
+    assert_eq!(
+        offsets,
+        vec![
+            ("x".to_string(), 0),
+            ("flag".to_string(), 4),
+            ("y".to_string(), 8),
+            ("rgb".to_string(), 16),
+        ]
+    );
+
+    let back: Vec<GappedPoint> = msg.clone().try_into_vec().unwrap();
+    assert_eq!(back, cloud);
+    let back_iter: Vec<GappedPoint> = msg.try_into_iter().unwrap().collect();
+    assert_eq!(back_iter, cloud);
+}
+
+#[test]
+fn point_step_for_fields() {
+    let field = |name: &str, offset: u32, datatype: u8, count: u32| PointFieldMsg {
+        name: name.into(),
+        offset,
+        datatype,
+        count,
+    };
+
+    let fields = vec![
+        field("intensity", 12, 7, 1),
+        field("x", 0, 7, 1),
+        field("y", 4, 7, 1),
+        field("z", 8, 7, 1),
+    ];
+    assert_eq!(PointCloud2Msg::point_step_for(&fields).unwrap(), 16);
+    assert_eq!(
+        PointCloud2Msg::point_step_for(&[field("normal", 4, 7, 3)]).unwrap(),
+        16
+    );
+    assert!(matches!(
+        PointCloud2Msg::point_step_for(&[field("x", 0, 7, 1), field("y", 2, 7, 1)]),
+        Err(MsgConversionError::InvalidFieldFormat)
+    ));
+    assert!(PointCloud2Msg::point_step_for(&[field("x", 0, 42, 1)]).is_err());
+    assert!(PointCloud2Msg::point_step_for(&[]).is_err());
+
+    // The builder rejects a point step that only covers the sum of the field sizes.
+    let gapped = vec![field("x", 0, 7, 1), field("intensity", 8, 7, 1)];
+    assert!(ros_pointcloud2::PointCloud2MsgBuilder::new()
+        .fields(gapped.clone())
+        .point_step(8)
+        .width(1)
+        .data(vec![0; 8])
+        .build()
+        .is_err());
+    assert!(ros_pointcloud2::PointCloud2MsgBuilder::new()
+        .fields(gapped)
+        .point_step(12)
+        .width(1)
+        .data(vec![0; 12])
+        .build()
+        .is_ok());
+}
+
+#[test]
+fn group_by_label() {
+    let cloud = vec![
+        PointXYZRGBL::new(0.0, 1.0, 5.0, 1, 2, 3, 7),
+        PointXYZRGBL::new(1.0, 1.5, 5.0, 4, 5, 6, 0),
+        PointXYZRGBL::new(1.3, 1.6, 5.7, 7, 8, 9, 7),
+        PointXYZRGBL::new(2.0, 0.0, 1.0, 1, 1, 1, 7),
+    ];
+    let msg = PointCloud2Msg::try_from_vec(cloud.clone()).unwrap();
+
+    let groups = msg.group_by_label("label").unwrap();
+    assert_eq!(groups.keys().copied().collect::<Vec<_>>(), vec![0, 7]);
+
+    let object: Vec<PointXYZRGBL> = groups[&7].clone().try_into_vec().unwrap();
+    assert_eq!(object, vec![cloud[0], cloud[2], cloud[3]]);
+    assert_eq!(groups[&7].row_step, 3 * msg.point_step);
+    let background: Vec<PointXYZRGBL> = groups[&0].clone().try_into_vec().unwrap();
+    assert_eq!(background, vec![cloud[1]]);
+
+    assert!(matches!(
+        msg.group_by_label("x"),
+        Err(MsgConversionError::InvalidFieldFormat)
+    ));
+    assert!(msg.group_by_label("segment").is_err());
+}
+
+#[test]
+fn point_data_buffer_try_from_slice() {
+    let buffer = PointDataBuffer::try_from_slice(&1.5f32.to_le_bytes()).unwrap();
+    assert_eq!(<f32 as FromBytes>::from_le_bytes(buffer), 1.5);
+    assert_eq!(PointDataBuffer::try_from_slice(&[]).unwrap().raw(), [0; 8]);
+    assert!(matches!(
+        PointDataBuffer::try_from_slice(&[0; 9]),
+        Err(MsgConversionError::InvalidArgument)
+    ));
+}
+
+#[test]
+fn write_big_endian() {
+    let cloud = vec![
+        PointXYZRGBL::new(1.0, 2.0, 3.0, 10, 20, 30, 4),
+        PointXYZRGBL::new(-4.5, 5.0, 6.25, 1, 2, 3, 70000),
+    ];
+    let native = PointCloud2Msg::try_from_vec(cloud.clone()).unwrap();
+    let msg =
+        PointCloud2Msg::try_from_vec_endian(cloud.clone(), ros_pointcloud2::Endian::Big).unwrap();
+    assert_eq!(msg.endian, ros_pointcloud2::Endian::Big);
+    assert_eq!(msg.data.len(), native.data.len());
+    assert_eq!(&msg.data[4..8], &2.0f32.to_be_bytes());
+
+    let back: Vec<PointXYZRGBL> = msg.clone().try_into_iter().unwrap().collect();
+    assert_eq!(back, cloud);
+    let back: Vec<PointXYZRGBL> = msg.try_into_vec().unwrap();
+    assert_eq!(back, cloud);
+
+    let little =
+        PointCloud2Msg::try_from_vec_endian(cloud, ros_pointcloud2::Endian::Little).unwrap();
+    assert_eq!(little.endian, ros_pointcloud2::Endian::Little);
+    assert_eq!(&little.data[4..8], &2.0f32.to_le_bytes());
+}
+
+#[test]
+fn try_from_iter_datatype_divergence() {
+    #[derive(Debug, PartialEq, Clone, Copy, Default)]
+    #[repr(C)]
+    struct FaultyPoint {
+        x: f32,
+        wide: bool,
+    }
+
+    impl From<RPCL2Point<2>> for FaultyPoint {
+        fn from(point: RPCL2Point<2>) -> Self {
+            Self {
+                x: point[0].get(),
+                wide: false,
+            }
+        }
+    }
+
+    impl From<FaultyPoint> for RPCL2Point<2> {
+        fn from(point: FaultyPoint) -> Self {
+            let extra = if point.wide {
+                PointData::new(1.0f64)
+            } else {
+                PointData::new(1.0f32)
+            };
+            [point.x.into(), extra].into()
+        }
+    }
+
+    unsafe impl PointConvertible<2> for FaultyPoint {
+        fn layout() -> LayoutDescription {
+            LayoutDescription::new(&[
+                LayoutField::new("x", "f32", 4),
+                LayoutField::new("extra", "f32", 4),
+            ])
+        }
+    }
+
+    let valid = vec![
+        FaultyPoint {
+            x: 1.0,
+            wide: false
+        };
+        2
+    ];
+    assert_eq!(PointCloud2Msg::try_from_iter(valid).unwrap().data.len(), 16);
+
+    let faulty = vec![
+        FaultyPoint {
+            x: 1.0,
+            wide: false,
+        },
+        FaultyPoint { x: 2.0, wide: true },
+    ];
+    assert!(matches!(
+        PointCloud2Msg::try_from_iter(faulty),
+        Err(MsgConversionError::InvalidFieldFormat)
+    ));
+}
+
+#[test]
+fn any_in_aabb() {
+    let msg = PointCloud2Msg::try_from_vec(vec![
+        PointXYZ::new(f32::NAN, 0.5, 0.5),
+        PointXYZ::new(3.0, 3.0, 3.0),
+        PointXYZ::new(-2.0, 1.0, 0.0),
+    ])
+    .unwrap();
+
+    assert!(msg.any_in_aabb([2.0, 2.0, 2.0], [3.0, 3.0, 3.0]).unwrap());
+    assert!(msg.any_in_aabb([-2.0, 0.0, -1.0], [0.0, 1.0, 1.0]).unwrap());
+    assert!(!msg.any_in_aabb([0.0, 0.0, 0.0], [1.0, 1.0, 1.0]).unwrap());
+
+    let empty = PointCloud2Msg::try_from_vec(Vec::<PointXYZ>::new()).unwrap();
+    assert!(!empty.any_in_aabb([0.0; 3], [1.0; 3]).unwrap());
+
+    let mut no_x = msg.clone();
+    no_x.fields[0].name = "u".into();
+    assert!(matches!(
+        no_x.any_in_aabb([0.0; 3], [1.0; 3]),
+        Err(MsgConversionError::FieldsNotFound(_))
+    ));
+}
+
+#[test]
+fn cloud_dimensions_checked_len() {
+    let dims = ros_pointcloud2::CloudDimensions {
+        width: 640,
+        height: 480,
+    };
+    assert_eq!(dims.checked_len(), Some(640 * 480));
+    assert_eq!(dims.len(), 640 * 480);
+    assert!(!dims.is_empty());
+    assert!(ros_pointcloud2::CloudDimensions {
+        width: 3,
+        height: 0
+    }
+    .is_empty());
+
+    let huge = ros_pointcloud2::CloudDimensions {
+        width: 65536,
+        height: 65536,
+    };
+    #[cfg(target_pointer_width = "32")]
+    {
+        assert_eq!(huge.checked_len(), None);
+        assert_eq!(huge.len(), usize::MAX);
+    }
+    #[cfg(target_pointer_width = "64")]
+    assert_eq!(huge.checked_len(), Some(1 << 32));
+
+    // Dimensions far beyond the buffer are rejected instead of under-reading the data.
+    let mut msg = PointCloud2Msg::try_from_vec(vec![PointXYZ::new(1.0, 2.0, 3.0)]).unwrap();
+    msg.dimensions = huge;
+    msg.row_step = 65536 * msg.point_step;
+    assert!(msg.clone().try_into_iter::<3, PointXYZ>().is_err());
+    assert!(msg.try_into_vec::<3, PointXYZ>().is_err());
+}
+
+#[test]
+fn organized_rgb_image() {
+    let points = vec![
+        PointXYZRGB::new(0.0, 0.0, 1.0, 10, 20, 30),
+        PointXYZRGB::new(1.0, 0.0, 1.0, 40, 50, 60),
+        PointXYZRGB::new(0.0, 1.0, 1.0, 70, 80, 90),
+        PointXYZRGB::new(1.0, 1.0, 1.0, 100, 110, 120),
+    ];
+    let packed = PointCloud2Msg::try_from_vec(points).unwrap();
+    assert!(matches!(
+        packed.to_rgb_image(),
+        Err(MsgConversionError::InvalidArgument)
+    ));
+
+    let point_step = packed.point_step;
+    let mut data = Vec::new();
+    for row in packed.data.chunks_exact(2 * point_step as usize) {
+        data.extend_from_slice(row);
+        data.extend_from_slice(&[0xff; 4]);
+    }
+    let msg = PointCloud2Msg::try_from_organized_with_row_step(
+        data,
+        2,
+        2,
+        point_step,
+        2 * point_step + 4,
+        packed.fields.clone(),
+        packed.endian,
+    )
+    .unwrap();
+
+    let (width, height, pixels) = msg.to_rgb_image().unwrap();
+    assert_eq!((width, height), (2, 2));
+    assert_eq!(
+        pixels,
+        vec![10, 20, 30, 40, 50, 60, 70, 80, 90, 100, 110, 120]
+    );
+
+    let mut no_color = PointCloud2Msg::try_from_vec(vec![PointXYZ::new(0.0, 0.0, 0.0); 4]).unwrap();
+    no_color.dimensions.width = 2;
+    no_color.dimensions.height = 2;
+    assert!(matches!(
+        no_color.to_rgb_image(),
+        Err(MsgConversionError::FieldsNotFound(_))
+    ));
+}
+
+#[test]
+fn try_into_vec_with_extras() {
+    let cloud = vec![
+        PointXYZRGBL::new(1.0, 2.0, 3.0, 10, 20, 30, 4),
+        PointXYZRGBL::new(4.0, 5.0, 6.0, 40, 50, 60, 5),
+    ];
+    let msg = PointCloud2Msg::try_from_vec(cloud).unwrap();
+
+    let (points, extras) = msg
+        .clone()
+        .try_into_vec_with_extras::<3, PointXYZ>()
+        .unwrap();
+    assert_eq!(
+        points,
+        vec![PointXYZ::new(1.0, 2.0, 3.0), PointXYZ::new(4.0, 5.0, 6.0)]
+    );
+    let names: Vec<&str> = extras.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, vec!["rgb", "label"]);
+
+    let mut rest = msg.clone();
+    rest.fields = extras;
+    let labels: Vec<u32> = rest.field_iter("label").unwrap().collect();
+    assert_eq!(labels, vec![4, 5]);
+
+    let (_, extras) = msg.try_into_vec_with_extras::<5, PointXYZRGBL>().unwrap();
+    assert!(extras.is_empty());
+}
+
+#[test]
+fn try_into_vec_limited() {
+    let cloud = vec![PointXYZI::new(1.0, 2.0, 3.0, 0.5); 4];
+    let msg = PointCloud2Msg::try_from_vec(cloud.clone()).unwrap();
+    assert_eq!(
+        msg.clone().try_into_vec_limited::<4, PointXYZI>(4).unwrap(),
+        cloud
+    );
+
+    // A crafted header is rejected before the buffer is inspected.
+    let mut crafted = msg;
+    crafted.dimensions.width = u32::MAX;
+    crafted.dimensions.height = 1;
+    assert!(matches!(
+        crafted.try_into_vec_limited::<4, PointXYZI>(1000),
+        Err(MsgConversionError::ExceedsLimit { limit: 1000, .. })
+    ));
+}
+
+#[test]
+fn rename_field() {
+    let cloud = vec![PointXYZI::new(1.0, 2.0, 3.0, 0.5)];
+    let mut msg = PointCloud2Msg::try_from_vec(cloud.clone()).unwrap();
+
+    msg.rename_field("intensity", "i").unwrap();
+    assert_eq!(msg.fields[3].name, "i");
+    assert!(matches!(
+        msg.rename_field("intensity", "i"),
+        Err(MsgConversionError::FieldsNotFound(_))
+    ));
+    assert!(matches!(
+        msg.rename_field("i", "x"),
+        Err(MsgConversionError::InvalidArgument)
+    ));
+    msg.rename_field("i", "i").unwrap();
+
+    msg.rename_field("i", "intensity").unwrap();
+    let back: Vec<PointXYZI> = msg.try_into_vec().unwrap();
+    assert_eq!(back, cloud);
+}
+
+#[test]
+fn organized_rows() {
+    let points = vec![
+        PointXYZI::new(0.0, 0.0, 1.0, 0.1),
+        PointXYZI::new(1.0, 0.0, 2.0, 0.2),
+        PointXYZI::new(0.0, 1.0, 3.0, 0.3),
+        PointXYZI::new(1.0, 1.0, 4.0, 0.4),
+    ];
+    let packed = PointCloud2Msg::try_from_vec(points.clone()).unwrap();
+    let point_step = packed.point_step;
+    let row_step = 2 * point_step + 8;
+
+    let mut data = Vec::new();
+    for row in packed.data.chunks_exact(2 * point_step as usize) {
+        data.extend_from_slice(row);
+        data.extend_from_slice(&[0xff; 8]);
+    }
+    let msg = PointCloud2Msg::try_from_organized_with_row_step(
+        data,
+        2,
+        2,
+        point_step,
+        row_step,
+        packed.fields.clone(),
+        packed.endian,
+    )
+    .unwrap();
+
+    let rows: Vec<&[u8]> = msg.rows().unwrap().collect();
+    assert_eq!(rows.len(), 2);
+    assert!(rows.iter().all(|row| row.len() == row_step as usize));
+    assert_eq!(&rows[1][..2 * point_step as usize], &packed.data[32..]);
+
+    let first: Vec<PointXYZI> = msg.row_points(0).unwrap();
+    assert_eq!(first, points[..2]);
+    let second: Vec<PointXYZ> = msg.row_points(1).unwrap();
+    assert_eq!(
+        second,
+        vec![PointXYZ::new(0.0, 1.0, 3.0), PointXYZ::new(1.0, 1.0, 4.0)]
+    );
+    assert!(matches!(
+        msg.row_points::<4, PointXYZI>(2),
+        Err(MsgConversionError::InvalidArgument)
+    ));
+
+    assert_eq!(packed.rows().unwrap().count(), 1);
+    let all: Vec<PointXYZI> = packed.row_points(0).unwrap();
+    assert_eq!(all, points);
+}
+
+#[test]
+fn from_slice_with_buffer() {
+    let cloud = vec![
+        PointXYZI::new(1.0, 2.0, 3.0, 0.5),
+        PointXYZI::new(4.0, 5.0, 6.0, 1.5),
+    ];
+    let buffer = vec![0xaa; 256];
+    let capacity = buffer.capacity();
+    let ptr = buffer.as_ptr();
+
+    let msg = PointCloud2Msg::try_from_slice_with_buffer(&cloud, buffer).unwrap();
+    assert_eq!(msg.data.len(), 2 * msg.point_step as usize);
+    assert_eq!(msg.data.capacity(), capacity);
+    assert_eq!(msg.data.as_ptr(), ptr);
+    assert_eq!(
+        msg.data,
+        PointCloud2Msg::try_from_vec(cloud.clone()).unwrap().data
+    );
+
+    let back: Vec<PointXYZI> = msg.try_into_vec().unwrap();
+    assert_eq!(back, cloud);
+}
+
+#[test]
+fn try_field_value() {
+    let cloud = vec![
+        PointXYZL::new(0.0, 1.0, 5.0, 7),
+        PointXYZL::new(1.0, 1.5, 5.0, 8),
+    ];
+    let msg = PointCloud2Msg::try_from_vec(cloud).unwrap();
+
+    assert_eq!(msg.try_field_value::<u32>(1, "label").unwrap(), Some(8));
+    assert_eq!(msg.try_field_value::<f32>(1, "y").unwrap(), Some(1.5));
+    assert_eq!(msg.try_field_value::<f32>(0, "intensity").unwrap(), None);
+    assert!(matches!(
+        msg.try_field_value::<f32>(0, "label"),
+        Err(MsgConversionError::InvalidFieldFormat)
+    ));
+    assert!(matches!(
+        msg.try_field_value::<u32>(2, "label"),
+        Err(MsgConversionError::InvalidArgument)
+    ));
+}
+
+#[test]
+fn same_schema() {
+    let a = PointCloud2Msg::try_from_vec(vec![PointXYZI::new(1.0, 2.0, 3.0, 0.5)]).unwrap();
+    let mut b = PointCloud2Msg::try_from_vec(vec![PointXYZI::new(0.0, 0.0, 0.0, 0.0); 3]).unwrap();
+    assert!(a.same_schema(&b));
+
+    b.fields.reverse();
+    assert!(a.same_schema(&b));
+
+    let mut renamed = a.clone();
+    renamed.rename_field("intensity", "i").unwrap();
+    assert!(!a.same_schema(&renamed));
+
+    let mut moved = a.clone();
+    moved.fields[3].offset = 16;
+    moved.point_step = 20;
+    assert!(!a.same_schema(&moved));
+
+    let mut swapped = a.clone();
+    swapped.endian = match a.endian {
+        ros_pointcloud2::Endian::Big => ros_pointcloud2::Endian::Little,
+        ros_pointcloud2::Endian::Little => ros_pointcloud2::Endian::Big,
+    };
+    assert!(!a.same_schema(&swapped));
+
+    let narrow = PointCloud2Msg::try_from_vec(vec![PointXYZ::new(1.0, 2.0, 3.0)]).unwrap();
+    assert!(!a.same_schema(&narrow));
+}
+
+#[test]
+fn schema_only() {
+    let header = ros_pointcloud2::ros::HeaderMsg {
+        frame_id: "map".into(),
+        ..Default::default()
+    };
+    let schema = PointCloud2Msg::schema_only::<5, PointXYZRGBL>(header).unwrap();
+    assert_eq!(schema.header.frame_id, "map");
+    assert_eq!(schema.dimensions.width, 0);
+    assert_eq!(schema.dimensions.height, 0);
+    assert!(schema.data.is_empty());
+
+    let msg =
+        PointCloud2Msg::try_from_iter(vec![PointXYZRGBL::new(1.0, 2.0, 3.0, 4, 5, 6, 7)]).unwrap();
+    assert!(schema.same_schema(&msg));
+
+    let empty: Vec<PointXYZRGBL> = schema.try_into_iter().unwrap().collect();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn from_flat_f32() {
+    let flat = vec![1.0, 2.0, 3.0, 0.5, 4.0, 5.0, 6.0, 1.5];
+    let msg = PointCloud2Msg::try_from_flat_f32_named(flat.clone(), &["x", "y", "z", "intensity"])
+        .unwrap();
+    assert_eq!(msg.point_step, 16);
+    let points: Vec<PointXYZI> = msg.try_into_vec().unwrap();
+    assert_eq!(
+        points,
+        vec![
+            PointXYZI::new(1.0, 2.0, 3.0, 0.5),
+            PointXYZI::new(4.0, 5.0, 6.0, 1.5)
+        ]
+    );
+
+    let msg = PointCloud2Msg::try_from_flat_f32(flat.clone(), 4).unwrap();
+    let names: Vec<&str> = msg.fields.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, vec!["f0", "f1", "f2", "f3"]);
+    let f3: Vec<f32> = msg.field_iter("f3").unwrap().collect();
+    assert_eq!(f3, vec![0.5, 1.5]);
+
+    assert!(matches!(
+        PointCloud2Msg::try_from_flat_f32(flat.clone(), 3),
+        Err(MsgConversionError::DataLengthMismatch)
+    ));
+    assert!(matches!(
+        PointCloud2Msg::try_from_flat_f32(flat, 0),
+        Err(MsgConversionError::InvalidArgument)
+    ));
+}
+
+#[test]
+fn builder_alignment_check() {
+    use ros_pointcloud2::ros::PointFieldMsg;
+
+    let field = |name: &str, offset, datatype| PointFieldMsg {
+        name: name.into(),
+        offset,
+        datatype,
+        count: 1,
+    };
+    let build = |fields, point_step| {
+        ros_pointcloud2::PointCloud2MsgBuilder::new()
+            .fields(fields)
+            .point_step(point_step)
+            .with_alignment_check(true)
+            .build()
+    };
+
+    assert!(build(vec![field("x", 0, 7), field("t", 8, 8)], 16).is_ok());
+    match build(vec![field("x", 0, 7), field("t", 4, 8)], 12) {
+        Err(MsgConversionError::Misaligned {
+            field: Some(name),
+            alignment: 8,
+        }) => assert_eq!(name, "t"),
+        _ => panic!("expected misaligned field"),
+    }
+    assert!(matches!(
+        build(vec![field("t", 0, 8), field("x", 8, 7)], 12),
+        Err(MsgConversionError::Misaligned {
+            field: None,
+            alignment: 8
+        })
+    ));
+
+    // The check is opt-in.
+    assert!(ros_pointcloud2::PointCloud2MsgBuilder::new()
+        .fields(vec![field("x", 0, 7), field("t", 4, 8)])
+        .point_step(12)
+        .build()
+        .is_ok());
+}
+
+#[test]
+fn from_soa_xyz() {
+    let x = [1.0, 4.0, 7.0];
+    let y = [2.0, 5.0, 8.0];
+    let z = [3.0, 6.0, 9.0];
+    let msg = PointCloud2Msg::try_from_soa_xyz(&x, &y, &z).unwrap();
+
+    let expected = vec![
+        PointXYZ::new(1.0, 2.0, 3.0),
+        PointXYZ::new(4.0, 5.0, 6.0),
+        PointXYZ::new(7.0, 8.0, 9.0),
+    ];
+    let aos = PointCloud2Msg::try_from_vec(expected.clone()).unwrap();
+    assert!(msg.same_schema(&aos));
+    assert_eq!(msg.row_step, aos.row_step);
+    assert_eq!(msg.try_into_vec::<3, PointXYZ>().unwrap(), expected);
+
+    assert!(matches!(
+        PointCloud2Msg::try_from_soa_xyz(&x, &y, &z[..2]),
+        Err(MsgConversionError::DataLengthMismatch)
+    ));
+    let empty = PointCloud2Msg::try_from_soa_xyz(&[], &[], &[]).unwrap();
+    assert_eq!(empty.dimensions.width, 0);
+}
+
+#[test]
+fn point_type_larger_than_point_step() {
+    let cloud = vec![PointXYZ::new(1.0, 2.0, 3.0), PointXYZ::new(4.0, 5.0, 6.0)];
+    // 12 bytes per point, while `PointXYZ` has 16 bytes with its padding.
+    let tight = PointCloud2Msg::try_from_vec(cloud.clone())
+        .unwrap()
+        .repack_tight()
+        .unwrap();
+    assert_eq!(tight.point_step, 12);
+    assert_eq!(tight.data.len(), 24);
+
+    assert_eq!(
+        tight.layout_matches::<3, PointXYZ>().unwrap(),
+        MatchKind::RequiresConversion
+    );
+    let points: Vec<PointXYZ> = tight.try_into_vec().unwrap();
+    assert_eq!(points, cloud);
+}
+
+#[test]
+fn try_into_vec_profiled() {
+    let cloud = vec![
+        PointXYZI::new(1.0, 2.0, 3.0, 0.5),
+        PointXYZI::new(4.0, 5.0, 6.0, 1.5),
+    ];
+    let msg = PointCloud2Msg::try_from_vec(cloud.clone()).unwrap();
+
+    let (points, path) = msg.clone().try_into_vec_profiled::<4, PointXYZI>().unwrap();
+    assert_eq!(points, cloud);
+    assert_eq!(path, DecodePath::ZeroCopy);
+
+    let wide = PointCloud2Msg::try_from_vec(vec![PointXYZINormal::new(
+        1.0, 2.0, 3.0, 0.5, 0.0, 0.0, 1.0,
+    )])
+    .unwrap();
+    let (_, path) = wide.try_into_vec_profiled::<4, PointXYZI>().unwrap();
+    assert_eq!(path, DecodePath::Strided);
+
+    let xyz = vec![PointXYZ::new(1.0, 2.0, 3.0)];
+    let tight = PointCloud2Msg::try_from_vec(xyz.clone())
+        .unwrap()
+        .repack_tight()
+        .unwrap();
+    let (points, path) = tight.try_into_vec_profiled::<3, PointXYZ>().unwrap();
+    assert_eq!(points, xyz);
+    assert_eq!(path, DecodePath::PerField);
+
+    let big =
+        PointCloud2Msg::try_from_vec_endian(cloud.clone(), ros_pointcloud2::Endian::Big).unwrap();
+    let little =
+        PointCloud2Msg::try_from_vec_endian(cloud.clone(), ros_pointcloud2::Endian::Little)
+            .unwrap();
+    let foreign = if cfg!(target_endian = "little") {
+        big
+    } else {
+        little
+    };
+    let (points, path) = foreign.try_into_vec_profiled::<4, PointXYZI>().unwrap();
+    assert_eq!(points, cloud);
+    assert_eq!(path, DecodePath::EndianFallback);
+}
+
+#[test]
+fn try_into_vec_profiled_unaligned_rows() {
+    let cloud = vec![
+        PointXYZI::new(1.0, 2.0, 3.0, 0.5),
+        PointXYZI::new(4.0, 5.0, 6.0, 1.5),
+        PointXYZI::new(7.0, 8.0, 9.0, 2.5),
+    ];
+    let packed = PointCloud2Msg::try_from_vec(cloud.clone()).unwrap();
+    let point_step = packed.point_step;
+    // 4 padding bytes per row leave every row after the first misaligned for `PointXYZI`.
+    let row_step = point_step + 4;
+    let mut data = Vec::new();
+    for point in packed.data.chunks(point_step as usize) {
+        data.extend_from_slice(point);
+        data.extend_from_slice(&[0xff; 4]);
+    }
+
+    let msg = PointCloud2Msg::try_from_organized_with_row_step(
+        data,
+        1,
+        3,
+        point_step,
+        row_step,
+        packed.fields.clone(),
+        packed.endian,
+    )
+    .unwrap();
+    let (points, path) = msg.try_into_vec_profiled::<4, PointXYZI>().unwrap();
+    assert_eq!(points, cloud);
+    assert_eq!(path, DecodePath::Strided);
+}
+
+#[test]
+fn append_reproject() {
+    let first = vec![
+        PointXYZI::new(1.0, 2.0, 3.0, 0.5),
+        PointXYZI::new(4.0, 5.0, 6.0, 1.5),
+    ];
+    let second = vec![PointXYZI::new(7.0, 8.0, 9.0, 2.5)];
+    let mut msg = PointCloud2Msg::try_from_vec(first.clone()).unwrap();
+
+    // Same fields in another order, byte order and with padding.
+    let big =
+        PointCloud2Msg::try_from_vec_endian(second.clone(), ros_pointcloud2::Endian::Big).unwrap();
+    let other = PointCloud2Msg::try_from_organized_with_row_step(
+        {
+            let mut data = Vec::new();
+            for point in big.data.chunks_exact(16) {
+                // move intensity to the front
+                data.extend_from_slice(&point[12..16]);
+                data.extend_from_slice(&point[0..12]);
+                data.extend_from_slice(&[0; 4]);
+            }
+            data
+        },
+        1,
+        1,
+        20,
+        20,
+        vec![
+            ros_pointcloud2::ros::PointFieldMsg {
+                name: "intensity".into(),
+                offset: 0,
+                datatype: 7,
+                count: 1,
+            },
+            ros_pointcloud2::ros::PointFieldMsg {
+                name: "x".into(),
+                offset: 4,
+                datatype: 7,
+                count: 1,
+            },
+            ros_pointcloud2::ros::PointFieldMsg {
+                name: "y".into(),
+                offset: 8,
+                datatype: 7,
+                count: 1,
+            },
+            ros_pointcloud2::ros::PointFieldMsg {
+                name: "z".into(),
+                offset: 12,
+                datatype: 7,
+                count: 1,
+            },
+        ],
+        ros_pointcloud2::Endian::Big,
+    )
+    .unwrap();
+
+    msg.try_append_reproject(&other).unwrap();
+    msg.try_append_reproject(&PointCloud2Msg::try_from_vec(second.clone()).unwrap())
+        .unwrap();
+    assert_eq!(msg.dimensions.width, 4);
+    assert_eq!(msg.row_step, 4 * msg.point_step);
+
+    let points: Vec<PointXYZI> = msg.clone().try_into_vec().unwrap();
+    assert_eq!(points, vec![first[0], first[1], second[0], second[0]]);
+
+    let xyz = PointCloud2Msg::try_from_vec(vec![PointXYZ::new(0.0, 0.0, 0.0)]).unwrap();
+    match msg.try_append_reproject(&xyz) {
+        Err(MsgConversionError::SchemaMismatch { missing, .. }) => {
+            assert_eq!(missing, vec!["intensity".to_string()]);
+        }
+        _ => panic!("expected schema mismatch"),
+    }
+}
+
+#[test]
+fn field_bytes_column() {
+    let cloud = vec![
+        PointXYZL::new(0.0, 1.0, 5.0, 7),
+        PointXYZL::new(1.0, 1.5, 5.0, 0x0102_0304),
+    ];
+    let msg = PointCloud2Msg::try_from_vec(cloud).unwrap();
+
+    let labels = msg.field_bytes("label").unwrap();
+    let mut expected = 7u32.to_ne_bytes().to_vec();
+    expected.extend_from_slice(&0x0102_0304u32.to_ne_bytes());
+    assert_eq!(labels, expected);
+
+    let y = msg.field_bytes("y").unwrap();
+    let values: Vec<f32> = y
+        .chunks_exact(4)
+        .map(|b| f32::from_ne_bytes([b[0], b[1], b[2], b[3]]))
+        .collect();
+    assert_eq!(values, vec![1.0, 1.5]);
+
+    assert!(matches!(
+        msg.field_bytes("intensity"),
+        Err(MsgConversionError::FieldsNotFound(_))
+    ));
+}
+
+#[test]
+fn tuple_points() {
+    let cloud = vec![(1.0f32, 2.0f32, 3.0f32), (4.0, 5.0, 6.0)];
+    let msg = PointCloud2Msg::try_from_vec(cloud.clone()).unwrap();
+    let names: Vec<&str> = msg.fields.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, vec!["0", "1", "2"]);
+    assert_eq!(msg.point_step, 12);
+
+    let back: Vec<(f32, f32, f32)> = msg.clone().try_into_vec().unwrap();
+    assert_eq!(back, cloud);
+    let back: Vec<(f32, f32, f32)> = msg.try_into_iter().unwrap().collect();
+    assert_eq!(back, cloud);
+
+    // Mixed sizes may be reordered in memory by the compiler.
+    let mixed = vec![(7u8, 1.5f64, 9u8), (8, -2.5, 10)];
+    let msg = PointCloud2Msg::try_from_vec(mixed.clone()).unwrap();
+    assert_eq!(
+        msg.point_step as usize,
+        core::mem::size_of::<(u8, f64, u8)>()
+    );
+    let second: Vec<u8> = msg.field_iter("2").unwrap().collect();
+    assert_eq!(second, vec![9, 10]);
+    let back: Vec<(u8, f64, u8)> = msg.clone().try_into_vec().unwrap();
+    assert_eq!(back, mixed);
+    let back: Vec<(u8, f64, u8)> = msg.try_into_iter().unwrap().collect();
+    assert_eq!(back, mixed);
+
+    let from_iter = PointCloud2Msg::try_from_iter(mixed.clone()).unwrap();
+    let back: Vec<(u8, f64, u8)> = from_iter.try_into_iter().unwrap().collect();
+    assert_eq!(back, mixed);
+
+    // Read coordinates of a predefined point type after renaming the fields.
+    let mut xyzi = PointCloud2Msg::try_from_vec(vec![PointXYZI::new(1.0, 2.0, 3.0, 0.5)]).unwrap();
+    for (from, to) in [("x", "0"), ("y", "1"), ("z", "2")] {
+        xyzi.rename_field(from, to).unwrap();
+    }
+    let coords: Vec<(f32, f32, f32)> = xyzi.try_into_iter().unwrap().collect();
+    assert_eq!(coords, vec![(1.0, 2.0, 3.0)]);
+}
+
+#[test]
+fn array_fields() {
+    #[derive(Clone, Copy, Debug, PartialEq, Default)]
+    #[repr(C)]
+    struct PointNormalArray {
+        x: f32,
+        y: f32,
+        z: f32,
+        normal: [f32; 3],
+    }
+
+    impl From<RPCL2Point<6>> for PointNormalArray {
+        fn from(point: RPCL2Point<6>) -> Self {
+            Self {
+                x: point[0].get(),
+                y: point[1].get(),
+                z: point[2].get(),
+                normal: point.get_array(3),
+            }
+        }
+    }
+
+    impl From<PointNormalArray> for RPCL2Point<6> {
+        fn from(point: PointNormalArray) -> Self {
+            [
+                point.x.into(),
+                point.y.into(),
+                point.z.into(),
+                point.normal[0].into(),
+                point.normal[1].into(),
+                point.normal[2].into(),
+            ]
+            .into()
+        }
+    }
+
+    unsafe impl PointConvertible<6> for PointNormalArray {
+        fn layout() -> LayoutDescription {
+            LayoutDescription::new(&[
+                LayoutField::new("x", "f32", 4),
+                LayoutField::new("y", "f32", 4),
+                LayoutField::new("z", "f32", 4),
+                LayoutField::array("normal", "f32", 4, 3),
+            ])
+        }
+    }
+
+    let cloud = vec![
+        PointNormalArray {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+            normal: [0.0, 0.0, 1.0],
+        },
+        PointNormalArray {
+            x: 4.0,
+            y: 5.0,
+            z: 6.0,
+            normal: [0.0, 1.0, 0.0],
+        },
+    ];
+
+    let msg = PointCloud2Msg::try_from_vec(cloud.clone()).unwrap();
+    assert_eq!(msg.fields.len(), 4);
+    assert_eq!(msg.fields[3].count, 3);
+    assert_eq!(msg.point_step, 24);
+    assert_eq!(
+        msg.layout_matches::<6, PointNormalArray>().unwrap(),
+        MatchKind::Exact
+    );
+
+    let from_iter = PointCloud2Msg::try_from_iter(cloud.clone()).unwrap();
+    assert!(from_iter.same_schema(&msg));
+    assert_eq!(from_iter.data, msg.data);
+
+    let back: Vec<PointNormalArray> = msg.clone().try_into_iter().unwrap().collect();
+    assert_eq!(back, cloud);
+    let back: Vec<PointNormalArray> = msg.clone().try_into_vec().unwrap();
+    assert_eq!(back, cloud);
+    let xyz: Vec<PointXYZ> = msg.clone().try_into_iter().unwrap().collect();
+    assert_eq!(xyz[1], PointXYZ::new(4.0, 5.0, 6.0));
+
+    // Reordered fields are decoded by name with the element offsets.
+    let mut reordered = msg.clone();
+    reordered.fields.rotate_right(1);
+    let mut data = Vec::new();
+    for point in msg.data.chunks_exact(24) {
+        data.extend_from_slice(&point[12..24]);
+        data.extend_from_slice(&point[0..12]);
+    }
+    reordered.data = data;
+    reordered.fields[0].offset = 0;
+    for (i, field) in reordered.fields.iter_mut().skip(1).enumerate() {
+        field.offset = 12 + 4 * i as u32;
+    }
+    let back: Vec<PointNormalArray> = reordered.clone().try_into_iter().unwrap().collect();
+    assert_eq!(back, cloud);
+    let mut buffer = vec![PointNormalArray::default(); 2];
+    reordered.decode_into(&mut buffer).unwrap();
+    assert_eq!(buffer, cloud);
+
+    let mut short = msg.clone();
+    short.fields[3].count = 2;
+    assert!(matches!(
+        short.try_into_iter::<6, PointNormalArray>(),
+        Err(MsgConversionError::UnsupportedFieldCount)
+    ));
+
+    let mut empty = msg.fields.clone();
+    empty[3].count = 0;
+    assert!(matches!(
+        ros_pointcloud2::PointCloud2MsgBuilder::new()
+            .fields(empty)
+            .point_step(24)
+            .build(),
+        Err(MsgConversionError::UnsupportedFieldCount)
+    ));
+}
+
+#[test]
+fn iter_coords() {
+    let cloud = vec![
+        PointXYZRGBL::new(1.0, 2.0, 3.0, 10, 20, 30, 4),
+        PointXYZRGBL::new(4.0, 5.0, 6.0, 40, 50, 60, 5),
+    ];
+    let mut msg = PointCloud2Msg::try_from_vec(cloud).unwrap();
+    let coords: Vec<(f32, f32, f32)> = msg.iter_coords().unwrap().collect();
+    assert_eq!(coords, vec![(1.0, 2.0, 3.0), (4.0, 5.0, 6.0)]);
+
+    msg.fields.reverse();
+    let coords: Vec<(f32, f32, f32)> = msg.iter_coords().unwrap().collect();
+    assert_eq!(coords[1], (4.0, 5.0, 6.0));
+
+    msg.rename_field("z", "depth").unwrap();
+    assert!(matches!(
+        msg.iter_coords(),
+        Err(MsgConversionError::FieldsNotFound(_))
+    ));
+}
+
+#[test]
+fn builder_schema_from() {
+    let cloud = vec![
+        PointXYZRGBL::new(1.0, 2.0, 3.0, 10, 20, 30, 4),
+        PointXYZRGBL::new(4.0, 5.0, 6.0, 40, 50, 60, 5),
+    ];
+    let input =
+        PointCloud2Msg::try_from_vec_endian(cloud.clone(), ros_pointcloud2::Endian::Big).unwrap();
+
+    let mut data = input.data.clone();
+    data.truncate(input.point_step as usize);
+    let output = ros_pointcloud2::PointCloud2MsgBuilder::new()
+        .with_schema_from(&input)
+        .width(1)
+        .row_step(input.point_step)
+        .data(data)
+        .build()
+        .unwrap();
+
+    assert!(output.same_schema(&input));
+    assert_eq!(output.endian, ros_pointcloud2::Endian::Big);
+    let points: Vec<PointXYZRGBL> = output.try_into_iter().unwrap().collect();
+    assert_eq!(points, vec![cloud[0]]);
+}
+
+#[test]
+fn truncate_cloud() {
+    let points: Vec<PointXYZ> = (0..6).map(|i| PointXYZ::new(i as f32, 0.0, 0.0)).collect();
+
+    let mut msg = PointCloud2Msg::try_from_vec(points.clone()).unwrap();
+    msg.truncate(10);
+    assert_eq!(msg.dimensions.width, 6);
+    msg.truncate(4);
+    assert_eq!(msg.row_step, 4 * msg.point_step);
+    let kept: Vec<PointXYZ> = msg.try_into_vec().unwrap();
+    assert_eq!(kept, points[..4]);
+
+    let packed = PointCloud2Msg::try_from_vec(points.clone()).unwrap();
+    let point_step = packed.point_step;
+    let row_step = 2 * point_step + 8;
+    let mut data = Vec::new();
+    for row in packed.data.chunks_exact(2 * point_step as usize) {
+        data.extend_from_slice(row);
+        data.extend_from_slice(&[0xff; 8]);
+    }
+    let organized = PointCloud2Msg::try_from_organized_with_row_step(
+        data,
+        2,
+        3,
+        point_step,
+        row_step,
+        packed.fields.clone(),
+        packed.endian,
+    )
+    .unwrap();
+
+    let mut msg = organized.clone();
+    msg.truncate(5);
+    assert_eq!(msg.dimensions.height, 2);
+    assert_eq!(msg.row_step, row_step);
+    assert_eq!(msg.data.len(), 2 * row_step as usize);
+    let kept: Vec<PointXYZ> = msg.try_into_vec().unwrap();
+    assert_eq!(kept, points[..4]);
+
+    let mut msg = organized.clone();
+    msg.truncate(3);
+    assert_eq!(msg.dimensions.height, 1);
+    assert_eq!(msg.row_step, 2 * point_step);
+    let kept: Vec<PointXYZ> = msg.try_into_vec().unwrap();
+    assert_eq!(kept, points[..2]);
+
+    let mut msg = organized;
+    msg.truncate(1);
+    assert!(msg.dimensions.is_empty());
+    assert!(msg.data.is_empty());
+}
+
+#[test]
+fn attach_field_f32() {
+    let points = vec![PointXYZ::new(1.0, 2.0, 3.0), PointXYZ::new(4.0, 5.0, 6.0)];
+    let msg = PointCloud2Msg::try_from_vec(points).unwrap();
+
+    let with_intensity = msg.attach_field_f32("intensity", &[0.5, 1.5]).unwrap();
+    assert_eq!(with_intensity.point_step, msg.point_step + 4);
+    let decoded: Vec<PointXYZI> = with_intensity.try_into_vec().unwrap();
+    assert_eq!(
+        decoded,
+        vec![
+            PointXYZI::new(1.0, 2.0, 3.0, 0.5),
+            PointXYZI::new(4.0, 5.0, 6.0, 1.5)
+        ]
+    );
+
+    assert!(matches!(
+        msg.attach_field_f32("intensity", &[0.5]),
+        Err(MsgConversionError::DataLengthMismatch)
+    ));
+    assert!(matches!(
+        msg.attach_field_f32("x", &[0.5, 1.5]),
+        Err(MsgConversionError::InvalidArgument)
+    ));
+}
+
+#[test]
+fn unpack_rgb_channels() {
+    #[derive(Debug, PartialEq, Clone, Copy, Default)]
+    #[repr(C)]
+    struct ChannelPoint {
+        x: f32,
+        y: f32,
+        z: f32,
+        r: u8,
+        g: u8,
+        b: u8,
+    }
+
+    impl From<RPCL2Point<6>> for ChannelPoint {
+        fn from(point: RPCL2Point<6>) -> Self {
+            Self {
+                x: point[0].get(),
+                y: point[1].get(),
+                z: point[2].get(),
+                r: point[3].get(),
+                g: point[4].get(),
+                b: point[5].get(),
+            }
+        }
+    }
+
+    impl From<ChannelPoint> for RPCL2Point<6> {
+        fn from(point: ChannelPoint) -> Self {
+            [
+                point.x.into(),
+                point.y.into(),
+                point.z.into(),
+                point.r.into(),
+                point.g.into(),
+                point.b.into(),
+            ]
+            .into()
+        }
+    }
+
+    unsafe impl PointConvertible<6> for ChannelPoint {
+        fn layout() -> LayoutDescription {
+            LayoutDescription::new(&[
+                LayoutField::new("x", "f32", 4),
+                LayoutField::new("y", "f32", 4),
+                LayoutField::new("z", "f32", 4),
+                LayoutField::new("r", "u8", 1),
+                LayoutField::new("g", "u8", 1),
+                LayoutField::new("b", "u8", 1),
+                LayoutField::padding(1),
+            ])
+        }
+    }
+
+    let cloud = vec![
+        PointXYZRGB::new(1.0, 2.0, 3.0, 10, 20, 30),
+        PointXYZRGB::new(4.0, 5.0, 6.0, 255, 0, 128),
+    ];
+    let expected = vec![
+        ChannelPoint {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+            r: 10,
+            g: 20,
+            b: 30,
+        },
+        ChannelPoint {
+            x: 4.0,
+            y: 5.0,
+            z: 6.0,
+            r: 255,
+            g: 0,
+            b: 128,
+        },
+    ];
+
+    for endian in [
+        ros_pointcloud2::Endian::Little,
+        ros_pointcloud2::Endian::Big,
+    ] {
+        let msg = PointCloud2Msg::try_from_vec_endian(cloud.clone(), endian).unwrap();
+        let points: Vec<ChannelPoint> = msg
+            .try_into_iter_with_color(ColorDecode::Channels)
+            .unwrap()
+            .collect();
+        assert_eq!(points, expected);
+    }
+
+    let msg = PointCloud2Msg::try_from_vec(cloud.clone()).unwrap();
+    assert!(matches!(
+        msg.clone()
+            .try_into_iter_with_color::<6, ChannelPoint>(ColorDecode::Packed),
+        Err(MsgConversionError::FieldsNotFound(_))
+    ));
+
+    // Packed color types are still read as is.
+    let packed: Vec<PointXYZRGB> = msg
+        .try_into_iter_with_color(ColorDecode::Channels)
+        .unwrap()
+        .collect();
+    assert_eq!(packed, cloud);
+}
+
+#[test]
+fn shuffle_points() {
+    let points: Vec<PointXYZI> = (0..6)
+        .map(|i| PointXYZI::new(i as f32, 0.0, 0.0, i as f32 * 0.5))
+        .collect();
+    let packed = PointCloud2Msg::try_from_vec(points.clone()).unwrap();
+    let point_step = packed.point_step;
+    let row_step = 3 * point_step + 4;
+
+    let mut data = Vec::new();
+    for row in packed.data.chunks_exact(3 * point_step as usize) {
+        data.extend_from_slice(row);
+        data.extend_from_slice(&[0xff; 4]);
+    }
+    let mut msg = PointCloud2Msg::try_from_organized_with_row_step(
+        data,
+        3,
+        2,
+        point_step,
+        row_step,
+        packed.fields.clone(),
+        packed.endian,
+    )
+    .unwrap();
+
+    msg.shuffle(7).unwrap();
+    assert_eq!(msg.dimensions.width, 6);
+    assert_eq!(msg.dimensions.height, 1);
+    assert_eq!(msg.row_step, 6 * point_step);
+
+    let mut unorganized = packed.clone();
+    unorganized.shuffle(7).unwrap();
+    assert_eq!(unorganized.data, msg.data);
+
+    let mut shuffled: Vec<PointXYZI> = msg.try_into_vec().unwrap();
+    assert_ne!(shuffled, points);
+    shuffled.sort_by(|a, b| a.x.total_cmp(&b.x));
+    assert_eq!(shuffled, points);
+}
+
+#[test]
+fn approx_eq_clouds() {
+    let points = vec![
+        PointXYZRGBL::new(1.0, 2.0, 3.0, 10, 20, 30, 4),
+        PointXYZRGBL::new(f32::NAN, 5.0, 6.0, 40, 50, 60, 5),
+    ];
+    let msg = PointCloud2Msg::try_from_vec(points.clone()).unwrap();
+    let big_endian =
+        PointCloud2Msg::try_from_vec_endian(points.clone(), ros_pointcloud2::Endian::Big).unwrap();
+    assert!(msg.approx_eq::<5, PointXYZRGBL>(&big_endian, 0.0).unwrap());
+
+    let mut shifted = points.clone();
+    shifted[0].z += 1e-3;
+    let shifted = PointCloud2Msg::try_from_vec(shifted).unwrap();
+    assert!(msg.approx_eq::<5, PointXYZRGBL>(&shifted, 1e-2).unwrap());
+    assert!(!msg.approx_eq::<5, PointXYZRGBL>(&shifted, 1e-4).unwrap());
+
+    let mut relabeled = points.clone();
+    relabeled[1].label = 6;
+    let relabeled = PointCloud2Msg::try_from_vec(relabeled).unwrap();
+    assert!(!msg.approx_eq::<5, PointXYZRGBL>(&relabeled, 1.0).unwrap());
+
+    let shorter = PointCloud2Msg::try_from_vec(points[..1].to_vec()).unwrap();
+    assert!(!msg.approx_eq::<5, PointXYZRGBL>(&shorter, 1.0).unwrap());
+
+    let xyz = PointCloud2Msg::try_from_vec(vec![PointXYZ::new(1.0, 2.0, 3.0)]).unwrap();
+    assert!(matches!(
+        msg.approx_eq::<5, PointXYZRGBL>(&xyz, 1.0),
+        Err(MsgConversionError::FieldsNotFound(_))
+    ));
+}
+
+#[test]
+fn try_into_vec_range() {
+    let points: Vec<PointXYZI> = (0..6)
+        .map(|i| PointXYZI::new(i as f32, 0.0, 0.0, i as f32 * 0.5))
+        .collect();
+    let packed = PointCloud2Msg::try_from_vec(points.clone()).unwrap();
+
+    let window: Vec<PointXYZI> = packed.try_into_vec_range(2, 3).unwrap();
+    assert_eq!(window, points[2..5]);
+    let empty: Vec<PointXYZI> = packed.try_into_vec_range(6, 0).unwrap();
+    assert!(empty.is_empty());
+    assert!(matches!(
+        packed.try_into_vec_range::<4, PointXYZI>(4, 3),
+        Err(MsgConversionError::InvalidArgument)
+    ));
+    assert!(matches!(
+        packed.try_into_vec_range::<4, PointXYZI>(1, usize::MAX),
+        Err(MsgConversionError::InvalidArgument)
+    ));
+
+    let point_step = packed.point_step;
+    let row_step = 3 * point_step + 4;
+    let mut data = Vec::new();
+    for row in packed.data.chunks_exact(3 * point_step as usize) {
+        data.extend_from_slice(row);
+        data.extend_from_slice(&[0xff; 4]);
+    }
+    let organized = PointCloud2Msg::try_from_organized_with_row_step(
+        data,
+        3,
+        2,
+        point_step,
+        row_step,
+        packed.fields.clone(),
+        packed.endian,
+    )
+    .unwrap();
+
+    let across_rows: Vec<PointXYZ> = organized.try_into_vec_range(1, 4).unwrap();
+    let expected: Vec<PointXYZ> = (1..5).map(|i| PointXYZ::new(i as f32, 0.0, 0.0)).collect();
+    assert_eq!(across_rows, expected);
+}
+
+#[test]
+fn remap_axes() {
+    let points = vec![
+        PointXYZI::new(1.0, 2.0, 3.0, 0.5),
+        PointXYZI::new(-4.0, 5.0, 0.0, 1.5),
+    ];
+    let mut msg =
+        PointCloud2Msg::try_from_vec_endian(points.clone(), ros_pointcloud2::Endian::Big).unwrap();
+
+    // Camera optical frame (z forward, x right, y down) to robot frame (x forward, y left, z up).
+    msg.remap_axes([Axis::Z, Axis::X, Axis::Y], [1.0, -1.0, -1.0])
+        .unwrap();
+    let remapped: Vec<PointXYZI> = msg.clone().try_into_iter().unwrap().collect();
+    assert_eq!(
+        remapped,
+        vec![
+            PointXYZI::new(3.0, -1.0, -2.0, 0.5),
+            PointXYZI::new(0.0, 4.0, -5.0, 1.5),
+        ]
+    );
+
+    msg.remap_axes([Axis::Y, Axis::Z, Axis::X], [-1.0, -1.0, 1.0])
+        .unwrap();
+    let back: Vec<PointXYZI> = msg.try_into_iter().unwrap().collect();
+    assert_eq!(back, points);
+
+    let mut no_z = PointCloud2Msg::try_from_flat_f32_named(vec![1.0, 2.0], &["x", "y"]).unwrap();
+    assert!(matches!(
+        no_z.remap_axes([Axis::X, Axis::Y, Axis::Z], [1.0; 3]),
+        Err(MsgConversionError::FieldsNotFound(_))
+    ));
+}
+
+#[test]
+fn incremental_writer() {
+    let points = vec![
+        PointXYZRGBL::new(1.0, 2.0, 3.0, 10, 20, 30, 4),
+        PointXYZRGBL::new(4.0, 5.0, 6.0, 40, 50, 60, 5),
+        PointXYZRGBL::new(7.0, 8.0, 9.0, 70, 80, 90, 6),
+    ];
+
+    let mut writer = PointCloud2Writer::<5, PointXYZRGBL>::new().unwrap();
+    assert!(writer.is_empty());
+    for point in points.iter() {
+        writer.push(*point).unwrap();
+    }
+    assert_eq!(writer.len(), 3);
+
+    let msg = writer.finish().unwrap();
+    let expected = PointCloud2Msg::try_from_iter(points.clone()).unwrap();
+    assert!(msg.same_schema(&expected));
+    assert_eq!(msg.row_step, expected.row_step);
+    assert_eq!(msg.data, expected.data);
+    let decoded: Vec<PointXYZRGBL> = msg.try_into_vec().unwrap();
+    assert_eq!(decoded, points);
+
+    let empty = PointCloud2Writer::<3, PointXYZ>::new()
+        .unwrap()
+        .finish()
+        .unwrap();
+    assert!(empty.dimensions.is_empty());
+    assert_eq!(empty.fields.len(), 3);
+}
+
+#[test]
+fn zero_size_points() {
+    #[derive(Debug, Clone, Copy, Default, PartialEq)]
+    struct EmptyPoint;
+
+    impl From<RPCL2Point<0>> for EmptyPoint {
+        fn from(_: RPCL2Point<0>) -> Self {
+            Self
+        }
+    }
+
+    impl From<EmptyPoint> for RPCL2Point<0> {
+        fn from(_: EmptyPoint) -> Self {
+            [].into()
+        }
+    }
+
+    unsafe impl PointConvertible<0> for EmptyPoint {
+        fn layout() -> LayoutDescription {
+            LayoutDescription::new(&[])
+        }
+    }
+
+    assert!(matches!(
+        PointCloud2Msg::try_from_iter(vec![EmptyPoint; 3]),
+        Err(MsgConversionError::ZeroPointStep)
+    ));
+    assert!(matches!(
+        PointCloud2Msg::try_from_vec(vec![EmptyPoint; 3]),
+        Err(MsgConversionError::ZeroPointStep)
+    ));
+    assert!(matches!(
+        PointCloud2Writer::<0, EmptyPoint>::new(),
+        Err(MsgConversionError::ZeroPointStep)
+    ));
+
+    let msg = PointCloud2Msg::try_from_vec(vec![PointXYZ::new(1.0, 2.0, 3.0)]).unwrap();
+    assert!(matches!(
+        msg.clone().try_into_iter::<0, EmptyPoint>(),
+        Err(MsgConversionError::ZeroPointStep)
+    ));
+    assert!(matches!(
+        msg.clone().try_into_vec::<0, EmptyPoint>(),
+        Err(MsgConversionError::ZeroPointStep)
+    ));
+
+    assert!(matches!(
+        ros_pointcloud2::PointCloud2MsgBuilder::new()
+            .fields(msg.fields.clone())
+            .width(1)
+            .build(),
+        Err(MsgConversionError::ZeroPointStep)
+    ));
+
+    let mut zero_step = msg;
+    zero_step.point_step = 0;
+    zero_step.row_step = 0;
+    zero_step.data.clear();
+    assert!(matches!(
+        zero_step.shuffle(1),
+        Err(MsgConversionError::ZeroPointStep)
+    ));
+}
+
+#[test]
+fn overlapping_union_fields() {
+    let points = vec![
+        PointXYZRGB::new(1.0, 2.0, 3.0, 10, 20, 30),
+        PointXYZRGB::new(4.0, 5.0, 6.0, 40, 50, 60),
+    ];
+    let msg = PointCloud2Msg::try_from_vec(points.clone()).unwrap();
+    let rgb = msg.fields.iter().find(|f| f.name == "rgb").unwrap().clone();
+
+    let mut fields = msg.fields.clone();
+    fields.push(ros_pointcloud2::ros::PointFieldMsg {
+        name: "rgba".into(),
+        offset: rgb.offset,
+        datatype: FieldDatatype::U32.into(),
+        count: 1,
+    });
+    let builder = ros_pointcloud2::PointCloud2MsgBuilder::new()
+        .fields(fields)
+        .point_step(msg.point_step)
+        .width(2)
+        .row_step(msg.row_step)
+        .data(msg.data.clone());
+
+    assert!(matches!(
+        builder.clone().build(),
+        Err(MsgConversionError::InvalidFieldFormat)
+    ));
+
+    let union = builder.allow_overlapping_fields(true).build().unwrap();
+    let decoded: Vec<PointXYZRGB> = union.clone().try_into_vec().unwrap();
+    assert_eq!(decoded, points);
+    let packed: Vec<u32> = union.field_iter("rgba").unwrap().collect();
+    assert_eq!(packed, vec![0x000a141e, 0x0028323c]);
+}
+
+#[test]
+fn morton_codes() {
+    let points = vec![
+        PointXYZ::new(3.5, 3.5, 3.5),
+        PointXYZ::new(0.5, 0.5, 0.5),
+        PointXYZ::new(1.5, 0.5, 0.5),
+        PointXYZ::new(-10.0, 10.0, f32::NAN),
+    ];
+    let msg = PointCloud2Msg::try_from_vec(points.clone()).unwrap();
+    let bounds = ([0.0; 3], [4.0; 3]);
+
+    let codes = msg.morton_codes(bounds, 2).unwrap();
+    assert_eq!(codes, vec![0b111111, 0, 0b000001, 0b010010]);
+
+    let sorted: Vec<PointXYZ> = msg
+        .sort_by_morton(bounds, 2)
+        .unwrap()
+        .try_into_vec()
+        .unwrap();
+    assert_eq!(sorted[0], points[1]);
+    assert_eq!(sorted[1], points[2]);
+    assert_eq!(sorted[3], points[0]);
+
+    assert!(matches!(
+        msg.morton_codes(bounds, 0),
+        Err(MsgConversionError::InvalidArgument)
+    ));
+    assert!(matches!(
+        msg.morton_codes(bounds, 22),
+        Err(MsgConversionError::InvalidArgument)
+    ));
+    assert!(matches!(
+        msg.morton_codes(([0.0; 3], [4.0, 0.0, 4.0]), 2),
+        Err(MsgConversionError::InvalidArgument)
+    ));
+}
+
+#[test]
+fn convert_field_type() {
+    let points: Vec<(f64, f64, f64, f32, u16)> =
+        vec![(1.0, 2.0, 3.0, 0.5, 7), (4.0, 5.0, 6.0, 1.5, 8)];
+    let mut msg = PointCloud2Msg::try_from_iter(points).unwrap();
+    for (from, to) in [
+        ("0", "x"),
+        ("1", "y"),
+        ("2", "z"),
+        ("3", "intensity"),
+        ("4", "ring"),
+    ] {
+        msg.rename_field(from, to).unwrap();
+    }
+
+    let mut narrowed = msg.clone();
+    for axis in ["x", "y", "z"] {
+        narrowed = narrowed
+            .convert_field_type(axis, FieldDatatype::F32)
+            .unwrap();
+    }
+    assert_eq!(narrowed.point_step, msg.point_step - 12);
+    let decoded: Vec<PointXYZI> = narrowed.clone().try_into_iter().unwrap().collect();
+    assert_eq!(
+        decoded,
+        vec![
+            PointXYZI::new(1.0, 2.0, 3.0, 0.5),
+            PointXYZI::new(4.0, 5.0, 6.0, 1.5)
+        ]
+    );
+    assert_eq!(
+        narrowed.field_bytes("ring").unwrap(),
+        msg.field_bytes("ring").unwrap()
+    );
+
+    let saturated = msg.convert_field_type("ring", FieldDatatype::I8).unwrap();
+    let rings: Vec<i8> = saturated.field_iter("ring").unwrap().collect();
+    assert_eq!(rings, vec![7, 8]);
+    let clamped = narrowed
+        .convert_field_type("intensity", FieldDatatype::U8)
+        .unwrap();
+    let intensities: Vec<u8> = clamped.field_iter("intensity").unwrap().collect();
+    assert_eq!(intensities, vec![0, 1]);
+
+    assert!(matches!(
+        msg.convert_field_type("missing", FieldDatatype::F32),
+        Err(MsgConversionError::FieldsNotFound(_))
+    ));
+}
+
+#[test]
+fn point_and_byte_len() {
+    let msg = PointCloud2Msg::try_from_vec(vec![PointXYZI::new(1.0, 2.0, 3.0, 0.5); 4]).unwrap();
+    assert_eq!(msg.len(), 4);
+    assert!(!msg.is_empty());
+    assert_eq!(msg.byte_len(), 4 * msg.point_step as usize);
+
+    let empty = PointCloud2Msg::schema_only::<4, PointXYZI>(Default::default()).unwrap();
+    assert_eq!(empty.len(), 0);
+    assert!(empty.is_empty());
+    assert_eq!(empty.byte_len(), 0);
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn from_json_points() {
+    #[derive(Debug, PartialEq, Clone, Copy, Default, serde::Deserialize)]
+    struct JsonPoint {
+        x: f32,
+        y: f32,
+        z: f32,
+        intensity: f32,
+    }
+
+    impl From<RPCL2Point<4>> for JsonPoint {
+        fn from(point: RPCL2Point<4>) -> Self {
+            Self {
+                x: point[0].get(),
+                y: point[1].get(),
+                z: point[2].get(),
+                intensity: point[3].get(),
+            }
+        }
+    }
+
+    impl From<JsonPoint> for RPCL2Point<4> {
+        fn from(point: JsonPoint) -> Self {
+            [
+                point.x.into(),
+                point.y.into(),
+                point.z.into(),
+                point.intensity.into(),
+            ]
+            .into()
+        }
+    }
+
+    unsafe impl PointConvertible<4> for JsonPoint {
+        fn layout() -> LayoutDescription {
+            LayoutDescription::new(&[
+                LayoutField::new("x", "f32", 4),
+                LayoutField::new("y", "f32", 4),
+                LayoutField::new("z", "f32", 4),
+                LayoutField::new("intensity", "f32", 4),
+            ])
+        }
+    }
+
+    let json = r#"[
+        { "x": 1.0, "y": 2.0, "z": 3.0, "intensity": 0.5 },
+        { "x": 4.0, "y": 5.0, "z": 6.0, "intensity": 1.5 }
+    ]"#;
+    let msg = PointCloud2Msg::try_from_json_points::<4, JsonPoint>(json).unwrap();
+    let points: Vec<PointXYZI> = msg.try_into_iter().unwrap().collect();
+    assert_eq!(
+        points,
+        vec![
+            PointXYZI::new(1.0, 2.0, 3.0, 0.5),
+            PointXYZI::new(4.0, 5.0, 6.0, 1.5)
+        ]
+    );
+
+    let tuples = PointCloud2Msg::try_from_json_points::<3, (f32, f32, f32)>("[[1, 2, 3]]").unwrap();
+    assert_eq!(tuples.len(), 1);
+
+    assert!(matches!(
+        PointCloud2Msg::try_from_json_points::<4, JsonPoint>(r#"[{ "x": 1.0 }]"#),
+        Err(MsgConversionError::InvalidJson(_))
+    ));
+}
+
+#[test]
+fn decimate_points() {
+    let points: Vec<PointXYZI> = (0..6)
+        .map(|i| PointXYZI::new(i as f32, 0.0, 0.0, i as f32 * 0.5))
+        .collect();
+    let packed = PointCloud2Msg::try_from_vec(points.clone()).unwrap();
+
+    let every_other: Vec<PointXYZI> = packed.decimate(2).unwrap().try_into_vec().unwrap();
+    assert_eq!(every_other, vec![points[0], points[2], points[4]]);
+    let all: Vec<PointXYZI> = packed.decimate(1).unwrap().try_into_vec().unwrap();
+    assert_eq!(all, points);
+    assert_eq!(packed.decimate(10).unwrap().len(), 1);
+    assert!(matches!(
+        packed.decimate(0),
+        Err(MsgConversionError::InvalidArgument)
+    ));
+
+    let point_step = packed.point_step;
+    let row_step = 3 * point_step + 4;
+    let mut data = Vec::new();
+    for row in packed.data.chunks_exact(3 * point_step as usize) {
+        data.extend_from_slice(row);
+        data.extend_from_slice(&[0xff; 4]);
+    }
+    let organized = PointCloud2Msg::try_from_organized_with_row_step(
+        data,
+        3,
+        2,
+        point_step,
+        row_step,
+        packed.fields.clone(),
+        packed.endian,
+    )
+    .unwrap();
+
+    let decimated = organized.decimate(4).unwrap();
+    assert_eq!(decimated.dimensions.height, 1);
+    let kept: Vec<PointXYZI> = decimated.try_into_vec().unwrap();
+    assert_eq!(kept, vec![points[0], points[4]]);
+}
+
+#[test]
+fn guess_endian_from_xyz() {
+    let points: Vec<PointXYZI> = (0..100)
+        .map(|i| PointXYZI::new(i as f32 * 0.37, -(i as f32) * 1.3, 2.5, 0.1))
+        .collect();
+
+    for endian in [
+        ros_pointcloud2::Endian::Little,
+        ros_pointcloud2::Endian::Big,
+    ] {
+        let mut msg = PointCloud2Msg::try_from_vec_endian(points.clone(), endian).unwrap();
+        assert_eq!(msg.guess_endian_from_xyz(), Some(endian));
+        msg.endian = match endian {
+            ros_pointcloud2::Endian::Little => ros_pointcloud2::Endian::Big,
+            ros_pointcloud2::Endian::Big => ros_pointcloud2::Endian::Little,
+        };
+        assert_eq!(msg.guess_endian_from_xyz(), Some(endian));
+    }
+
+    let zeros = PointCloud2Msg::try_from_vec(vec![PointXYZ::default(); 4]).unwrap();
+    assert_eq!(zeros.guess_endian_from_xyz(), None);
+    let empty = PointCloud2Msg::schema_only::<3, PointXYZ>(Default::default()).unwrap();
+    assert_eq!(empty.guess_endian_from_xyz(), None);
+    let no_xyz = PointCloud2Msg::try_from_flat_f32_named(vec![1.0], &["a"]).unwrap();
+    assert_eq!(no_xyz.guess_endian_from_xyz(), None);
+}
+
+#[test]
+fn scaled_fields() {
+    let points: Vec<(i32, i32, i32, u16)> = vec![(1000, -2000, 30, 7), (1001, -1999, 31, 8)];
+    let mut msg = PointCloud2Msg::try_from_iter(points).unwrap();
+    for (from, to) in [("0", "x"), ("1", "y"), ("2", "z"), ("3", "intensity")] {
+        msg.rename_field(from, to).unwrap();
+    }
+
+    msg.set_field_transform("x", 0.001, 500_000.0).unwrap();
+    msg.set_field_transform("y", 0.001, 4_000_000.0).unwrap();
+    msg.set_field_transform("y", 0.01, 4_000_000.0).unwrap();
+    assert_eq!(msg.field_transforms.len(), 2);
+    assert!(matches!(
+        msg.set_field_transform("missing", 1.0, 0.0),
+        Err(MsgConversionError::FieldsNotFound(_))
+    ));
+
+    assert_eq!(
+        msg.decode_scaled_field_f64("x").unwrap(),
+        vec![500_001.0, 500_001.001]
+    );
+    assert_eq!(
+        msg.decode_scaled_field_f64("y").unwrap(),
+        vec![3_999_980.0, 3_999_980.01]
+    );
+    assert_eq!(msg.decode_scaled_field_f64("z").unwrap(), vec![30.0, 31.0]);
+    assert_eq!(
+        msg.decode_scaled_field_f64("intensity").unwrap(),
+        vec![7.0, 8.0]
+    );
+
+    // The transforms follow the points and the field names.
+    msg.rename_field("x", "easting").unwrap();
+    let window = msg.decimate(2).unwrap();
+    assert_eq!(
+        window.decode_scaled_field_f64("easting").unwrap(),
+        vec![500_001.0]
+    );
+}
+
+#[test]
+#[cfg(feature = "las")]
+fn las_reader() {
+    let mut file = vec![0u8; 227];
+    file[0..4].copy_from_slice(b"LASF");
+    file[24] = 1;
+    file[25] = 2;
+    file[94..96].copy_from_slice(&227u16.to_le_bytes());
+    file[96..100].copy_from_slice(&227u32.to_le_bytes());
+    file[104] = 3;
+    file[105..107].copy_from_slice(&34u16.to_le_bytes());
+    file[107..111].copy_from_slice(&2u32.to_le_bytes());
+    for (axis, (scale, offset)) in [(0.01, 1000.0), (0.01, 2000.0), (0.001, 0.0)]
+        .into_iter()
+        .enumerate()
+    {
+        file[131 + axis * 8..139 + axis * 8].copy_from_slice(&f64::to_le_bytes(scale));
+        file[155 + axis * 8..163 + axis * 8].copy_from_slice(&f64::to_le_bytes(offset));
+    }
+
+    for (x, y, z, intensity, returns, class, time, red) in [
+        (
+            150i32,
+            -25i32,
+            1234i32,
+            700u16,
+            0b0001_0001u8,
+            2u8,
+            10.5f64,
+            65535u16,
+        ),
+        (-100, 50, -500, 90, 0b0001_0010, 6, 11.25, 256),
+    ] {
+        let mut record = vec![0u8; 34];
+        record[0..4].copy_from_slice(&x.to_le_bytes());
+        record[4..8].copy_from_slice(&y.to_le_bytes());
+        record[8..12].copy_from_slice(&z.to_le_bytes());
+        record[12..14].copy_from_slice(&intensity.to_le_bytes());
+        record[14] = returns;
+        record[15] = class;
+        record[16] = (-12i8) as u8;
+        record[20..28].copy_from_slice(&time.to_le_bytes());
+        record[28..30].copy_from_slice(&red.to_le_bytes());
+        file.extend_from_slice(&record);
+    }
+
+    let msg = ros_pointcloud2::las::from_las_reader(file.as_slice()).unwrap();
+    assert_eq!(msg.len(), 2);
+    assert_eq!(msg.dimensions.height, 1);
+    assert_eq!(
+        msg.decode_scaled_field_f64("x").unwrap(),
+        vec![1001.5, 999.0]
+    );
+    assert_eq!(
+        msg.decode_scaled_field_f64("y").unwrap(),
+        vec![1999.75, 2000.5]
+    );
+    assert_eq!(msg.decode_scaled_field_f64("z").unwrap(), vec![1.234, -0.5]);
+    assert_eq!(
+        msg.decode_scaled_field_f64("gps_time").unwrap(),
+        vec![10.5, 11.25]
+    );
+    assert_eq!(
+        msg.decode_scaled_field_f64("intensity").unwrap(),
+        vec![700.0, 90.0]
+    );
+    assert_eq!(
+        msg.decode_scaled_field_f64("return_number").unwrap(),
+        vec![1.0, 2.0]
+    );
+    assert_eq!(
+        msg.decode_scaled_field_f64("number_of_returns").unwrap(),
+        vec![2.0, 2.0]
+    );
+    assert_eq!(
+        msg.decode_scaled_field_f64("classification").unwrap(),
+        vec![2.0, 6.0]
+    );
+    assert_eq!(
+        msg.decode_scaled_field_f64("scan_angle").unwrap(),
+        vec![-12.0, -12.0]
+    );
+    assert_eq!(
+        msg.decode_scaled_field_f64("red").unwrap(),
+        vec![65535.0, 256.0]
+    );
+
+    let mut laz = file.clone();
+    laz[104] |= 0x80;
+    assert!(matches!(
+        ros_pointcloud2::las::from_las_reader(laz.as_slice()),
+        Err(MsgConversionError::InvalidLas(_))
+    ));
+    assert!(matches!(
+        ros_pointcloud2::las::from_las_reader(&file[..file.len() - 1]),
+        Err(MsgConversionError::InvalidLas(_))
+    ));
+
+    // A huge point count in the header must not allocate before the body runs out.
+    let mut huge = file.clone();
+    huge[107..111].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert!(matches!(
+        ros_pointcloud2::las::from_las_reader(huge.as_slice()),
+        Err(MsgConversionError::InvalidLas(_))
+    ));
+}
+
+#[test]
+fn cast_point_type() {
+    let points = vec![
+        PointXYZI::new(0.0, 0.0, 1.0, 0.1),
+        PointXYZI::new(1.0, 0.0, 2.0, 0.2),
+        PointXYZI::new(0.0, 1.0, 3.0, 0.3),
+        PointXYZI::new(1.0, 1.0, 4.0, 0.4),
+    ];
+    let packed = PointCloud2Msg::try_from_vec(points.clone()).unwrap();
+    let point_step = packed.point_step;
+
+    let mut data = Vec::new();
+    for row in packed.data.chunks_exact(2 * point_step as usize) {
+        data.extend_from_slice(row);
+        data.extend_from_slice(&[0xff; 8]);
+    }
+    let mut msg = PointCloud2Msg::try_from_organized_with_row_step(
+        data,
+        2,
+        2,
+        point_step,
+        2 * point_step + 8,
+        packed.fields.clone(),
+        packed.endian,
+    )
+    .unwrap();
+    msg.header.frame_id = "lidar".into();
+    msg.set_field_transform("intensity", 2.0, 0.0).unwrap();
+    msg.set_field_transform("x", 0.5, 0.0).unwrap();
+
+    let xyz = msg.cast::<4, PointXYZI, 3, PointXYZ>().unwrap();
+    assert_eq!(xyz.header.frame_id, "lidar");
+    assert_eq!(xyz.dimensions.width, 2);
+    assert_eq!(xyz.dimensions.height, 2);
+    assert_eq!(xyz.row_step, 2 * xyz.point_step);
+    assert_eq!(xyz.fields.len(), 3);
+    assert_eq!(xyz.field_transforms.len(), 1);
+    let from_xyz: Vec<PointXYZ> = xyz.clone().try_into_iter().unwrap().collect();
+    assert_eq!(
+        from_xyz,
+        points
+            .iter()
+            .map(|p| PointXYZ::from(*p))
+            .collect::<Vec<_>>()
+    );
+
+    let widened = xyz.cast::<3, PointXYZ, 4, PointXYZI>().unwrap();
+    let from_widened: Vec<PointXYZI> = widened.try_into_iter().unwrap().collect();
+    assert_eq!(from_widened[3], PointXYZI::new(1.0, 1.0, 4.0, 0.0));
+
+    let labeled =
+        PointCloud2Msg::try_from_vec(vec![PointXYZRGBL::new(1.0, 2.0, 3.0, 10, 20, 30, 7)])
+            .unwrap();
+    let xyzl = labeled.cast::<5, PointXYZRGBL, 4, PointXYZL>().unwrap();
+    let from_xyzl: Vec<PointXYZL> = xyzl.try_into_iter().unwrap().collect();
+    assert_eq!(from_xyzl, vec![PointXYZL::new(1.0, 2.0, 3.0, 7)]);
+
+    assert!(msg.cast::<4, PointXYZL, 3, PointXYZ>().is_err());
+
+    // A custom conversion is applied even when the output fields are a prefix of the input.
+    #[derive(Debug, PartialEq, Clone, Copy, Default)]
+    struct Tight {
+        x: f32,
+        y: f32,
+        z: f32,
+    }
+
+    impl From<RPCL2Point<3>> for Tight {
+        fn from(point: RPCL2Point<3>) -> Self {
+            Self {
+                x: point[0].get(),
+                y: point[1].get(),
+                z: point[2].get(),
+            }
+        }
+    }
+
+    impl From<Tight> for RPCL2Point<3> {
+        fn from(point: Tight) -> Self {
+            [point.x.into(), point.y.into(), point.z.into()].into()
+        }
+    }
+
+    unsafe impl PointConvertible<3> for Tight {
+        fn layout() -> LayoutDescription {
+            LayoutDescription::new(&[
+                LayoutField::new("x", "f32", 4),
+                LayoutField::new("y", "f32", 4),
+                LayoutField::new("z", "f32", 4),
+            ])
+        }
+    }
+
+    impl From<Tight> for PointXYZ {
+        fn from(point: Tight) -> Self {
+            PointXYZ::new(point.x * 2.0, point.y, point.z)
+        }
+    }
+
+    let tight = PointCloud2Msg::try_from_iter([Tight {
+        x: 1.0,
+        y: 2.0,
+        z: 3.0,
+    }])
+    .unwrap();
+    let doubled = tight.cast::<3, Tight, 3, PointXYZ>().unwrap();
+    let from_doubled: Vec<PointXYZ> = doubled.try_into_iter().unwrap().collect();
+    assert_eq!(from_doubled, vec![PointXYZ::new(2.0, 2.0, 3.0)]);
+}
+
+#[test]
+fn try_from_vec_zeroes_padding() {
+    let mut raw = [0xabu8; core::mem::size_of::<PointXYZ>()];
+    raw[0..4].copy_from_slice(&1.0f32.to_ne_bytes());
+    raw[4..8].copy_from_slice(&2.0f32.to_ne_bytes());
+    raw[8..12].copy_from_slice(&3.0f32.to_ne_bytes());
+    let point: PointXYZ = unsafe { core::ptr::read_unaligned(raw.as_ptr().cast()) };
+
+    let msg = PointCloud2Msg::try_from_vec(vec![point; 2]).unwrap();
+    assert_eq!(msg.point_step, 16);
+    for point in msg.data.chunks_exact(16) {
+        assert_eq!(&point[12..], &[0; 4]);
+    }
+
+    let points: Vec<PointXYZ> = msg.try_into_vec().unwrap();
+    assert_eq!(points, vec![PointXYZ::new(1.0, 2.0, 3.0); 2]);
+}
+
+#[test]
+fn enum_field_values() {
+    #[derive(Debug, PartialEq, Clone, Copy, Default)]
+    enum Class {
+        #[default]
+        Unclassified,
+        Ground,
+        Building,
+    }
+
+    impl FromFieldValue for Class {
+        type Repr = u8;
+
+        fn from_value(value: u8) -> Self {
+            match value {
+                2 => Class::Ground,
+                6 => Class::Building,
+                _ => Class::Unclassified,
+            }
+        }
+
+        fn to_value(&self) -> u8 {
+            match self {
+                Class::Unclassified => 1,
+                Class::Ground => 2,
+                Class::Building => 6,
+            }
+        }
+    }
+
+    // Not `Copy`, so the direct copy conversions can not produce invalid enum values.
+    #[derive(Debug, PartialEq, Clone, Default)]
+    struct ClassPoint {
+        x: f32,
+        class: Class,
+    }
+
+    impl From<ClassPoint> for RPCL2Point<2> {
+        fn from(point: ClassPoint) -> Self {
+            [point.x.into(), PointData::from_field_value(&point.class)].into()
+        }
+    }
+
+    impl From<RPCL2Point<2>> for ClassPoint {
+        fn from(point: RPCL2Point<2>) -> Self {
+            Self {
+                x: point[0].get(),
+                class: point[1].get_field_value(),
+            }
+        }
+    }
+
+    unsafe impl PointConvertible<2> for ClassPoint {
+        fn layout() -> LayoutDescription {
+            LayoutDescription::new(&[
+                LayoutField::new("x", "f32", 4),
+                LayoutField::new("class", "u8", 1),
+                LayoutField::padding(3),
+            ])
+        }
+    }
+
+    let points = vec![
+        ClassPoint {
+            x: 1.0,
+            class: Class::Ground,
+        },
+        ClassPoint {
+            x: 2.0,
+            class: Class::Building,
+        },
+    ];
+    let mut msg = PointCloud2Msg::try_from_iter(points.clone()).unwrap();
+    assert_eq!(msg.fields[1].datatype, u8::from(FieldDatatype::U8));
+    assert_eq!(
+        msg.decode_scaled_field_f64("class").unwrap(),
+        vec![2.0, 6.0]
+    );
+
+    let decoded: Vec<ClassPoint> = msg.clone().try_into_iter().unwrap().collect();
+    assert_eq!(decoded, points);
+
+    // Values without a variant decode to the fallback.
+    let offset = msg.fields[1].offset as usize;
+    msg.data[offset] = 42;
+    let decoded: Vec<ClassPoint> = msg.try_into_iter().unwrap().collect();
+    assert_eq!(decoded[0].class, Class::Unclassified);
+}
+
+#[test]
+fn stack_organized_rows() {
+    let row = |y: f32| (0..3).map(move |x| PointXYZI::new(x as f32, y, 1.0, y));
+    let first = PointCloud2Msg::try_from_vec(row(0.0).collect()).unwrap();
+    let point_step = first.point_step;
+    let row_step = 3 * point_step + 4;
+
+    let padded = |rows: &[f32]| {
+        let mut data = Vec::new();
+        for &y in rows {
+            data.extend_from_slice(
+                &PointCloud2Msg::try_from_vec(row(y).collect::<Vec<_>>())
+                    .unwrap()
+                    .data,
+            );
+            data.extend_from_slice(&[0xff; 4]);
+        }
+        PointCloud2Msg::try_from_organized_with_row_step(
+            data,
+            3,
+            rows.len() as u32,
+            point_step,
+            row_step,
+            first.fields.clone(),
+            first.endian,
+        )
+        .unwrap()
+    };
+
+    let mut frame = padded(&[0.0, 1.0]);
+    frame.header.frame_id = "lidar".into();
+    let mut batch = padded(&[2.0, 3.0]);
+    batch.header.frame_id = "other".into();
+    frame.stack_rows(&batch).unwrap();
+
+    assert_eq!(frame.dimensions.width, 3);
+    assert_eq!(frame.dimensions.height, 4);
+    assert_eq!(frame.row_step, row_step);
+    assert_eq!(frame.data.len(), 4 * row_step as usize);
+    assert_eq!(frame.header.frame_id, "lidar");
+    let points: Vec<PointXYZI> = frame.clone().try_into_iter().unwrap().collect();
+    let expected: Vec<PointXYZI> = [0.0, 1.0, 2.0, 3.0].into_iter().flat_map(row).collect();
+    assert_eq!(points, expected);
+
+    let mut packed = PointCloud2Msg::try_from_vec(row(0.0).collect()).unwrap();
+    packed
+        .stack_rows(&PointCloud2Msg::try_from_vec(row(1.0).collect()).unwrap())
+        .unwrap();
+    assert_eq!(packed.dimensions.height, 2);
+    assert_eq!(packed.data.len(), 6 * point_step as usize);
+
+    assert!(matches!(
+        frame.stack_rows(&packed),
+        Err(MsgConversionError::InvalidArgument)
+    ));
+    let narrow = PointCloud2Msg::try_from_vec(vec![PointXYZI::default(); 2]).unwrap();
+    assert!(matches!(
+        packed.stack_rows(&narrow),
+        Err(MsgConversionError::InvalidArgument)
+    ));
+    let other_schema = PointCloud2Msg::try_from_vec(vec![PointXYZL::default(); 3]).unwrap();
+    let mut single = PointCloud2Msg::try_from_vec(row(0.0).collect()).unwrap();
+    assert!(matches!(
+        single.stack_rows(&other_schema),
+        Err(MsgConversionError::InvalidFieldFormat)
+    ));
+}
+
+#[test]
+fn data_fingerprint() {
+    let msg = PointCloud2Msg::try_from_iter(vec![PointXYZI::new(1.0, 2.0, 3.0, 0.5)]).unwrap();
+    // The value must never change, since fingerprints are persisted as cache keys.
+    assert_eq!(msg.data_fingerprint(), 4897207469589926895);
+
+    let mut stamped = msg.clone();
+    stamped.header.stamp.sec = 100;
+    stamped.header.frame_id = "map".into();
+    assert_eq!(stamped.data_fingerprint(), msg.data_fingerprint());
+
+    let mut renamed = msg.clone();
+    renamed.rename_field("intensity", "i").unwrap();
+    assert_ne!(renamed.data_fingerprint(), msg.data_fingerprint());
+
+    let mut swapped = msg.clone();
+    swapped.endian = ros_pointcloud2::Endian::Big;
+    assert_ne!(swapped.data_fingerprint(), msg.data_fingerprint());
+
+    let mut changed = msg.clone();
+    changed.data[0] ^= 1;
+    assert_ne!(changed.data_fingerprint(), msg.data_fingerprint());
+}
+
+#[test]
+#[cfg(feature = "smallvec")]
+fn dynamic_points() {
+    let points = vec![
+        PointXYZRGBNormal::new(1.0, 2.0, 3.0, RGB::new(10, 20, 30), 0.0, 0.0, 1.0),
+        PointXYZRGBNormal::new(4.0, 5.0, 6.0, RGB::new(40, 50, 60), 1.0, 0.0, 0.0),
+    ];
+    let msg = PointCloud2Msg::try_from_vec(points.clone()).unwrap();
+
+    let decoded: Vec<DynamicPoint> = msg.dynamic_iter().unwrap().collect();
+    assert_eq!(decoded.len(), 2);
+    for (point, expected) in decoded.iter().zip(points.iter()) {
+        assert!(!point.spilled());
+        assert_eq!(point.len(), 7);
+        assert_eq!(point[0].get::<f32>(), expected.x);
+        assert_eq!(point[3].get::<RGB>(), expected.rgb);
+        assert_eq!(point[6].get::<f32>(), expected.normal_z);
+    }
+
+    let swapped =
+        PointCloud2Msg::try_from_vec_endian(points, ros_pointcloud2::Endian::Big).unwrap();
+    let decoded: Vec<DynamicPoint> = swapped.dynamic_iter().unwrap().collect();
+    assert_eq!(decoded[1][1].get::<f32>(), 5.0);
+
+    // Array fields yield one value per element and spill beyond 8 values.
+    let wide = ros_pointcloud2::PointCloud2MsgBuilder::new()
+        .fields(vec![ros_pointcloud2::ros::PointFieldMsg {
+            name: "descriptor".into(),
+            offset: 0,
+            datatype: FieldDatatype::F32.into(),
+            count: 9,
+        }])
+        .point_step(36)
+        .width(1)
+        .row_step(36)
+        .data((0..9).flat_map(|i| (i as f32).to_ne_bytes()).collect())
+        .build()
+        .unwrap();
+    let decoded: Vec<DynamicPoint> = wide.dynamic_iter().unwrap().collect();
+    assert_eq!(decoded[0].len(), 9);
+    assert!(decoded[0].spilled());
+    assert_eq!(decoded[0][8].get::<f32>(), 8.0);
+}
+
+#[test]
+fn from_xyz_iter() {
+    let sphere: Vec<(f32, f32, f32)> = (0..8)
+        .map(|i| {
+            let angle = i as f32 * core::f32::consts::FRAC_PI_4;
+            (angle.cos(), angle.sin(), 0.0)
+        })
+        .collect();
+    let msg = PointCloud2Msg::try_from_xyz_iter(sphere.iter().copied()).unwrap();
+    assert_eq!(msg.len(), 8);
+    assert_eq!(msg.dimensions.height, 1);
+    assert_eq!(msg.iter_coords().unwrap().collect::<Vec<_>>(), sphere);
+
+    let points: Vec<PointXYZ> = msg.try_into_iter().unwrap().collect();
+    assert_eq!(points[2], PointXYZ::new(sphere[2].0, sphere[2].1, 0.0));
+
+    let empty = PointCloud2Msg::try_from_xyz_iter(core::iter::empty()).unwrap();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn to_xyz_vec() {
+    let points = vec![
+        PointXYZRGBNormal::new(1.0, 2.0, 3.0, RGB::new(1, 2, 3), 0.0, 0.0, 1.0),
+        PointXYZRGBNormal::new(f32::NAN, 5.0, 6.0, RGB::new(4, 5, 6), 1.0, 0.0, 0.0),
+    ];
+    let msg = PointCloud2Msg::try_from_vec(points).unwrap();
+    let xyz = msg.to_xyz_vec().unwrap();
+    assert_eq!(xyz.len(), 2);
+    assert_eq!(xyz[0], [1.0, 2.0, 3.0]);
+    assert!(xyz[1][0].is_nan());
+    assert_eq!(&xyz[1][1..], &[5.0, 6.0]);
+
+    let no_xyz = PointCloud2Msg::try_from_vec(vec![PointNormal::default()]).unwrap();
+    assert!(matches!(
+        no_xyz.to_xyz_vec(),
+        Err(MsgConversionError::FieldsNotFound(_))
+    ));
+}
+
+#[test]
+fn from_iter_organized() {
+    let grid: Vec<PointXYZI> = (0..6)
+        .map(|idx| {
+            if idx == 4 {
+                PointXYZI::new(f32::NAN, f32::NAN, f32::NAN, 0.0)
+            } else {
+                PointXYZI::new((idx % 3) as f32, (idx / 3) as f32, 1.0, idx as f32)
+            }
+        })
+        .collect();
+
+    let msg = PointCloud2Msg::try_from_iter_organized(grid.clone(), 3, 2).unwrap();
+    assert_eq!(msg.dimensions.width, 3);
+    assert_eq!(msg.dimensions.height, 2);
+    assert_eq!(msg.row_step, 3 * msg.point_step);
+    assert_eq!(msg.dense, ros_pointcloud2::Denseness::Sparse);
+
+    let decoded: Vec<PointXYZI> = msg.clone().try_into_iter().unwrap().collect();
+    assert!(decoded[4].x.is_nan());
+    assert_eq!(decoded[5], grid[5]);
+    let row: Vec<PointXYZI> = msg.row_points(1).unwrap();
+    assert_eq!(row[0], grid[3]);
+
+    let finite =
+        PointCloud2Msg::try_from_iter_organized(vec![PointXYZ::new(1.0, 2.0, 3.0); 4], 2, 2)
+            .unwrap();
+    assert_eq!(finite.dense, ros_pointcloud2::Denseness::Dense);
+
+    assert!(matches!(
+        PointCloud2Msg::try_from_iter_organized(grid, 4, 2),
+        Err(MsgConversionError::DataLengthMismatch)
+    ));
+}
+
+#[test]
+fn reduce_field() {
+    let msg = PointCloud2Msg::try_from_vec(vec![
+        PointXYZL::new(0.0, 0.0, 0.0, 1),
+        PointXYZL::new(1.0, 0.0, 0.0, 3),
+        PointXYZL::new(2.0, 0.0, 0.0, 1),
+    ])
+    .unwrap();
+
+    let histogram = msg
+        .reduce_field("label", [0usize; 4], |mut bins, label: u32| {
+            bins[label as usize] += 1;
+            bins
+        })
+        .unwrap();
+    assert_eq!(histogram, [0, 2, 0, 1]);
+
+    let max_x = msg
+        .reduce_field("x", f32::MIN, |max, x: f32| max.max(x))
+        .unwrap();
+    assert_eq!(max_x, 2.0);
+
+    assert!(matches!(
+        msg.reduce_field("label", 0.0, |sum, v: f32| sum + v),
+        Err(MsgConversionError::InvalidFieldFormat)
+    ));
+    assert!(matches!(
+        msg.reduce_field("ring", 0, |sum, v: u16| sum + v),
+        Err(MsgConversionError::FieldsNotFound(_))
+    ));
+}
+
+#[test]
+fn trailing_bytes() {
+    let points = vec![
+        PointXYZI::new(1.0, 2.0, 3.0, 0.5),
+        PointXYZI::new(4.0, 5.0, 6.0, 1.5),
+    ];
+    let mut msg = PointCloud2Msg::try_from_vec(points.clone()).unwrap();
+    assert_eq!(msg.trailing_bytes(), 0);
+
+    // A partial point appended by a recorder.
+    msg.data.extend_from_slice(&[0xff; 7]);
+    assert_eq!(msg.trailing_bytes(), 7);
+    assert_eq!(msg.len(), 2);
+
+    let from_iter: Vec<PointXYZI> = msg.clone().try_into_iter().unwrap().collect();
+    assert_eq!(from_iter, points);
+    let from_vec: Vec<PointXYZI> = msg.clone().try_into_vec().unwrap();
+    assert_eq!(from_vec, points);
+    let xyz: Vec<PointXYZ> = msg.clone().try_into_iter().unwrap().collect();
+    assert_eq!(xyz.len(), 2);
+
+    let mut organized = PointCloud2Msg::try_from_vec(vec![PointXYZ::default(); 4]).unwrap();
+    organized.dimensions.width = 2;
+    organized.dimensions.height = 2;
+    organized.row_step = 2 * organized.point_step + 4;
+    organized.data = vec![0; 2 * organized.row_step as usize + 3];
+    assert_eq!(organized.trailing_bytes(), 3);
+    assert_eq!(organized.try_into_iter::<3, PointXYZ>().unwrap().count(), 4);
+
+    // The builder accepts the same message and keeps the trailing bytes.
+    let built = ros_pointcloud2::PointCloud2MsgBuilder::new()
+        .with_schema_from(&msg)
+        .width(2)
+        .row_step(msg.row_step)
+        .data(msg.data.clone())
+        .build()
+        .unwrap();
+    assert_eq!(built.trailing_bytes(), 7);
+    let from_builder: Vec<PointXYZI> = built.try_into_iter().unwrap().collect();
+    assert_eq!(from_builder, points);
+    assert!(matches!(
+        ros_pointcloud2::PointCloud2MsgBuilder::new()
+            .with_schema_from(&msg)
+            .width(3)
+            .data(msg.data.clone())
+            .build(),
+        Err(MsgConversionError::DataLengthMismatch)
+    ));
+
+    msg.data.truncate(msg.point_step as usize);
+    assert_eq!(msg.trailing_bytes(), 0);
+}
+
+#[test]
+fn set_field_f32() {
+    let points = vec![
+        PointXYZI::new(0.0, 0.0, 1.0, 0.1),
+        PointXYZI::new(1.0, 0.0, 2.0, 0.2),
+        PointXYZI::new(0.0, 1.0, 3.0, 0.3),
+        PointXYZI::new(1.0, 1.0, 4.0, 0.4),
+    ];
+    let packed = PointCloud2Msg::try_from_vec(points.clone()).unwrap();
+    let point_step = packed.point_step;
+    let mut data = Vec::new();
+    for row in packed.data.chunks_exact(2 * point_step as usize) {
+        data.extend_from_slice(row);
+        data.extend_from_slice(&[0xff; 8]);
+    }
+    let mut msg = PointCloud2Msg::try_from_organized_with_row_step(
+        data,
+        2,
+        2,
+        point_step,
+        2 * point_step + 8,
+        packed.fields.clone(),
+        packed.endian,
+    )
+    .unwrap();
+
+    msg.set_field_f32("intensity", &[1.0, 2.0, 3.0, 4.0])
+        .unwrap();
+    let decoded: Vec<PointXYZI> = msg.clone().try_into_iter().unwrap().collect();
+    for (idx, (point, original)) in decoded.iter().zip(points.iter()).enumerate() {
+        assert_eq!(point.intensity, (idx + 1) as f32);
+        assert_eq!(point.z, original.z);
+    }
+    let row_padding = 2 * point_step as usize;
+    assert_eq!(&msg.data[row_padding..row_padding + 8], &[0xff; 8]);
+
+    let mut big =
+        PointCloud2Msg::try_from_vec_endian(points.clone(), ros_pointcloud2::Endian::Big).unwrap();
+    big.set_field_f32("x", &[9.0; 4]).unwrap();
+    let decoded: Vec<PointXYZI> = big.try_into_iter().unwrap().collect();
+    assert!(decoded.iter().all(|point| point.x == 9.0));
+
+    assert!(matches!(
+        msg.set_field_f32("intensity", &[1.0]),
+        Err(MsgConversionError::DataLengthMismatch)
+    ));
+    assert!(matches!(
+        msg.set_field_f32("ring", &[1.0; 4]),
+        Err(MsgConversionError::FieldsNotFound(_))
+    ));
+    let mut labeled = PointCloud2Msg::try_from_vec(vec![PointXYZL::default()]).unwrap();
+    assert!(matches!(
+        labeled.set_field_f32("label", &[1.0]),
+        Err(MsgConversionError::InvalidFieldFormat)
+    ));
+}
+
+#[test]
+fn widen_to() {
+    let mut msg = PointCloud2Msg::try_from_iter_organized(
+        vec![
+            PointXYZL::new(0.0, 0.0, 1.0, 3),
+            PointXYZL::new(1.0, 0.0, 1.0, 4),
+            PointXYZL::new(0.0, 1.0, 1.0, 5),
+            PointXYZL::new(1.0, 1.0, 1.0, 6),
+        ],
+        2,
+        2,
+    )
+    .unwrap();
+    msg.header.frame_id = "camera".into();
+
+    let wide = msg.widen_to::<5, PointXYZRGBL>().unwrap();
+    assert_eq!(wide.header.frame_id, "camera");
+    assert_eq!(wide.dimensions.width, 2);
+    assert_eq!(wide.dimensions.height, 2);
+    assert_eq!(wide.fields.len(), 5);
+    let points: Vec<PointXYZRGBL> = wide.try_into_iter().unwrap().collect();
+    assert_eq!(points[3], PointXYZRGBL::new(1.0, 1.0, 1.0, 0, 0, 0, 6));
+
+    let no_xyz = PointCloud2Msg::try_from_vec(vec![PointNormal::default()]).unwrap();
+    assert!(no_xyz.widen_to::<4, PointXYZI>().is_err());
+}
+
+#[test]
+fn as_xyz_slice() {
+    let coords = [(1.0, 2.0, 3.0), (4.0, 5.0, 6.0), (7.0, 8.0, 9.0)];
+    let mut msg = PointCloud2Msg::try_from_xyz_iter(coords).unwrap();
+    assert_eq!(msg.point_step, 12);
+    assert_eq!(
+        msg.as_xyz_slice().unwrap(),
+        &[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]
+    );
+    assert_eq!(
+        msg.as_xyz_slice().unwrap(),
+        msg.to_xyz_vec().unwrap().as_slice()
+    );
+
+    // Trailing bytes are not part of the view.
+    msg.data.extend_from_slice(&[0; 12]);
+    assert_eq!(msg.as_xyz_slice().unwrap().len(), 3);
+
+    let empty = PointCloud2Msg::try_from_xyz_iter(core::iter::empty()).unwrap();
+    assert!(empty.as_xyz_slice().unwrap().is_empty());
+
+    let with_intensity =
+        PointCloud2Msg::try_from_iter(vec![PointXYZI::new(1.0, 2.0, 3.0, 0.5)]).unwrap();
+    assert!(matches!(
+        with_intensity.as_xyz_slice(),
+        Err(MsgConversionError::InvalidFieldFormat)
+    ));
+
+    let swapped = PointCloud2Msg::try_from_vec_endian(
+        vec![PointXYZ::new(1.0, 2.0, 3.0)],
+        ros_pointcloud2::Endian::Big,
+    )
+    .unwrap()
+    .repack_tight()
+    .unwrap();
+    assert!(matches!(
+        swapped.as_xyz_slice(),
+        Err(MsgConversionError::InvalidFieldFormat)
+    ));
+
+    let mut short = PointCloud2Msg::try_from_xyz_iter(coords).unwrap();
+    short.data.truncate(20);
+    assert!(matches!(
+        short.as_xyz_slice(),
+        Err(MsgConversionError::DataLengthMismatch)
+    ));
+}
+
+#[test]
+fn borrow_slice_and_owned_vec() {
+    let cloud = vec![
+        PointXYZI::new(1.0, 2.0, 3.0, 0.5),
+        PointXYZI::new(4.0, 5.0, 6.0, 1.5),
+    ];
+    let msg = PointCloud2Msg::try_from_vec(cloud.clone()).unwrap();
+
+    let borrowed: &[PointXYZI] = msg.try_borrow_slice().unwrap();
+    assert_eq!(borrowed, &cloud[..]);
+    assert_eq!(borrowed.as_ptr().cast::<u8>(), msg.data.as_ptr());
+    assert_eq!(msg.to_owned_vec::<4, PointXYZI>().unwrap(), cloud);
+
+    // The padding of `PointXYZ` covers the intensity, so the points can be borrowed as well.
+    let xyz = vec![PointXYZ::new(1.0, 2.0, 3.0), PointXYZ::new(4.0, 5.0, 6.0)];
+    assert_eq!(msg.try_borrow_slice::<3, PointXYZ>().unwrap(), &xyz[..]);
+
+    // Without the padding, the points can only be decoded into an owned Vec.
+    let tight = PointCloud2Msg::try_from_xyz_iter([(1.0, 2.0, 3.0), (4.0, 5.0, 6.0)]).unwrap();
+    assert!(matches!(
+        tight.try_borrow_slice::<3, PointXYZ>(),
+        Err(MsgConversionError::InvalidFieldFormat)
+    ));
+    assert_eq!(tight.to_owned_vec::<3, PointXYZ>().unwrap(), xyz);
+
+    let swapped =
+        PointCloud2Msg::try_from_vec_endian(cloud.clone(), ros_pointcloud2::Endian::Big).unwrap();
+    assert!(matches!(
+        swapped.try_borrow_slice::<4, PointXYZI>(),
+        Err(MsgConversionError::InvalidFieldFormat)
+    ));
+    assert_eq!(swapped.to_owned_vec::<4, PointXYZI>().unwrap(), cloud);
+
+    let mut short = msg.clone();
+    short.data.truncate(20);
+    assert!(matches!(
+        short.try_borrow_slice::<4, PointXYZI>(),
+        Err(MsgConversionError::DataLengthMismatch)
+    ));
+
+    let empty = PointCloud2Msg::try_from_vec(Vec::<PointXYZI>::new()).unwrap();
+    assert!(empty.try_borrow_slice::<4, PointXYZI>().unwrap().is_empty());
+    assert!(empty.to_owned_vec::<4, PointXYZI>().unwrap().is_empty());
+}
+
+#[test]
+fn detect_point_type() {
+    let xyz = PointCloud2Msg::try_from_vec(vec![PointXYZ::new(1.0, 2.0, 3.0)]).unwrap();
+    assert_eq!(xyz.detect_point_type(), Some("PointXYZ"));
+    assert_eq!(
+        xyz.repack_tight().unwrap().detect_point_type(),
+        Some("PointXYZ")
+    );
+
+    let xyzi = PointCloud2Msg::try_from_vec(vec![PointXYZI::new(1.0, 2.0, 3.0, 0.5)]).unwrap();
+    assert_eq!(xyzi.detect_point_type(), Some("PointXYZI"));
+
+    let rgbl =
+        PointCloud2Msg::try_from_vec(vec![PointXYZRGBL::new(1.0, 2.0, 3.0, 1, 2, 3, 4)]).unwrap();
+    assert_eq!(rgbl.detect_point_type(), Some("PointXYZRGBL"));
+
+    let normal = PointCloud2Msg::try_from_vec(vec![PointXYZINormal::new(
+        1.0, 2.0, 3.0, 0.5, 0.0, 0.0, 1.0,
+    )])
+    .unwrap();
+    assert_eq!(normal.detect_point_type(), Some("PointXYZINormal"));
+
+    // The field order does not matter.
+    let mut reordered = xyzi.clone();
+    reordered.fields.reverse();
+    assert_eq!(reordered.detect_point_type(), Some("PointXYZI"));
+
+    // A different datatype or an additional field is not a predefined type.
+    let mut wide = xyz.clone();
+    wide.fields[0].datatype = FieldDatatype::F64.into();
+    assert_eq!(wide.detect_point_type(), None);
+
+    let custom = PointCloud2Msg::try_from_vec(vec![(1.0f32, 2.0f32, 3.0f32, 4u8)]).unwrap();
+    assert_eq!(custom.detect_point_type(), None);
+}
+
+#[test]
+fn builder_strict() {
+    use ros_pointcloud2::ros::PointFieldMsg;
+
+    let field = |name: &str, offset, datatype, count| PointFieldMsg {
+        name: name.into(),
+        offset,
+        datatype,
+        count,
+    };
+    let xyz = vec![
+        field("x", 0, 7, 1),
+        field("y", 4, 7, 1),
+        field("z", 8, 7, 1),
+    ];
+    let build = |fields: Vec<PointFieldMsg>, point_step| {
+        ros_pointcloud2::PointCloud2MsgBuilder::new()
+            .fields(fields)
+            .point_step(point_step)
+            .width(1)
+            .row_step(point_step)
+            .data(vec![0; point_step as usize])
+    };
+
+    let msg = build(xyz.clone(), 12).build_strict().unwrap();
+    assert_eq!(msg.point_step, 12);
+
+    // Undeclared padding at the end of the point.
+    assert!(build(xyz.clone(), 16).build().is_ok());
+    assert!(matches!(
+        build(xyz.clone(), 16).build_strict(),
+        Err(MsgConversionError::InvalidFieldFormat)
+    ));
+
+    // Declared padding and any field order are accepted.
+    let mut padded = xyz.clone();
+    padded.insert(0, field("_", 12, 2, 4));
+    assert!(build(padded, 16).build_strict().is_ok());
+
+    // A gap between the fields.
+    let gap = vec![field("x", 0, 7, 1), field("y", 8, 7, 1)];
+    assert!(build(gap.clone(), 12).build().is_ok());
+    assert!(matches!(
+        build(gap, 12).build_strict(),
+        Err(MsgConversionError::InvalidFieldFormat)
+    ));
+
+    // Fields that do not start at the beginning of the point.
+    let shifted = vec![field("x", 4, 7, 1), field("y", 8, 7, 1)];
+    assert!(matches!(
+        build(shifted, 12).build_strict(),
+        Err(MsgConversionError::InvalidFieldFormat)
+    ));
+
+    // Overlapping fields are never contiguous.
+    let overlap = vec![field("rgb", 0, 7, 1), field("rgba", 0, 6, 1)];
+    assert!(matches!(
+        build(overlap, 4)
+            .allow_overlapping_fields(true)
+            .build_strict(),
+        Err(MsgConversionError::InvalidFieldFormat)
+    ));
+
+    // The point step is too short, which the lenient build rejects as well.
+    assert!(build(xyz.clone(), 8).build().is_err());
+    assert!(build(xyz, 8).build_strict().is_err());
+}
+
+#[test]
+fn for_each_mut() {
+    let cloud = vec![
+        PointXYZI::new(1.0, 2.0, 3.0, 0.5),
+        PointXYZI::new(4.0, 5.0, 6.0, 1.5),
+    ];
+    let expected = vec![
+        PointXYZI::new(1.0, 2.0, 3.0, 1.0),
+        PointXYZI::new(4.0, 5.0, 6.0, 3.0),
+    ];
+
+    let mut msg = PointCloud2Msg::try_from_vec(cloud.clone()).unwrap();
+    msg.try_for_each_mut(|point: &mut PointXYZI| point.intensity *= 2.0)
+        .unwrap();
+    assert_eq!(msg.try_into_vec::<4, PointXYZI>().unwrap(), expected);
+
+    // Reversed field order, so the points can not be borrowed as a slice.
+    let mut reordered = PointCloud2Msg::try_from_vec(cloud.clone()).unwrap();
+    reordered.fields.reverse();
+    assert!(reordered.try_borrow_slice::<4, PointXYZI>().is_err());
+    reordered
+        .try_for_each_mut(|point: &mut PointXYZI| point.intensity *= 2.0)
+        .unwrap();
+    assert_eq!(reordered.try_into_vec::<4, PointXYZI>().unwrap(), expected);
+
+    // Fields that are not part of the point type stay untouched.
+    let mut narrow = PointCloud2Msg::try_from_vec(cloud.clone()).unwrap();
+    narrow
+        .try_for_each_mut(|point: &mut PointXYZ| point.x = -point.x)
+        .unwrap();
+    let points: Vec<PointXYZI> = narrow.try_into_vec().unwrap();
+    assert_eq!(
+        points,
+        vec![
+            PointXYZI::new(-1.0, 2.0, 3.0, 0.5),
+            PointXYZI::new(-4.0, 5.0, 6.0, 1.5),
+        ]
+    );
+
+    let mut swapped =
+        PointCloud2Msg::try_from_vec_endian(cloud.clone(), ros_pointcloud2::Endian::Big).unwrap();
+    swapped
+        .try_for_each_mut(|point: &mut PointXYZI| point.intensity *= 2.0)
+        .unwrap();
+    assert_eq!(swapped.endian, ros_pointcloud2::Endian::Big);
+    let points: Vec<PointXYZI> = swapped.try_into_iter().unwrap().collect();
+    assert_eq!(points, expected);
+
+    // A datatype that differs from the message is rejected before writing.
+    let mut wide = PointCloud2Msg::try_from_vec(cloud.clone())
+        .unwrap()
+        .convert_field_type("x", FieldDatatype::F64)
+        .unwrap();
+    let data = wide.data.clone();
+    assert!(matches!(
+        wide.try_for_each_mut(|point: &mut PointXYZ| point.x = 0.0),
+        Err(MsgConversionError::InvalidFieldFormat)
+    ));
+    assert_eq!(wide.data, data);
+
+    let mut missing = PointCloud2Msg::try_from_vec(vec![PointXYZ::new(1.0, 2.0, 3.0)]).unwrap();
+    assert!(missing.try_for_each_mut(|_: &mut PointXYZI| {}).is_err());
+}
+
+#[test]
+#[cfg(feature = "arrow")]
+fn arrow_record_batch() {
+    use ros_pointcloud2::ros::PointFieldMsg;
+
+    let cloud = vec![
+        PointXYZI::new(1.0, 2.0, 3.0, 0.5),
+        PointXYZI::new(4.0, 5.0, 6.0, 1.5),
+        PointXYZI::new(7.0, 8.0, 9.0, 2.5),
+    ];
+    let msg = PointCloud2Msg::try_from_vec(cloud.clone()).unwrap();
+
+    let batch = msg.to_arrow().unwrap();
+    assert_eq!(batch.num_rows(), 3);
+    let schema = batch.schema();
+    let columns: Vec<(String, String)> = schema
+        .fields()
+        .iter()
+        .map(|field| (field.name().clone(), field.data_type().to_string()))
+        .collect();
+    assert_eq!(
+        columns,
+        vec![
+            ("x".to_string(), "Float32".to_string()),
+            ("y".to_string(), "Float32".to_string()),
+            ("z".to_string(), "Float32".to_string()),
+            ("intensity".to_string(), "Float32".to_string()),
+        ]
+    );
+
+    // The padding of the point type is dropped.
+    let back = PointCloud2Msg::try_from_arrow(&batch).unwrap();
+    assert_eq!(back.point_step, 16);
+    assert_eq!(back.dense, ros_pointcloud2::Denseness::Dense);
+    let points: Vec<PointXYZI> = back.try_into_iter().unwrap().collect();
+    assert_eq!(points, cloud);
+
+    let sliced = PointCloud2Msg::try_from_arrow(&batch.slice(1, 2)).unwrap();
+    let points: Vec<PointXYZI> = sliced.try_into_iter().unwrap().collect();
+    assert_eq!(points, cloud[1..]);
+
+    // Values are converted to the endian of the system.
+    let swapped =
+        PointCloud2Msg::try_from_vec_endian(cloud.clone(), ros_pointcloud2::Endian::Big).unwrap();
+    let back = PointCloud2Msg::try_from_arrow(&swapped.to_arrow().unwrap()).unwrap();
+    let points: Vec<PointXYZI> = back.try_into_iter().unwrap().collect();
+    assert_eq!(points, cloud);
+
+    // Fields with a count become fixed size lists.
+    let mut data = Vec::new();
+    for point in 0..2u16 {
+        data.extend_from_slice(&f64::from(point).to_le_bytes());
+        for value in 0..3u16 {
+            data.extend_from_slice(&(point * 10 + value).to_le_bytes());
+        }
+        data.extend_from_slice(&[0; 2]);
+    }
+    let histogram = ros_pointcloud2::PointCloud2MsgBuilder::new()
+        .fields(vec![
+            PointFieldMsg {
+                name: "t".into(),
+                offset: 0,
+                datatype: FieldDatatype::F64.into(),
+                count: 1,
+            },
+            PointFieldMsg {
+                name: "bins".into(),
+                offset: 8,
+                datatype: FieldDatatype::U16.into(),
+                count: 3,
+            },
+        ])
+        .point_step(16)
+        .width(2)
+        .row_step(32)
+        .data(data)
+        .build()
+        .unwrap();
+    let batch = histogram.to_arrow().unwrap();
+    assert!(batch
+        .column_by_name("bins")
+        .unwrap()
+        .data_type()
+        .to_string()
+        .starts_with("FixedSizeList"));
+    let back = PointCloud2Msg::try_from_arrow(&batch).unwrap();
+    assert_eq!(back.point_step, 14);
+    assert_eq!(back.fields[1].count, 3);
+    let bins: Vec<u16> = (0..2)
+        .flat_map(|point| {
+            let start = point * 14 + 8;
+            back.data[start..start + 6]
+                .chunks_exact(2)
+                .map(|value| u16::from_ne_bytes([value[0], value[1]]))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    assert_eq!(bins, vec![0, 1, 2, 10, 11, 12]);
+
+    let sparse = PointCloud2Msg::try_from_vec(vec![PointXYZ::new(f32::NAN, 0.0, 0.0)]).unwrap();
+    let back = PointCloud2Msg::try_from_arrow(&sparse.to_arrow().unwrap()).unwrap();
+    assert_eq!(back.dense, ros_pointcloud2::Denseness::Sparse);
+}
+
+#[test]
+#[cfg(feature = "test-util")]
+fn roundtrip_check() {
+    use ros_pointcloud2::test_util::assert_roundtrip;
+
+    assert_roundtrip(&[
+        PointXYZ::new(1.0, 2.0, 3.0),
+        PointXYZ::new(f32::MAX, f32::MIN, 0.0),
+    ])
+    .unwrap();
+    assert_roundtrip(&[PointXYZRGBNormal::new(
+        1.0,
+        2.0,
+        3.0,
+        RGB::new(10, 20, 30),
+        0.0,
+        0.0,
+        1.0,
+    )])
+    .unwrap();
+    assert_roundtrip::<3, PointXYZ>(&[]).unwrap();
+
+    // Reads the values in the wrong order.
+    #[derive(Debug, PartialEq, Clone, Default)]
+    struct SwappedPoint {
+        x: f32,
+        y: f32,
+        label: u8,
+    }
+
+    impl From<RPCL2Point<3>> for SwappedPoint {
+        fn from(point: RPCL2Point<3>) -> Self {
+            Self {
+                x: point[1].get(),
+                y: point[0].get(),
+                label: point[2].get(),
+            }
+        }
+    }
+
+    impl From<SwappedPoint> for RPCL2Point<3> {
+        fn from(point: SwappedPoint) -> Self {
+            [point.x.into(), point.y.into(), point.label.into()].into()
+        }
+    }
+
+    unsafe impl PointConvertible<3> for SwappedPoint {
+        fn layout() -> LayoutDescription {
+            LayoutDescription::new(&[
+                LayoutField::new("x", "f32", 4),
+                LayoutField::new("y", "f32", 4),
+                LayoutField::new("label", "u8", 1),
+                LayoutField::padding(3),
+            ])
+        }
+    }
+
+    let points = [
+        SwappedPoint {
+            x: 1.0,
+            y: 1.0,
+            label: 3,
+        },
+        SwappedPoint {
+            x: 1.0,
+            y: 2.0,
+            label: 3,
+        },
+    ];
+    match assert_roundtrip(&points) {
+        Err(MsgConversionError::RoundtripMismatch { index, fields }) => {
+            assert_eq!(index, 1);
+            assert_eq!(fields, vec!["x".to_string(), "y".to_string()]);
+        }
+        result => panic!("expected a mismatch, got {result:?}"),
+    }
+
+    let nan = [PointXYZ::new(f32::NAN, 0.0, 0.0)];
+    match assert_roundtrip(&nan) {
+        Err(MsgConversionError::RoundtripMismatch { index, fields }) => {
+            assert_eq!(index, 0);
+            assert!(fields.is_empty());
+        }
+        result => panic!("expected a mismatch, got {result:?}"),
+    }
+}
+
+#[test]
+#[cfg(feature = "test-util")]
+fn roundtrip_check_copy() {
+    use ros_pointcloud2::test_util::{assert_roundtrip, assert_roundtrip_copy};
+
+    assert_roundtrip_copy(&[
+        PointXYZI::new(1.0, 2.0, 3.0, 0.5),
+        PointXYZI::new(4.0, 5.0, 6.0, 1.5),
+    ])
+    .unwrap();
+
+    // The compiler aligns `x` to offset 4, but the layout puts the padding after it.
+    #[derive(Debug, PartialEq, Clone, Copy, Default)]
+    #[repr(C)]
+    struct MispaddedPoint {
+        label: u8,
+        x: f32,
+    }
+
+    impl From<RPCL2Point<2>> for MispaddedPoint {
+        fn from(point: RPCL2Point<2>) -> Self {
+            Self {
+                label: point[0].get(),
+                x: point[1].get(),
+            }
+        }
+    }
+
+    impl From<MispaddedPoint> for RPCL2Point<2> {
+        fn from(point: MispaddedPoint) -> Self {
+            [point.label.into(), point.x.into()].into()
+        }
+    }
+
+    unsafe impl PointConvertible<2> for MispaddedPoint {
+        fn layout() -> LayoutDescription {
+            LayoutDescription::new(&[
+                LayoutField::new("label", "u8", 1),
+                LayoutField::new("x", "f32", 4),
+                LayoutField::padding(3),
+            ])
+        }
+    }
+
+    let points = [MispaddedPoint { label: 7, x: 1.5 }];
+    // The iterators only use the layout, so they agree with themselves.
+    assert_roundtrip(&points).unwrap();
+    match assert_roundtrip_copy(&points) {
+        Err(MsgConversionError::RoundtripMismatch { index, fields }) => {
+            assert_eq!(index, 0);
+            assert_eq!(fields, vec!["x".to_string()]);
+        }
+        result => panic!("expected a mismatch, got {result:?}"),
+    }
+}
+
+#[test]
+fn header_frame_id_cow() {
+    use std::borrow::Cow;
+
+    let mut msg = PointCloud2Msg::try_from_vec(vec![PointXYZ::new(1.0, 2.0, 3.0)]).unwrap();
+    msg.header.frame_id = "lidar".into();
+    assert!(matches!(msg.header.frame_id, Cow::Borrowed("lidar")));
+
+    // Forwarding a static frame id does not copy it.
+    let forwarded = msg.clone();
+    assert!(matches!(forwarded.header.frame_id, Cow::Borrowed("lidar")));
+
+    msg.header.frame_id = String::from("map").into();
+    assert!(matches!(msg.header.frame_id, Cow::Owned(_)));
+    assert_eq!(msg.header.frame_id, "map");
+}
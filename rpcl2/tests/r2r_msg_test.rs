@@ -30,3 +30,25 @@ fn convertxyz_r2r_msg() {
     let back_to_type = to_convert.collect::<Vec<PointXYZ>>();
     assert_eq!(copy, back_to_type);
 }
+
+#[cfg(feature = "r2r_msg")]
+#[test]
+fn header_r2r_msg() {
+    use ros_pointcloud2::{points::PointXYZ, PointCloud2Msg};
+
+    use r2r::sensor_msgs::msg::PointCloud2;
+
+    let mut internal_cloud =
+        PointCloud2Msg::try_from_iter(vec![PointXYZ::new(1.0, 2.0, 3.0)]).unwrap();
+    internal_cloud.header.seq = 42;
+    internal_cloud.header.stamp.sec = 7;
+    internal_cloud.header.stamp.nanosec = 9;
+    internal_cloud.header.frame_id = "map".into();
+
+    let r2r_msg_cloud: PointCloud2 = internal_cloud.into();
+    let convert_back_internal: PointCloud2Msg = r2r_msg_cloud.into();
+    assert_eq!(convert_back_internal.header.seq, 0);
+    assert_eq!(convert_back_internal.header.stamp.sec, 7);
+    assert_eq!(convert_back_internal.header.stamp.nanosec, 9);
+    assert_eq!(convert_back_internal.header.frame_id, "map");
+}
@@ -0,0 +1,73 @@
+/// Mirrors the layout of the ROS2 messages that are defined for ros2-client.
+mod my_msgs {
+    pub mod builtin_interfaces {
+        #[derive(Clone, Debug, Default, PartialEq)]
+        pub struct Time {
+            pub sec: i32,
+            pub nanosec: u32,
+        }
+    }
+
+    pub mod std_msgs {
+        #[derive(Clone, Debug, Default, PartialEq)]
+        pub struct Header {
+            pub stamp: super::builtin_interfaces::Time,
+            pub frame_id: String,
+        }
+    }
+
+    pub mod sensor_msgs {
+        #[derive(Clone, Debug, Default, PartialEq)]
+        pub struct PointField {
+            pub name: String,
+            pub offset: u32,
+            pub datatype: u8,
+            pub count: u32,
+        }
+
+        #[derive(Clone, Debug, Default, PartialEq)]
+        pub struct PointCloud2 {
+            pub header: super::std_msgs::Header,
+            pub height: u32,
+            pub width: u32,
+            pub fields: Vec<PointField>,
+            pub is_bigendian: bool,
+            pub point_step: u32,
+            pub row_step: u32,
+            pub data: Vec<u8>,
+            pub is_dense: bool,
+        }
+    }
+}
+
+ros_pointcloud2::impl_pointcloud2_for_ros2_client_native!(crate::my_msgs);
+
+#[test]
+fn convertxyz_ros2_client_native() {
+    use ros_pointcloud2::{points::PointXYZ, PointCloud2Msg};
+
+    let cloud = vec![
+        PointXYZ::new(1.0, 2.0, 3.0),
+        PointXYZ::new(4.0, 5.0, 6.0),
+        PointXYZ::new(7.0, 8.0, 9.0),
+    ];
+    let mut internal_msg = PointCloud2Msg::try_from_iter(cloud.clone()).unwrap();
+    internal_msg.header.seq = 3;
+    internal_msg.header.stamp.sec = -10;
+    internal_msg.header.stamp.nanosec = 20;
+    internal_msg.header.frame_id = "lidar".into();
+
+    let ros_msg: my_msgs::sensor_msgs::PointCloud2 = internal_msg.into();
+    assert_eq!(ros_msg.header.stamp.sec, -10);
+    assert_eq!(ros_msg.header.stamp.nanosec, 20);
+    assert_eq!(ros_msg.header.frame_id, "lidar");
+    assert_eq!(ros_msg.width, 3);
+    assert!(!ros_msg.is_bigendian);
+
+    let to_p2: PointCloud2Msg = ros_msg.into();
+    assert_eq!(to_p2.header.seq, 0);
+    assert_eq!(to_p2.header.stamp.sec, -10);
+    assert_eq!(to_p2.header.frame_id, "lidar");
+    let cloud_back: Vec<PointXYZ> = to_p2.try_into_iter().unwrap().collect();
+    assert_eq!(cloud, cloud_back);
+}
@@ -28,3 +28,23 @@ fn convertxyz_rosrust_msg() {
     let back_to_type = to_convert.collect::<Vec<PointXYZ>>();
     assert_eq!(copy, back_to_type);
 }
+
+#[cfg(feature = "rosrust_msg")]
+#[test]
+fn header_rosrust_msg() {
+    use ros_pointcloud2::{points::PointXYZ, PointCloud2Msg};
+
+    let mut internal_cloud =
+        PointCloud2Msg::try_from_iter(vec![PointXYZ::new(1.0, 2.0, 3.0)]).unwrap();
+    internal_cloud.header.seq = 42;
+    internal_cloud.header.stamp.sec = 7;
+    internal_cloud.header.stamp.nanosec = 9;
+    internal_cloud.header.frame_id = "map".into();
+
+    let rosrust_msg_cloud: rosrust_msg::sensor_msgs::PointCloud2 = internal_cloud.into();
+    let convert_back_internal: PointCloud2Msg = rosrust_msg_cloud.into();
+    assert_eq!(convert_back_internal.header.seq, 42);
+    assert_eq!(convert_back_internal.header.stamp.sec, 7);
+    assert_eq!(convert_back_internal.header.stamp.nanosec, 9);
+    assert_eq!(convert_back_internal.header.frame_id, "map");
+}